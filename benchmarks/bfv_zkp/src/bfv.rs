@@ -6,9 +6,9 @@ use ark_poly::univariate::DensePolynomial;
 use sunscreen::{
     types::zkp::{Mod, NativeField, RnsRingPolynomial, Scale, ToBinary, ToResidues},
     zkp_program, Application, BackendField, Compiler, Runtime, ZkpApplication, ZkpBackend,
-    ZkpProgramInput, ZkpRuntime,
+    ZkpProgramInput, ZkpProof, ZkpRuntime,
 };
-use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, BigInt as ZkpBigInt, Proof};
+use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, BigInt as ZkpBigInt};
 
 use crate::poly_ring::PolyRing;
 
@@ -369,7 +369,7 @@ pub fn prove_public_encryption(
     message: &Poly,
     encryption_data: &(Ciphertext, Noise, Poly),
     public_key: &PublicKey,
-) -> Proof {
+) -> ZkpProof {
     let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
 
     let prog = app.get_zkp_program(prove_enc).unwrap();
@@ -395,7 +395,7 @@ pub fn prove_public_encryption(
 
 pub fn verify_public_encryption(
     app: &ZkpApplication,
-    proof: &Proof,
+    proof: &ZkpProof,
     ciphertext: &Ciphertext,
     public_key: &PublicKey,
 ) {
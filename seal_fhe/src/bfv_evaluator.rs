@@ -245,6 +245,14 @@ impl Evaluator for BFVEvaluator {
 
         Ok(())
     }
+
+    fn transform_to_ntt(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.transform_to_ntt(a)
+    }
+
+    fn transform_from_ntt(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        self.0.transform_from_ntt(a)
+    }
 }
 
 #[cfg(test)]
@@ -968,4 +976,27 @@ mod tests {
             assert_eq!(a[4097], c[1]);
         });
     }
+
+    #[test]
+    fn ntt_transform_round_trips() {
+        run_bfv_test(|decryptor, encoder, encryptor, evaluator, _| {
+            let a = make_vec(&encoder);
+            let a_p = encoder.encode_signed(&a).unwrap();
+            let a_c = encryptor.encrypt(&a_p).unwrap();
+
+            let a_ntt = evaluator.transform_to_ntt(&a_c).unwrap();
+
+            assert!(a_ntt.is_ntt_form());
+            assert_ne!(a_ntt.data().unwrap(), a_c.data().unwrap());
+
+            let b_c = evaluator.transform_from_ntt(&a_ntt).unwrap();
+
+            assert!(!b_c.is_ntt_form());
+            assert_eq!(b_c.data().unwrap(), a_c.data().unwrap());
+
+            let b_p = decryptor.decrypt(&b_c).unwrap();
+
+            assert_eq!(a, encoder.decode_signed(&b_p).unwrap());
+        });
+    }
 }
@@ -85,6 +85,22 @@ impl Context {
     pub fn get_handle(&self) -> *mut c_void {
         self.handle
     }
+
+    /**
+     * Returns the parms id of the first set of parameters in the modulus
+     * switching chain (i.e. the parameters fresh ciphertexts and plaintexts
+     * are created under).
+     */
+    pub fn get_first_parms_id(&self) -> [u64; 4] {
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::SEALContext_FirstParmsId(self.handle, parms_id.as_mut_ptr())
+        })
+        .expect("Internal error in Context::get_first_parms_id().");
+
+        parms_id
+    }
 }
 
 impl Drop for Context {
@@ -215,6 +215,125 @@ impl Drop for BFVEncoder {
     }
 }
 
+/**
+ * Encodes vectors of real numbers into plaintexts for the CKKS scheme. If
+ * the polynomial modulus degree is `N`, the encoder provides `N/2` slots.
+ * Values are multiplied by a caller-chosen scale and rounded to integers
+ * during encoding, so the scale determines the precision of the encoded
+ * values. Homomorphic operations act on the slots element-wise.
+ *
+ * # Remarks
+ * CKKS is an approximate scheme: decoding returns values close to, but
+ * generally not exactly equal to, the encoded values. Each multiplication
+ * squares the scale, which you should bring back down with
+ * [`CKKSEvaluator::rescale_to_next`](crate::CKKSEvaluator::rescale_to_next).
+ */
+pub struct CKKSEncoder {
+    handle: *mut c_void,
+    parms_id: [u64; 4],
+}
+
+unsafe impl Sync for CKKSEncoder {}
+unsafe impl Send for CKKSEncoder {}
+
+impl CKKSEncoder {
+    /**
+     * Creates a CKKSEncoder. The given context must use the CKKS scheme.
+     *
+     * * `ctx` - The Context
+     */
+    pub fn new(ctx: &Context) -> Result<Self> {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_Create(ctx.get_handle(), &mut handle) })?;
+
+        Ok(Self {
+            handle,
+            parms_id: ctx.get_first_parms_id(),
+        })
+    }
+
+    /**
+     * Encodes the given values into a plaintext at the top of the modulus
+     * switching chain. The number of values must be at most the slot count;
+     * any remaining slots are zero.
+     *
+     * * `data` - The values to encode
+     * * `scale` - The scale to multiply each value by before rounding
+     */
+    pub fn encode_f64(&self, data: &[f64], scale: f64) -> Result<Plaintext> {
+        let plaintext = Plaintext::new()?;
+        let mut parms_id = self.parms_id;
+
+        // We pinky promise SEAL won't mutate data, the C bindings just aren't
+        // const correct.
+        convert_seal_error(unsafe {
+            bindgen::CKKSEncoder_Encode1(
+                self.handle,
+                data.len() as u64,
+                data.as_ptr() as *mut f64,
+                parms_id.as_mut_ptr(),
+                scale,
+                plaintext.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(plaintext)
+    }
+
+    /**
+     * Inverse of encode. Decodes the slots of the given plaintext using
+     * the plaintext's scale.
+     *
+     * * `plaintext` - The plaintext to decode
+     */
+    pub fn decode_f64(&self, plaintext: &Plaintext) -> Result<Vec<f64>> {
+        let mut data = Vec::with_capacity(self.get_slot_count());
+        let data_ptr = data.as_mut_ptr();
+        let mut size: u64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::CKKSEncoder_Decode1(
+                self.handle,
+                plaintext.get_handle(),
+                &mut size,
+                data_ptr,
+                null_mut(),
+            )
+        })?;
+
+        if data.capacity() < size as usize {
+            panic!("Allocation overflow CKKSEncoder::decode_f64");
+        }
+
+        unsafe {
+            data.set_len(size as usize);
+        }
+
+        Ok(data)
+    }
+
+    /**
+     * Returns the number of slots this encoder provides.
+     */
+    pub fn get_slot_count(&self) -> usize {
+        let mut count: u64 = 0;
+
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_SlotCount(self.handle, &mut count) })
+            .expect("Internal error in CKKSEncoder::get_slot_count().");
+
+        count as usize
+    }
+}
+
+impl Drop for CKKSEncoder {
+    fn drop(&mut self) {
+        convert_seal_error(unsafe { bindgen::CKKSEncoder_Destroy(self.handle) })
+            .expect("Internal error in CKKSEncoder::drop.");
+    }
+}
+
 /**
  * Creates an encoder that can turn i64 or u64 values into a Plaintext. This encoder
  * is not recommended as it's an inefficient use of the plain modulus space.
@@ -393,4 +512,32 @@ mod tests {
 
         assert_eq!(encoder.decode_signed(&p).unwrap(), 42);
     }
+
+    #[test]
+    fn can_encode_and_decode_ckks() {
+        let params = CkksEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(CoefficientModulus::create(8192, &[60, 40, 40, 60]).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, true, SecurityLevel::TC128).unwrap();
+
+        let encoder = CKKSEncoder::new(&ctx).unwrap();
+
+        assert_eq!(encoder.get_slot_count(), 4096);
+
+        let data = (0..encoder.get_slot_count())
+            .map(|x| x as f64 / 3.)
+            .collect::<Vec<_>>();
+
+        let plaintext = encoder.encode_f64(&data, 2f64.powi(40)).unwrap();
+        let data_2 = encoder.decode_f64(&plaintext).unwrap();
+
+        assert_eq!(data.len(), data_2.len());
+
+        for (a, b) in data.iter().zip(data_2.iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
 }
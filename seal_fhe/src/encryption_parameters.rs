@@ -316,6 +316,86 @@ impl Default for BfvEncryptionParametersBuilder {
     }
 }
 
+/**
+ * Represents a builder that sets up and creates encryption parameters for
+ * the CKKS scheme. Unlike BFV, CKKS has no plaintext modulus; precision is
+ * instead determined by the scale used when encoding values and the sizes of
+ * the primes in the coefficient modulus.
+ */
+pub struct CkksEncryptionParametersBuilder {
+    poly_modulus_degree: Option<u64>,
+    coefficient_modulus: CoefficientModulusType,
+}
+
+impl CkksEncryptionParametersBuilder {
+    /**
+     * Creates a new builder.
+     */
+    pub fn new() -> Self {
+        Self {
+            poly_modulus_degree: None,
+            coefficient_modulus: CoefficientModulusType::NotSet,
+        }
+    }
+
+    /**
+     * Set the degree of the polynomial used in the CKKS scheme. CKKS
+     * provides `degree / 2` slots of complex numbers.
+     */
+    pub fn set_poly_modulus_degree(mut self, degree: u64) -> Self {
+        self.poly_modulus_degree = Some(degree);
+        self
+    }
+
+    /**
+     * Sets the coefficient modulus parameter. Each rescale operation drops
+     * the last prime in the coefficient modulus, so the middle primes should
+     * be roughly the size of the scale (e.g. `[60, 40, 40, 60]` with a scale
+     * of `2^40`). Create these with [`CoefficientModulus::create`](crate::CoefficientModulus::create).
+     */
+    pub fn set_coefficient_modulus(mut self, modulus: Vec<Modulus>) -> Self {
+        self.coefficient_modulus = CoefficientModulusType::Modulus(modulus);
+        self
+    }
+
+    /**
+     * Validate the parameter choices and return the encryption parameters.
+     */
+    pub fn build(self) -> Result<EncryptionParameters, Error> {
+        let params = EncryptionParameters::new(SchemeType::Ckks)?;
+
+        convert_seal_error(unsafe {
+            bindgen::EncParams_SetPolyModulusDegree(
+                params.handle,
+                self.poly_modulus_degree.ok_or(Error::DegreeNotSet)?,
+            )
+        })?;
+
+        match self.coefficient_modulus {
+            CoefficientModulusType::NotSet => return Err(Error::CoefficientModulusNotSet),
+            CoefficientModulusType::Modulus(m) => {
+                convert_seal_error(unsafe {
+                    let modulus_ref = m
+                        .iter()
+                        .map(|m| m.get_handle())
+                        .collect::<Vec<*mut c_void>>();
+                    let modulus_ptr = modulus_ref.as_ptr() as *mut *mut c_void;
+
+                    bindgen::EncParams_SetCoeffModulus(params.handle, m.len() as u64, modulus_ptr)
+                })?;
+            }
+        };
+
+        Ok(params)
+    }
+}
+
+impl Default for CkksEncryptionParametersBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for EncryptionParameters {
     fn drop(&mut self) {
         unsafe { bindgen::EncParams_Destroy(self.handle) };
@@ -365,4 +445,17 @@ mod tests {
         assert_eq!(modulus[3].value(), 1125899906629633);
         assert_eq!(modulus[4].value(), 1125899906826241);
     }
+
+    #[test]
+    fn can_build_ckks_params() {
+        let params = CkksEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(CoefficientModulus::create(8192, &[60, 40, 40, 60]).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(params.get_poly_modulus_degree(), 8192);
+        assert_eq!(params.get_scheme(), SchemeType::Ckks);
+        assert_eq!(params.get_coefficient_modulus().len(), 4);
+    }
 }
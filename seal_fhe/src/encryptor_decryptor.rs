@@ -3,7 +3,7 @@ use std::ptr::null_mut;
 
 use crate::bindgen;
 use crate::error::*;
-use crate::{Ciphertext, Context, Plaintext, PublicKey, SecretKey};
+use crate::{Ciphertext, CompactCiphertext, Context, Plaintext, PublicKey, SecretKey};
 
 /**
  *
@@ -88,6 +88,26 @@ impl Encryptor {
         Ok(Encryptor { handle })
     }
 
+    /**
+     * Creates an Encryptor instance initialized with the specified SEALContext
+     * and secret key. The resulting Encryptor only supports symmetric-key
+     * encryption.
+     */
+    pub fn with_secret_key(ctx: &Context, secret_key: &SecretKey) -> Result<Encryptor> {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe {
+            bindgen::Encryptor_Create(
+                ctx.get_handle(),
+                null_mut(),
+                secret_key.get_handle(),
+                &mut handle,
+            )
+        })?;
+
+        Ok(Encryptor { handle })
+    }
+
     /**
      *
      * Encrypts a plaintext with the public key and returns the ciphertext as
@@ -115,6 +135,53 @@ impl Encryptor {
 
         Ok(ciphertext)
     }
+
+    /**
+     * Encrypts a plaintext with the secret key and returns the ciphertext.
+     * Requires this Encryptor to have been created with a secret key.
+     *
+     * * `plainext` - The plaintext to encrypt.
+     */
+    pub fn encrypt_symmetric(&self, plaintext: &Plaintext) -> Result<Ciphertext> {
+        self.encrypt_symmetric_internal(plaintext, false)
+    }
+
+    /**
+     * Encrypts a plaintext with the secret key and returns a compact
+     * ciphertext.
+     *
+     * Half of the ciphertext data is pseudo-randomly generated from a seed to
+     * reduce the object size. The resulting serializable object cannot be
+     * used directly and is meant to be serialized for the size reduction to
+     * have an impact. Deserializing it yields a regular [`Ciphertext`].
+     *
+     * * `plainext` - The plaintext to encrypt.
+     */
+    pub fn encrypt_symmetric_compact(&self, plaintext: &Plaintext) -> Result<CompactCiphertext> {
+        Ok(CompactCiphertext(
+            self.encrypt_symmetric_internal(plaintext, true)?,
+        ))
+    }
+
+    fn encrypt_symmetric_internal(
+        &self,
+        plaintext: &Plaintext,
+        save_seed: bool,
+    ) -> Result<Ciphertext> {
+        let ciphertext = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Encryptor_EncryptSymmetric(
+                self.handle,
+                plaintext.get_handle(),
+                save_seed,
+                ciphertext.get_handle(),
+                null_mut(),
+            )
+        })?;
+
+        Ok(ciphertext)
+    }
 }
 
 impl Drop for Encryptor {
@@ -386,4 +453,50 @@ mod tests {
 
         assert_eq!(data, data_2);
     }
+
+    #[test]
+    fn can_encrypt_symmetric_and_decrypt() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 20).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let encoder = BFVEncoder::new(&ctx).unwrap();
+
+        let data = (0..encoder.get_slot_count())
+            .map(|i| i as u64)
+            .collect::<Vec<_>>();
+
+        let plaintext = encoder.encode_unsigned(&data).unwrap();
+
+        let secret_key = gen.secret_key();
+
+        let encryptor = Encryptor::with_secret_key(&ctx, &secret_key).unwrap();
+        let decryptor = Decryptor::new(&ctx, &secret_key).unwrap();
+
+        let ciphertext = encryptor.encrypt_symmetric(&plaintext).unwrap();
+        let decrypted = decryptor.decrypt(&ciphertext).unwrap();
+
+        assert_eq!(data, encoder.decode_unsigned(&decrypted).unwrap());
+
+        let compact = encryptor
+            .encrypt_symmetric_compact(&plaintext)
+            .unwrap()
+            .as_bytes()
+            .unwrap();
+
+        assert!(compact.len() < ciphertext.as_bytes().unwrap().len());
+
+        let expanded = Ciphertext::from_bytes(&ctx, &compact).unwrap();
+        let decrypted = decryptor.decrypt(&expanded).unwrap();
+
+        assert_eq!(data, encoder.decode_unsigned(&decrypted).unwrap());
+    }
 }
@@ -277,4 +277,20 @@ pub trait Evaluator {
      * * `galoisKeys` - The Galois keys
      */
     fn rotate_columns_inplace(&self, a: &Ciphertext, galois_keys: &GaloisKeys) -> Result<()>;
+
+    /**
+     * Transforms a ciphertext to NTT form, where multiplying polynomials
+     * becomes coefficient-wise multiplication.
+     *
+     * * `a` - The ciphertext to transform. Must not already be in NTT form.
+     */
+    fn transform_to_ntt(&self, a: &Ciphertext) -> Result<Ciphertext>;
+
+    /**
+     * Transforms a ciphertext in NTT form back to coefficient form. This is
+     * the inverse of [`transform_to_ntt`](Self::transform_to_ntt).
+     *
+     * * `a` - The ciphertext in NTT form to transform.
+     */
+    fn transform_from_ntt(&self, a: &Ciphertext) -> Result<Ciphertext>;
 }
@@ -403,5 +403,23 @@ impl EvaluatorBase {
         Ok(())
     }
 
-    // TODO: NTT transform.
+    pub(crate) fn transform_to_ntt(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_TransformToNTT2(self.handle, a.get_handle(), out.get_handle())
+        })?;
+
+        Ok(out)
+    }
+
+    pub(crate) fn transform_from_ntt(&self, a: &Ciphertext) -> Result<Ciphertext> {
+        let out = Ciphertext::new()?;
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_TransformFromNTT(self.handle, a.get_handle(), out.get_handle())
+        })?;
+
+        Ok(out)
+    }
 }
@@ -3,6 +3,7 @@ use std::ptr::null_mut;
 
 use crate::bindgen;
 use crate::error::*;
+use crate::plaintext_ciphertext::{ciphertext_data, set_ciphertext_data};
 use crate::serialization::CompressionType;
 use crate::{Context, FromBytes, ToBytes};
 
@@ -171,6 +172,56 @@ impl KeyGenerator {
         self.create_galois_keys_internal(false)
     }
 
+    /**
+     * Generates Galois keys for only the given rotation steps.
+     *
+     * # Remarks
+     * Positive steps rotate left and negative steps rotate right. In BFV, a step
+     * of 0 generates the key for swapping rows (i.e. column rotation). The
+     * resulting keys can only perform rotations by exactly these steps, but are
+     * much smaller and faster to generate than those from
+     * [`create_galois_keys`](Self::create_galois_keys) when a program only
+     * uses a few rotation amounts.
+     */
+    pub fn create_galois_keys_from_steps(&self, steps: &[i32]) -> Result<GaloisKeys> {
+        self.create_galois_keys_from_steps_internal(steps, false)
+    }
+
+    /**
+     * Generates Galois keys for only the given rotation steps as a serializable
+     * object. See [`create_galois_keys_from_steps`](Self::create_galois_keys_from_steps)
+     * and [`create_compact_galois_keys`](Self::create_compact_galois_keys).
+     */
+    pub fn create_compact_galois_keys_from_steps(
+        &self,
+        steps: &[i32],
+    ) -> Result<CompactGaloisKeys> {
+        Ok(CompactGaloisKeys(
+            self.create_galois_keys_from_steps_internal(steps, true)?,
+        ))
+    }
+
+    fn create_galois_keys_from_steps_internal(
+        &self,
+        steps: &[i32],
+        save_seed: bool,
+    ) -> Result<GaloisKeys> {
+        let mut handle = null_mut();
+        let mut steps = steps.to_owned();
+
+        convert_seal_error(unsafe {
+            bindgen::KeyGenerator_CreateGaloisKeysFromSteps(
+                self.handle,
+                steps.len() as u64,
+                steps.as_mut_ptr(),
+                save_seed,
+                &mut handle,
+            )
+        })?;
+
+        Ok(GaloisKeys { handle })
+    }
+
     fn create_galois_keys_internal(&self, save_seed: bool) -> Result<GaloisKeys> {
         let mut handle = null_mut();
 
@@ -268,6 +319,33 @@ impl PublicKey {
     pub fn get_handle(&self) -> *mut c_void {
         self.handle
     }
+
+    /**
+     * Returns the key's 2 polynomials in RNS form, laid out as in
+     * [`Ciphertext::data`](crate::Ciphertext::data). Keys are stored in NTT
+     * form modulo every prime in the coefficient modulus, including the
+     * special prime.
+     */
+    pub fn data(&self) -> Result<Vec<u64>> {
+        ciphertext_data(self.ciphertext_handle()?)
+    }
+
+    /**
+     * Overwrites the key's polynomials with the given RNS data, laid out as
+     * in [`data`](Self::data).
+     */
+    pub fn set_data(&mut self, data: &[u64]) -> Result<()> {
+        set_ciphertext_data(self.ciphertext_handle()?, data)
+    }
+
+    fn ciphertext_handle(&self) -> Result<*mut c_void> {
+        let mut handle: *mut c_void = null_mut();
+
+        // This borrows the key's internal ciphertext, so we mustn't free it.
+        convert_seal_error(unsafe { bindgen::PublicKey_Data(self.handle, &mut handle) })?;
+
+        Ok(handle)
+    }
 }
 
 impl Drop for PublicKey {
@@ -322,6 +400,28 @@ impl SecretKey {
         Ok(Self { handle })
     }
 
+    /**
+     * Returns the key's polynomial in RNS form. The key is stored in NTT
+     * form modulo every prime in the coefficient modulus, including the
+     * special prime.
+     */
+    pub fn data(&self) -> Result<Vec<u64>> {
+        let mut plaintext: *mut c_void = null_mut();
+        let mut len: u64 = 0;
+
+        // This borrows the key's internal plaintext, so we mustn't free it.
+        convert_seal_error(unsafe { bindgen::SecretKey_Data(self.handle, &mut plaintext) })?;
+        convert_seal_error(unsafe { bindgen::Plaintext_CoeffCount(plaintext, &mut len) })?;
+
+        let mut data = vec![0u64; len as usize];
+
+        for (i, x) in data.iter_mut().enumerate() {
+            convert_seal_error(unsafe { bindgen::Plaintext_CoeffAt(plaintext, i as u64, x) })?;
+        }
+
+        Ok(data)
+    }
+
     /**
      * Returns the handle to the underlying SEAL object.
      */
@@ -466,6 +566,22 @@ impl RelinearizationKeys {
         Ok(Self { handle })
     }
 
+    /**
+     * Returns the key switching keys. SEAL stores the key for `s^2` at
+     * index 0, and each key contains one [`PublicKey`] per data prime.
+     */
+    pub fn key_lists(&self) -> Result<Vec<Vec<PublicKey>>> {
+        kswitch_key_lists(self.handle)
+    }
+
+    /**
+     * Replaces the key switching keys with copies of the given keys, laid
+     * out as in [`key_lists`](Self::key_lists).
+     */
+    pub fn set_key_lists(&mut self, key_lists: &[Vec<PublicKey>]) -> Result<()> {
+        set_kswitch_key_lists(self.handle, key_lists)
+    }
+
     /**
      * Returns the key as a byte array.
      */
@@ -629,6 +745,71 @@ impl GaloisKeys {
 
         Ok(Self { handle })
     }
+
+    /**
+     * Returns the key switching keys. SEAL stores the key for Galois
+     * element `g` at index `(g - 1) / 2`, leaving the lists at unused
+     * indices empty. Each key contains one [`PublicKey`] per data prime.
+     */
+    pub fn key_lists(&self) -> Result<Vec<Vec<PublicKey>>> {
+        kswitch_key_lists(self.handle)
+    }
+
+    /**
+     * Replaces the key switching keys with copies of the given keys, laid
+     * out as in [`key_lists`](Self::key_lists).
+     */
+    pub fn set_key_lists(&mut self, key_lists: &[Vec<PublicKey>]) -> Result<()> {
+        set_kswitch_key_lists(self.handle, key_lists)
+    }
+}
+
+fn kswitch_key_lists(handle: *mut c_void) -> Result<Vec<Vec<PublicKey>>> {
+    let mut num_lists: u64 = 0;
+
+    convert_seal_error(unsafe { bindgen::KSwitchKeys_RawSize(handle, &mut num_lists) })?;
+
+    (0..num_lists)
+        .map(|i| {
+            let mut count: u64 = 0;
+
+            convert_seal_error(unsafe {
+                bindgen::KSwitchKeys_GetKeyList(handle, i, &mut count, null_mut())
+            })?;
+
+            let mut key_list: Vec<*mut c_void> = vec![null_mut(); count as usize];
+
+            // SEAL allocates a copy of each key, which PublicKey then owns.
+            convert_seal_error(unsafe {
+                bindgen::KSwitchKeys_GetKeyList(handle, i, &mut count, key_list.as_mut_ptr())
+            })?;
+
+            Ok(key_list
+                .into_iter()
+                .map(|handle| PublicKey { handle })
+                .collect())
+        })
+        .collect()
+}
+
+fn set_kswitch_key_lists(handle: *mut c_void, key_lists: &[Vec<PublicKey>]) -> Result<()> {
+    convert_seal_error(unsafe {
+        bindgen::KSwitchKeys_ClearDataAndReserve(handle, key_lists.len() as u64)
+    })?;
+
+    for keys in key_lists {
+        let mut key_list = keys.iter().map(|k| k.handle).collect::<Vec<_>>();
+
+        // SEAL rejects a null list, even when it's empty, so pad the list
+        // to make sure it's allocated.
+        key_list.push(null_mut());
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_AddKeyList(handle, keys.len() as u64, key_list.as_mut_ptr())
+        })?;
+    }
+
+    Ok(())
 }
 
 impl PartialEq for GaloisKeys {
@@ -810,6 +991,26 @@ mod tests {
         gen.create_galois_keys().unwrap();
     }
 
+    #[test]
+    fn galois_keys_from_steps_are_smaller() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::bfv_default(8192, SecurityLevel::TC128).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let all = gen.create_galois_keys().unwrap();
+        let some = gen.create_galois_keys_from_steps(&[0, 1]).unwrap();
+
+        assert!(some.as_bytes().unwrap().len() < all.as_bytes().unwrap().len());
+    }
+
     #[test]
     fn can_init_from_existing_secret_key() {
         let params = BfvEncryptionParametersBuilder::new()
@@ -837,4 +1038,69 @@ mod tests {
             serde_json::to_string(&secret_key).unwrap()
         );
     }
+
+    #[test]
+    fn public_key_data_is_at_key_level() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(2048)
+            .set_coefficient_modulus(CoefficientModulus::create(2048, &[27, 27]).unwrap())
+            .set_plain_modulus_u64(1153)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let public_key = gen.create_public_key();
+        let data = public_key.data().unwrap();
+
+        assert_eq!(data.len(), 2 * 2 * 2048);
+        assert_eq!(gen.secret_key().data().unwrap().len(), 2 * 2048);
+
+        let mut other = gen.create_public_key();
+
+        assert_ne!(other.data().unwrap(), data);
+
+        other.set_data(&data).unwrap();
+
+        assert!(other == public_key);
+    }
+
+    #[test]
+    fn can_get_and_set_key_lists() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let relin_keys = gen.create_relinearization_keys().unwrap();
+        let key_lists = relin_keys.key_lists().unwrap();
+
+        // One key for s^2, made of one key per data prime.
+        assert_eq!(key_lists.len(), 1);
+        assert_eq!(key_lists[0].len(), 4);
+
+        let mut other = gen.create_relinearization_keys().unwrap();
+        other.set_key_lists(&key_lists).unwrap();
+
+        assert!(other == relin_keys);
+
+        let galois_keys = gen.create_galois_keys_from_steps(&[1]).unwrap();
+        let key_lists = galois_keys.key_lists().unwrap();
+
+        assert!(key_lists.iter().any(|k| k.is_empty()));
+        assert_eq!(key_lists.iter().filter(|k| !k.is_empty()).count(), 1);
+
+        let mut other = gen.create_galois_keys_from_steps(&[1]).unwrap();
+        other.set_key_lists(&key_lists).unwrap();
+
+        assert!(other == galois_keys);
+    }
 }
@@ -9,9 +9,18 @@
 //! should safely hold. The internal handles should be of little use to you anyways.
 //!
 //! This crate intentionally omits more esoteric use cases to streamline the API and
-//! is currently incomplete (e.g. CKKS is not currently supported). If any underlying
-//! SEAL API you care about is missing, please add it in a pull request or file
-//! an [issue](https://github.com/Sunscreen-tech/Sunscreen/issues).
+//! is currently incomplete. If any underlying SEAL API you care about is missing,
+//! please add it in a pull request or file an
+//! [issue](https://github.com/Sunscreen-tech/Sunscreen/issues).
+//!
+//! # CKKS
+//! This crate exposes SEAL's CKKS scheme through
+//! [`CkksEncryptionParametersBuilder`], [`CKKSEncoder`] and [`CKKSEvaluator`].
+//! This is the only CKKS support in Sunscreen. The compiler and runtime only
+//! target BFV, so there is no CKKS `SchemeType`, no CKKS types for
+//! `#[fhe_program]`s, no rescale operation in FHE program graphs and no
+//! scale-aware parameter search. Using CKKS means choosing parameters and
+//! managing scales and rescaling by hand with the types in this crate.
 
 #![warn(missing_docs)]
 
@@ -44,6 +53,7 @@ mod serialization {
 }
 
 mod bfv_evaluator;
+mod ckks_evaluator;
 mod context;
 mod encoder;
 mod encryption_parameters;
@@ -56,15 +66,19 @@ mod modulus;
 mod plaintext_ciphertext;
 
 pub use bfv_evaluator::BFVEvaluator;
+pub use ckks_evaluator::CKKSEvaluator;
 pub use context::Context;
-pub use encoder::{BFVEncoder, BFVScalarEncoder};
+pub use encoder::{BFVEncoder, BFVScalarEncoder, CKKSEncoder};
 pub use encryption_parameters::*;
 pub use encryptor_decryptor::{Decryptor, Encryptor};
 pub use error::{Error, Result};
 pub use evaluator::Evaluator;
-pub use key_generator::{GaloisKeys, KeyGenerator, PublicKey, RelinearizationKeys, SecretKey};
+pub use key_generator::{
+    CompactGaloisKeys, CompactPublicKey, CompactRelinearizationKeys, GaloisKeys, KeyGenerator,
+    PublicKey, RelinearizationKeys, SecretKey,
+};
 pub use modulus::{CoefficientModulus, Modulus, PlainModulus, SecurityLevel};
-pub use plaintext_ciphertext::{Ciphertext, Plaintext};
+pub use plaintext_ciphertext::{Ciphertext, CompactCiphertext, Plaintext};
 
 /**
  * A trait for converting objects into byte arrays.
@@ -272,6 +272,19 @@ impl Plaintext {
 
         size as usize
     }
+
+    /**
+     * Returns the scale of this plaintext. Only meaningful for plaintexts
+     * encoded with the CKKS scheme.
+     */
+    pub fn scale(&self) -> f64 {
+        let mut scale: f64 = 0.;
+
+        convert_seal_error(unsafe { bindgen::Plaintext_Scale(self.handle, &mut scale) })
+            .expect("Fatal error in Plaintext::scale().");
+
+        scale
+    }
 }
 
 impl Drop for Plaintext {
@@ -351,6 +364,155 @@ impl Ciphertext {
 
         size
     }
+
+    /**
+     * Returns the scale of this ciphertext. Only meaningful for ciphertexts
+     * under the CKKS scheme.
+     */
+    pub fn scale(&self) -> f64 {
+        let mut scale: f64 = 0.;
+
+        convert_seal_error(unsafe { bindgen::Ciphertext_Scale(self.handle, &mut scale) })
+            .expect("Fatal error in Ciphertext::scale().");
+
+        scale
+    }
+
+    /**
+     * Sets the scale of this ciphertext. Only meaningful for ciphertexts
+     * under the CKKS scheme. This doesn't change the underlying
+     * polynomials; it only changes how they're interpreted when decoding,
+     * so you should only call this to fix up a scale that has drifted by
+     * rescaling (e.g. before adding two ciphertexts).
+     */
+    pub fn set_scale(&mut self, scale: f64) {
+        convert_seal_error(unsafe { bindgen::Ciphertext_SetScale(self.handle, scale) })
+            .expect("Fatal error in Ciphertext::set_scale().");
+    }
+
+    /**
+     * Returns the number of coefficients in each polynomial.
+     */
+    pub fn poly_modulus_degree(&self) -> u64 {
+        let mut degree: u64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::Ciphertext_PolyModulusDegree(self.handle, &mut degree)
+        })
+        .expect("Fatal error in Ciphertext::poly_modulus_degree().");
+
+        degree
+    }
+
+    /**
+     * Returns the number of primes in the coefficient modulus at this
+     * ciphertext's level.
+     */
+    pub fn coeff_modulus_size(&self) -> u64 {
+        let mut size: u64 = 0;
+
+        convert_seal_error(unsafe { bindgen::Ciphertext_CoeffModulusSize(self.handle, &mut size) })
+            .expect("Fatal error in Ciphertext::coeff_modulus_size().");
+
+        size
+    }
+
+    /**
+     * Returns whether this ciphertext's polynomials are in NTT form.
+     */
+    pub fn is_ntt_form(&self) -> bool {
+        let mut is_ntt_form = false;
+
+        convert_seal_error(unsafe { bindgen::Ciphertext_IsNTTForm(self.handle, &mut is_ntt_form) })
+            .expect("Fatal error in Ciphertext::is_ntt_form().");
+
+        is_ntt_form
+    }
+
+    /**
+     * Returns the polynomials in RNS form. Each polynomial is stored
+     * consecutively, and within a polynomial, the residues of every
+     * coefficient modulo the first prime come first, then the second prime
+     * and so on.
+     */
+    pub fn data(&self) -> Result<Vec<u64>> {
+        ciphertext_data(self.handle)
+    }
+
+    /**
+     * Overwrites the polynomials with the given RNS data, laid out as in
+     * [`data`](Self::data). This doesn't change the size, level or NTT form
+     * of the ciphertext.
+     *
+     * # Remarks
+     * Returns [`Error::InvalidArgument`](crate::Error::InvalidArgument) if
+     * `data` doesn't have exactly as many values as the ciphertext.
+     */
+    pub fn set_data(&mut self, data: &[u64]) -> Result<()> {
+        set_ciphertext_data(self.handle, data)
+    }
+}
+
+fn ciphertext_data_len(handle: *mut c_void) -> Result<usize> {
+    let mut size: u64 = 0;
+    let mut degree: u64 = 0;
+    let mut coeff_modulus_size: u64 = 0;
+
+    convert_seal_error(unsafe { bindgen::Ciphertext_Size(handle, &mut size) })?;
+    convert_seal_error(unsafe { bindgen::Ciphertext_PolyModulusDegree(handle, &mut degree) })?;
+    convert_seal_error(unsafe {
+        bindgen::Ciphertext_CoeffModulusSize(handle, &mut coeff_modulus_size)
+    })?;
+
+    Ok((size * degree * coeff_modulus_size) as usize)
+}
+
+/**
+ * Reads the RNS data of the SEAL ciphertext behind `handle`. Public keys and
+ * key switching keys are ciphertexts internally, so this works on the
+ * handles they expose too.
+ */
+pub(crate) fn ciphertext_data(handle: *mut c_void) -> Result<Vec<u64>> {
+    let len = ciphertext_data_len(handle)?;
+    let mut data = vec![0u64; len];
+
+    for (i, x) in data.iter_mut().enumerate() {
+        convert_seal_error(unsafe { bindgen::Ciphertext_GetDataAt1(handle, i as u64, x) })?;
+    }
+
+    Ok(data)
+}
+
+/**
+ * Overwrites the RNS data of the SEAL ciphertext behind `handle`.
+ */
+pub(crate) fn set_ciphertext_data(handle: *mut c_void, data: &[u64]) -> Result<()> {
+    if data.len() != ciphertext_data_len(handle)? {
+        return Err(crate::Error::InvalidArgument);
+    }
+
+    for (i, x) in data.iter().enumerate() {
+        convert_seal_error(unsafe { bindgen::Ciphertext_SetDataAt(handle, i as u64, *x) })?;
+    }
+
+    Ok(())
+}
+
+/**
+ * A symmetric-key ciphertext that stores a random number seed in place of
+ * one of its polynomials. This form isn't directly usable, but serializes
+ * in a very compact representation that deserializes into a regular
+ * [`Ciphertext`].
+ */
+pub struct CompactCiphertext(pub(crate) Ciphertext);
+
+impl CompactCiphertext {
+    /**
+     * Returns the ciphertext as a byte array.
+     */
+    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+        self.0.as_bytes()
+    }
 }
 
 impl PartialEq for Ciphertext {
@@ -417,6 +579,7 @@ impl Drop for Ciphertext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::*;
 
     #[test]
     fn can_create_and_destroy_ciphertext() {
@@ -432,6 +595,49 @@ mod tests {
         std::mem::drop(plaintext);
     }
 
+    #[test]
+    fn can_get_and_set_ciphertext_data() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(2048)
+            .set_coefficient_modulus(CoefficientModulus::create(2048, &[27, 27]).unwrap())
+            .set_plain_modulus_u64(1153)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+        let public_key = gen.create_public_key();
+        let encryptor = Encryptor::with_public_key(&ctx, &public_key).unwrap();
+        let decryptor = Decryptor::new(&ctx, &gen.secret_key()).unwrap();
+
+        let plaintext = Plaintext::from_hex_string("3x^1 + 7").unwrap();
+        let ciphertext = encryptor.encrypt(&plaintext).unwrap();
+
+        assert_eq!(ciphertext.poly_modulus_degree(), 2048);
+        assert_eq!(ciphertext.coeff_modulus_size(), 1);
+        assert!(!ciphertext.is_ntt_form());
+
+        let data = ciphertext.data().unwrap();
+
+        assert_eq!(data.len(), 2 * 2048);
+
+        // Copying the data into another ciphertext of the same shape gives
+        // the same ciphertext.
+        let mut other = encryptor
+            .encrypt(&Plaintext::from_hex_string("1").unwrap())
+            .unwrap();
+        other.set_data(&data).unwrap();
+
+        assert_eq!(
+            decryptor.decrypt(&other).unwrap().get_coefficient(0),
+            plaintext.get_coefficient(0)
+        );
+        assert_eq!(
+            other.set_data(&data[1..]),
+            Err(crate::Error::InvalidArgument)
+        );
+    }
+
     #[test]
     fn plaintext_coefficients_in_increasing_order() {
         let plaintext = Plaintext::from_hex_string("1234x^2 + 4321").unwrap();
@@ -6,6 +6,7 @@ use crate::{
 };
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
+use sunscreen_backend::insert_mod_switches;
 use sunscreen_fhe_program::FheProgramTrait;
 use sunscreen_runtime::{marker, CompiledFheProgram, Fhe, FheZkp, Zkp};
 use sunscreen_zkp_backend::{BackendField, CompiledZkpProgram, ZkpBackend};
@@ -52,6 +53,7 @@ struct FheCompilerData {
     plain_modulus_constraint: PlainModulusConstraint,
     security_level: SecurityLevel,
     noise_margin: u32,
+    modulus_switching: bool,
 }
 
 impl Default for FheCompilerData {
@@ -64,6 +66,7 @@ impl Default for FheCompilerData {
             plain_modulus_constraint: PlainModulusConstraint::Raw(262_144),
             security_level: SecurityLevel::TC128,
             noise_margin: 20,
+            modulus_switching: false,
         }
     }
 }
@@ -282,7 +285,13 @@ impl<T, B> GenericCompiler<T, B> {
             .map(|prog| {
                 let execution_graph = prog.build(&params);
                 let mut required_keys = vec![];
-                let fhe_program_fn = execution_graph?.compile();
+                let mut fhe_program_fn = execution_graph?.compile();
+
+                // Chained programs feed their outputs back in as inputs, which
+                // must be at the same level as the other ciphertexts.
+                if fhe_data.modulus_switching && prog.chain_count() == 1 {
+                    insert_mod_switches(&mut fhe_program_fn, &params, fhe_data.noise_margin)?;
+                }
 
                 if fhe_program_fn.requires_relin_keys() {
                     required_keys.push(RequiredKeys::Relin);
@@ -296,6 +305,7 @@ impl<T, B> GenericCompiler<T, B> {
                     params: params.clone(),
                     required_keys,
                     signature: prog.signature(),
+                    galois_steps: fhe_program_fn.galois_steps(),
                 };
 
                 let compiled_program = CompiledFheProgram {
@@ -538,6 +548,26 @@ where
         self.data.fhe_data_mut().noise_margin = noise_margin;
         self
     }
+
+    /**
+     * Insert modulus switches into each FHE program as far as the noise
+     * budget allows while retaining the
+     * [`additional_noise_budget`](Self::additional_noise_budget). Switching
+     * before multiplications makes them faster and switching before outputs
+     * makes output ciphertexts smaller. Defaults to `false`.
+     *
+     * # Remarks
+     * Finding where to switch runs each FHE program several times, which
+     * slows compilation.
+     *
+     * Switched outputs can't be combined with fresh ciphertexts, so don't
+     * enable this if you pass outputs as inputs to other FHE programs.
+     * Programs with a chain count greater than 1 are never switched.
+     */
+    pub fn modulus_switching(mut self, enabled: bool) -> Self {
+        self.data.fhe_data_mut().modulus_switching = enabled;
+        self
+    }
 }
 
 /**
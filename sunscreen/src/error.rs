@@ -13,11 +13,20 @@ pub enum Error {
     MissingPlainModulusConstraint,
 
     /**
-     * No parameters were found that satisfy the given FHE program.
+     * No parameters were found that satisfy the given FHE program, and there
+     * is no more specific reason. Parameter search returns
+     * [`Error::NoParamsWithReason`] when it can explain the failure.
      */
     #[error("Failed to find satisfying parameters")]
     NoParams,
 
+    /**
+     * No parameters were found that satisfy the given FHE program. Contains
+     * an explanation of why each candidate lattice dimension failed.
+     */
+    #[error("Failed to find satisfying parameters: {0}")]
+    NoParamsWithReason(Box<String>),
+
     /**
      * Attempted to compile the given FHE program with the wrong scheme.
      */
@@ -72,6 +81,12 @@ pub enum Error {
     #[error("FHE program error: {0}")]
     FheProgramError(sunscreen_fhe_program::Error),
 
+    /**
+     * An error occurred in the backend compiler.
+     */
+    #[error("Backend error: {0:?}")]
+    BackendError(Box<sunscreen_backend::Error>),
+
     /**
      * The given configuration is not supported.
      */
@@ -81,6 +96,12 @@ pub enum Error {
 
 const_assert!(std::mem::size_of::<Error>() <= 24);
 
+impl From<sunscreen_backend::Error> for Error {
+    fn from(err: sunscreen_backend::Error) -> Self {
+        Self::BackendError(Box::new(err))
+    }
+}
+
 impl Error {
     /**
      * Create an [`Error::Unsupported`]
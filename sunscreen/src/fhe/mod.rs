@@ -97,6 +97,29 @@ pub enum FheOperation {
      */
     SwapRows,
 
+    /**
+     * Compares two integers whose bits are encrypted in separate
+     * ciphertexts. See [`FheProgramOperation::LessThan`].
+     */
+    LessThan {
+        /**
+         * Whether the integers are two's complement.
+         */
+        signed: bool,
+    },
+
+    /**
+     * Tests whether two integers whose bits are encrypted in separate
+     * ciphertexts differ. See [`FheProgramOperation::NotEqual`].
+     */
+    NotEqual,
+
+    /**
+     * Selects between two ciphertexts based on an encrypted condition. See
+     * [`FheProgramOperation::Select`].
+     */
+    Select,
+
     /**
      * This node indicates the previous node's result should be a result of the [`fhe_program`](crate::fhe_program).
      */
@@ -137,7 +160,10 @@ impl OperationTrait for FheOperation {
     }
 
     fn is_ordered(&self) -> bool {
-        false
+        matches!(
+            self,
+            FheOperation::LessThan { .. } | FheOperation::NotEqual | FheOperation::Select
+        )
     }
 }
 
@@ -255,6 +281,30 @@ pub trait FheContextOps {
      */
     fn add_swap_rows(&mut self, x: NodeIndex) -> NodeIndex;
 
+    /**
+     * Adds an encrypted select, returning `a` when `cond` encrypts 1 and `b`
+     * when it encrypts 0.
+     *
+     * # Remarks
+     * The backend compiler lowers this to `b + cond * (a - b)`, which costs
+     * a single ciphertext multiplication (and thus one relinearization)
+     * rather than the two needed by the naive `cond * a + (1 - cond) * b`.
+     */
+    fn add_select(&mut self, cond: NodeIndex, a: NodeIndex, b: NodeIndex) -> NodeIndex;
+
+    /**
+     * Adds a comparison returning an encrypted 1 if the integer with bits
+     * `a` is less than the integer with bits `b` and 0 otherwise. Each
+     * bit is a separate ciphertext, least significant first.
+     */
+    fn add_less_than(&mut self, a: &[NodeIndex], b: &[NodeIndex], signed: bool) -> NodeIndex;
+
+    /**
+     * Adds a comparison returning an encrypted 1 if the bits `a` and `b`
+     * differ and 0 otherwise. Each bit is a separate ciphertext.
+     */
+    fn add_not_equal(&mut self, a: &[NodeIndex], b: &[NodeIndex]) -> NodeIndex;
+
     /**
      * Add a node that captures the previous node as an output.
      */
@@ -331,11 +381,50 @@ impl FheContextOps for FheContext {
         self.add_unary_operation(FheOperation::SwapRows, x)
     }
 
+    fn add_select(&mut self, cond: NodeIndex, a: NodeIndex, b: NodeIndex) -> NodeIndex {
+        let node = self.add_node(FheOperation::Select);
+
+        for (i, x) in [cond, a, b].iter().enumerate() {
+            self.add_edge(*x, node, EdgeInfo::Ordered(i));
+        }
+
+        node
+    }
+
+    fn add_less_than(&mut self, a: &[NodeIndex], b: &[NodeIndex], signed: bool) -> NodeIndex {
+        add_comparison(self, FheOperation::LessThan { signed }, a, b)
+    }
+
+    fn add_not_equal(&mut self, a: &[NodeIndex], b: &[NodeIndex]) -> NodeIndex {
+        add_comparison(self, FheOperation::NotEqual, a, b)
+    }
+
     fn add_output(&mut self, i: NodeIndex) -> NodeIndex {
         self.add_unary_operation(FheOperation::Output, i)
     }
 }
 
+/**
+ * Adds a comparison node whose ordered operands are the bits of `a`
+ * followed by the bits of `b`.
+ */
+fn add_comparison(
+    ctx: &mut FheContext,
+    op: FheOperation,
+    a: &[NodeIndex],
+    b: &[NodeIndex],
+) -> NodeIndex {
+    assert_eq!(a.len(), b.len(), "Compared values must have the same width");
+
+    let node = ctx.add_node(op);
+
+    for (i, x) in a.iter().chain(b.iter()).enumerate() {
+        ctx.add_edge(*x, node, EdgeInfo::Ordered(i));
+    }
+
+    node
+}
+
 /**
  * Extends FheFrontendCompilation to add a backend compilation method.
  */
@@ -386,13 +475,18 @@ impl FheCompile for FheFrontendCompilation {
                 FheOperation::RotateRight => NodeInfo::new(FheProgramOperation::ShiftRight),
                 FheOperation::SwapRows => NodeInfo::new(FheProgramOperation::SwapRows),
                 FheOperation::AddPlaintext => NodeInfo::new(FheProgramOperation::AddPlaintext),
+                FheOperation::LessThan { signed } => {
+                    NodeInfo::new(FheProgramOperation::LessThan { signed: *signed })
+                }
+                FheOperation::NotEqual => NodeInfo::new(FheProgramOperation::NotEqual),
+                FheOperation::Select => NodeInfo::new(FheProgramOperation::Select),
             },
             |_, e| match e {
                 EdgeInfo::Left => EdgeInfo::Left,
                 EdgeInfo::Right => EdgeInfo::Right,
                 EdgeInfo::Unary => EdgeInfo::Unary,
                 EdgeInfo::Unordered => unreachable!("FHE programs have no unordered edges."),
-                EdgeInfo::Ordered(_) => unreachable!("FHE programs have no ordered edges."),
+                EdgeInfo::Ordered(x) => EdgeInfo::Ordered(*x),
             },
         );
 
@@ -77,14 +77,22 @@ pub use compiler::{Compiler, FheProgramFn, GenericCompiler};
 pub use error::{Error, Result};
 pub use params::PlainModulusConstraint;
 pub use seal_fhe::Plaintext as SealPlaintext;
+pub use sunscreen_backend::{FheProgramReport, NodeReport, OutputReport};
 pub use sunscreen_compiler_macros::*;
 pub use sunscreen_fhe_program::{SchemeType, SecurityLevel};
 pub use sunscreen_runtime::{
-    CallSignature, Ciphertext, CompiledFheProgram, Error as RuntimeError, FheProgramInput,
-    FheProgramInputTrait, FheProgramMetadata, FheRuntime, FheZkpRuntime, InnerCiphertext,
-    InnerPlaintext, Params, Plaintext, PrivateKey, PublicKey, RequiredKeys, Runtime, WithContext,
-    ZkpProgramInput, ZkpRuntime,
+    ArtifactKind, CallSignature, Ciphertext, CompactCiphertext, CompactPublicKey,
+    CompiledFheProgram, Error as RuntimeError, FheProgramInput, FheProgramInputTrait,
+    FheProgramMetadata, FheProgramStream, FheRuntime, FheZkpRuntime, InnerCiphertext,
+    InnerPlaintext, KeyShare, LogProof, Params, PartialDecryption, Plaintext, PrivateKey,
+    PrivateKeyFragment, PublicKey, PublicKeyShare, RelinKeyShare, RequiredKeys, RunOptions,
+    Runtime, Scheduling, ThresholdConfig, ThresholdPrivateKey, TryFromNativeFields, WireFormat,
+    WithContext, ZkpOutputs, ZkpProgramInput, ZkpProof, ZkpRuntime, MIN_WIRE_FORMAT_VERSION,
+    SMUDGING_SECURITY_BITS, WIRE_FORMAT_VERSION,
 };
+pub use sunscreen_zkp_backend::bulletproofs::BulletproofsBackend;
+#[cfg(feature = "groth16")]
+pub use sunscreen_zkp_backend::groth16::Groth16Backend;
 pub use sunscreen_zkp_backend::{BackendField, Error as ZkpError, Result as ZkpResult, ZkpBackend};
 pub use zkp::ZkpProgramFn;
 pub use zkp::{
@@ -209,6 +217,31 @@ where
     }
 }
 
+/**
+ * Compiler diagnostics for a [`CompiledFheProgram`].
+ */
+pub trait FheProgramReportExt {
+    /**
+     * Reports the multiplicative depth and noise of every node and output in
+     * this FHE program under its compiled parameters. Each output includes the
+     * critical path of operations that dominates its noise.
+     *
+     * # Remarks
+     * This runs the FHE program to measure noise, so it can take substantial
+     * time for large programs.
+     */
+    fn report(&self) -> Result<FheProgramReport>;
+}
+
+impl FheProgramReportExt for CompiledFheProgram {
+    fn report(&self) -> Result<FheProgramReport> {
+        Ok(FheProgramReport::new(
+            &self.fhe_program_fn,
+            &self.metadata.params,
+        )?)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 /**
  * Information about an edge in the frontend IR.
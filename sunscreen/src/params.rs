@@ -6,8 +6,9 @@ use seal_fhe::{
     BfvEncryptionParametersBuilder, CoefficientModulus, Context, KeyGenerator, Modulus,
     PlainModulus,
 };
+use sunscreen_backend::multiplicative_depths;
 use sunscreen_backend::noise_model::{
-    noise_budget_to_noise, predict_noise, MeasuredModel, TargetNoiseLevel,
+    noise_budget_to_noise, noise_to_noise_budget, predict_noise, MeasuredModel, TargetNoiseLevel,
 };
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Operation, SchemeType};
 pub use sunscreen_runtime::Params;
@@ -93,12 +94,14 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
         .set_poly_modulus_degree(params.lattice_dimension)
         .build()?;
 
-    let context = Context::new(&enc_params, true, params.security_level).unwrap();
+    let context = Context::new(&enc_params, true, params.security_level)?;
 
-    let keygen = KeyGenerator::new(&context).unwrap();
+    let keygen = KeyGenerator::new(&context)?;
 
     let create_galois = if fhe_program.requires_galois_keys() {
-        keygen.create_galois_keys().is_ok()
+        keygen
+            .create_galois_keys_from_steps(&fhe_program.galois_steps())
+            .is_ok()
     } else {
         true
     };
@@ -115,6 +118,14 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
 /**
  * Determines the minimal parameters required to satisfy the noise constraint for
  * the given FHE program and plaintext modulo and security level.
+ *
+ * # Remarks
+ * If no lattice dimension works, returns [`Error::NoParamsWithReason`]
+ * containing a human-readable explanation of why each dimension failed, or
+ * [`Error::NoParams`] if there were no dimensions to try. For
+ * noise failures, this lists the offending output's multiplicative depth and remaining noise
+ * budget. Use [`FheProgramReport`](sunscreen_backend::FheProgramReport) to find
+ * which operations dominate the depth.
  */
 pub fn determine_params(
     fhe_program_fns: &[Box<dyn FheProgramFn>],
@@ -123,12 +134,19 @@ pub fn determine_params(
     noise_margin_bits: u32,
     scheme_type: SchemeType,
 ) -> Result<Params> {
+    // Why each lattice dimension failed.
+    let mut failures = vec![];
+
     'params_loop: for (i, n) in LATTICE_DIMENSIONS.iter().enumerate() {
         // Select a plain modulus that meets needs of the passed
         // constraint.
         let plaintext_modulus = match plaintext_constraint_to_modulus(plaintext_constraint, i) {
             Ok(v) => v,
             Err(_) => {
+                failures.push(format!(
+                    "n={}: no plain modulus satisfies {:?}",
+                    n, plaintext_constraint
+                ));
                 continue 'params_loop;
             }
         };
@@ -161,12 +179,13 @@ pub fn determine_params(
             trace!("Built and validated {}", program.name());
 
             match can_make_required_keys(&ir, &params) {
-                Ok(can_make_keys) => {
-                    if !can_make_keys {
-                        continue 'params_loop;
-                    }
-                }
-                Err(_) => {
+                Ok(true) => {}
+                _ => {
+                    failures.push(format!(
+                        "n={}: cannot create the relinearization or Galois keys {} requires",
+                        n,
+                        program.name()
+                    ));
                     continue 'params_loop;
                 }
             };
@@ -204,6 +223,11 @@ pub fn determine_params(
                             program.name(),
                             n
                         );
+                        failures.push(format!(
+                            "n={}: cannot run {} to measure noise (inputs from chained runs may already exceed the noise budget)",
+                            n,
+                            program.name()
+                        ));
                         continue 'params_loop;
                     }
                 };
@@ -212,13 +236,30 @@ pub fn determine_params(
 
                 let target_noise = noise_budget_to_noise(noise_margin_bits as f64);
 
-                for output_noise in output_noises {
+                for (output_id, output_noise) in output_noises.into_iter().enumerate() {
                     if output_noise > target_noise {
                         trace!(
                             "Failed to meet noise constraints with lattice dimension {} for program {}",
                             n,
                             program.name()
                         );
+
+                        let depths = multiplicative_depths(&ir);
+                        let depth = ir
+                            .get_outputs()
+                            .nth(output_id)
+                            .map(|o| depths[o.index()])
+                            .unwrap_or(0);
+
+                        failures.push(format!(
+                            "n={}: output {} of {} has multiplicative depth {} and {:.1} bits of noise budget remaining, but {} are required",
+                            n,
+                            output_id,
+                            program.name(),
+                            depth,
+                            noise_to_noise_budget(output_noise),
+                            noise_margin_bits
+                        ));
                         continue 'params_loop;
                     } else if output_noise > chain_noise_level {
                         chain_noise_level = output_noise
@@ -232,5 +273,15 @@ pub fn determine_params(
         return Ok(params);
     }
 
-    Err(Error::NoParams)
+    if failures.is_empty() {
+        return Err(Error::NoParams);
+    }
+
+    Err(Error::NoParamsWithReason(Box::new(format!(
+        "No lattice dimension in {:?} works:\n{}\n\
+        Consider reducing the multiplicative depth of the listed outputs, using a \
+        smaller plain modulus, or requesting less additional noise budget.",
+        LATTICE_DIMENSIONS,
+        failures.join("\n")
+    ))))
 }
@@ -1,13 +1,14 @@
 use crate::{
-    fhe::{with_fhe_ctx, FheContextOps, Literal},
+    fhe::{with_fhe_ctx, FheContext, FheContextOps, Literal},
     types::{
         intern::{Cipher, FheProgramNode},
         ops::*,
-        BfvType, FheType, LaneCount, NumCiphertexts, SwapRows, TryFromPlaintext, TryIntoPlaintext,
-        Type, TypeName, TypeNameInstance, Version,
+        BfvType, BroadcastLane, FheType, LaneCount, NumCiphertexts, SumLanes, SwapRows,
+        TryFromPlaintext, TryIntoPlaintext, Type, TypeName, TypeNameInstance, Version,
     },
     FheProgramInputTrait, InnerPlaintext, Params, Plaintext, WithContext,
 };
+use petgraph::stable_graph::NodeIndex;
 use seal_fhe::{
     BFVEncoder, BfvEncryptionParametersBuilder, Context as SealContext, Modulus,
     Result as SealResult,
@@ -492,6 +493,30 @@ impl<const LANES: usize> SwapRows for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> SumLanes for Batched<LANES> {
+    type Output = Self;
+
+    fn sum_lanes(self) -> Self::Output {
+        let sum = self.data.iter().flatten().sum::<i64>();
+
+        Self {
+            data: [[sum; LANES]; 2],
+        }
+    }
+}
+
+impl<const LANES: usize> BroadcastLane for Batched<LANES> {
+    type Output = Self;
+
+    fn broadcast_lane(self, lane: (usize, usize)) -> Self::Output {
+        let val = self[lane];
+
+        Self {
+            data: [[val; LANES]; 2],
+        }
+    }
+}
+
 impl<const LANES: usize> Index<(usize, usize)> for Batched<LANES> {
     type Output = i64;
 
@@ -622,6 +647,237 @@ impl<const LANES: usize> GraphCipherNeg for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> GraphCipherSelect for Batched<LANES> {
+    type Cond = Self;
+    type Val = Self;
+
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_select(cond.ids[0], a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> Batched<LANES> {
+    /**
+     * Homomorphically computes the matrix-vector product `matrix * x` inside an
+     * [`fhe_program`](crate::fhe_program), where `matrix` is public.
+     *
+     * # Remarks
+     * `x` is treated as a vector of `2 * LANES` elements, where element `i`
+     * resides in row `i / LANES` and column `i % LANES`. The result uses the
+     * same layout. `matrix` is a list of rows and may be smaller than
+     * `2 * LANES x 2 * LANES`, in which case missing entries are 0.
+     *
+     * This uses the Halevi-Shoup diagonal method generalized to the BFV
+     * `2 x LANES` layout: each generalized diagonal pairs a row swap (or not)
+     * with a column rotation, yielding `2 * LANES` diagonals. Rotations use the
+     * baby-step giant-step decomposition, so a dense matrix costs
+     * `2 * LANES` plaintext multiplications, roughly `2 * sqrt(2 * LANES)`
+     * rotations, at most one row swap, and no ciphertext multiplications.
+     * Diagonals that are entirely zero are skipped along with any rotations
+     * only they need.
+     *
+     * # Panics
+     * If `matrix` has more than `2 * LANES` rows or columns, or if every
+     * entry is zero.
+     */
+    pub fn mat_vec_mul(
+        matrix: &[Vec<i64>],
+        x: FheProgramNode<Cipher<Self>>,
+    ) -> FheProgramNode<Cipher<Self>> {
+        let size = 2 * LANES;
+
+        if matrix.len() > size || matrix.iter().any(|r| r.len() > size) {
+            panic!("Matrix must be at most {} x {}", size, size);
+        }
+
+        let entry = |i: usize, j: usize| matrix.get(i).and_then(|r| r.get(j)).copied();
+
+        // The generalized diagonal (s, k) holds M[r * L + c][(r ^ s) * L + (c + k) % L]
+        // at row r and column c. We pre-rotate it right by `shift` so that the giant step
+        // rotation lines it back up.
+        let diagonal = |s: usize, k: usize, shift: usize| {
+            let d = [0, 1].map(|r| {
+                (0..LANES)
+                    .map(|c| {
+                        let c = (c + LANES - shift) % LANES;
+
+                        entry(r * LANES + c, (r ^ s) * LANES + (c + k) % LANES).unwrap_or(0)
+                    })
+                    .collect::<Vec<i64>>()
+            });
+
+            if d.iter().flatten().all(|v| *v == 0) {
+                None
+            } else {
+                Some(d)
+            }
+        };
+
+        // Choose the baby step count g near sqrt(L / 2) to minimize 2g + L / g
+        // rotations.
+        let mut g = 1;
+
+        while 2 * g * g < LANES {
+            g *= 2;
+        }
+
+        let giant_steps = LANES / g;
+
+        with_fhe_ctx(|ctx| {
+            let mut baby_steps = vec![None; 2 * g];
+            let mut acc = None;
+
+            for i in 0..giant_steps {
+                let mut inner = None;
+
+                for s in 0..2 {
+                    for j in 0..g {
+                        let d = match diagonal(s, g * i + j, g * i) {
+                            Some(d) => d,
+                            None => continue,
+                        };
+
+                        let v = Self::baby_step(ctx, x.ids[0], &mut baby_steps, g, s, j);
+
+                        let d = Self::try_from(d)
+                            .unwrap()
+                            .try_into_plaintext(&ctx.data)
+                            .unwrap();
+                        let d = ctx.add_plaintext_literal(d.inner);
+
+                        let term = ctx.add_multiplication_plaintext(v, d);
+
+                        inner = Some(match inner {
+                            Some(inner) => ctx.add_addition(inner, term),
+                            None => term,
+                        });
+                    }
+                }
+
+                let inner = match inner {
+                    Some(inner) => inner,
+                    None => continue,
+                };
+
+                let rotated = if i == 0 {
+                    inner
+                } else {
+                    let amount = ctx.add_literal(Literal::U64((g * i) as u64));
+                    ctx.add_rotate_left(inner, amount)
+                };
+
+                acc = Some(match acc {
+                    Some(acc) => ctx.add_addition(acc, rotated),
+                    None => rotated,
+                });
+            }
+
+            let n = acc.expect("Matrix must contain a nonzero entry");
+
+            FheProgramNode::new(&[n])
+        })
+    }
+
+    /**
+     * Returns `x`, with rows swapped if `s == 1`, rotated left by `j`. Caches
+     * results so each rotation appears in the graph at most once.
+     */
+    fn baby_step(
+        ctx: &mut FheContext,
+        x: NodeIndex,
+        cache: &mut [Option<NodeIndex>],
+        g: usize,
+        s: usize,
+        j: usize,
+    ) -> NodeIndex {
+        if let Some(n) = cache[s * g + j] {
+            return n;
+        }
+
+        let n = match (s, j) {
+            (0, 0) => x,
+            (_, 0) => ctx.add_swap_rows(x),
+            _ => {
+                let base = Self::baby_step(ctx, x, cache, g, s, 0);
+                let amount = ctx.add_literal(Literal::U64(j as u64));
+
+                ctx.add_rotate_left(base, amount)
+            }
+        };
+
+        cache[s * g + j] = Some(n);
+
+        n
+    }
+
+    /**
+     * Emits a rotate-and-add ladder that sums every lane of `x` into every
+     * lane. Each rotation amount is a power of 2, so this only requires
+     * Galois keys for power of 2 rotations and the row swap.
+     */
+    fn sum_lanes_ladder(ctx: &mut FheContext, x: NodeIndex) -> NodeIndex {
+        let mut acc = x;
+        let mut shift = 1;
+
+        while shift < LANES {
+            let amount = ctx.add_literal(Literal::U64(shift as u64));
+            let rotated = ctx.add_rotate_left(acc, amount);
+            acc = ctx.add_addition(acc, rotated);
+
+            shift *= 2;
+        }
+
+        let swapped = ctx.add_swap_rows(acc);
+
+        ctx.add_addition(acc, swapped)
+    }
+}
+
+impl<const LANES: usize> GraphCipherSumLanes for Batched<LANES> {
+    fn graph_cipher_sum_lanes(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let n = Self::sum_lanes_ladder(ctx, x.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> GraphCipherBroadcastLane for Batched<LANES> {
+    fn graph_cipher_broadcast_lane(
+        x: FheProgramNode<Cipher<Self>>,
+        lane: (usize, usize),
+    ) -> FheProgramNode<Cipher<Self>> {
+        let (row, col) = lane;
+
+        if row > 1 || col >= LANES {
+            panic!("Lane ({}, {}) out of range", row, col);
+        }
+
+        with_fhe_ctx(|ctx| {
+            // Zero every lane but the one we want, then sum.
+            let mut mask = [[0; LANES]; 2];
+            mask[row][col] = 1;
+
+            let mask = Self::from(mask).try_into_plaintext(&ctx.data).unwrap();
+            let mask = ctx.add_plaintext_literal(mask.inner);
+
+            let masked = ctx.add_multiplication_plaintext(x.ids[0], mask);
+            let n = Self::sum_lanes_ladder(ctx, masked);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
 impl<const LANES: usize> LaneCount for Batched<LANES> {
     fn lane_count() -> usize {
         LANES
@@ -699,6 +955,20 @@ mod tests {
         assert_eq!(a << 3, [[4, 1, 2, 3], [8, 5, 6, 7]].into());
     }
 
+    #[test]
+    fn can_sum_lanes_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a.sum_lanes(), [[36; 4]; 2].into());
+    }
+
+    #[test]
+    fn can_broadcast_lane_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a.broadcast_lane((1, 2)), [[7; 4]; 2].into());
+    }
+
     #[test]
     fn can_shr_non_fhe() {
         let a = Batched::<4>::try_from(A_VEC).unwrap();
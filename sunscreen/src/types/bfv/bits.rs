@@ -0,0 +1,333 @@
+use petgraph::stable_graph::NodeIndex;
+
+use crate::{
+    fhe::{with_fhe_ctx, FheContext, FheContextOps},
+    types::{
+        bfv::Signed,
+        intern::FheProgramNode,
+        ops::{GraphCipherCompare, GraphCipherSelect},
+        BfvType, Cipher, FheType, TypeNameInstance,
+    },
+    FheProgramInputTrait, Params,
+};
+
+use sunscreen_runtime::{
+    Error, InnerPlaintext, NumCiphertexts, Plaintext, TryFromPlaintext, TryIntoPlaintext, Type,
+    TypeName, Version,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/**
+ * An unsigned integer of `BITS` bits, where each bit is encrypted in its
+ * own ciphertext.
+ *
+ * # Remarks
+ * Unlike [`Signed`] and [`Unsigned`](crate::types::bfv::Unsigned), this
+ * type supports encrypted comparison and equality (see
+ * [`lt`](crate::types::lt), [`eq`](crate::types::eq),
+ * [`max`](crate::types::max) and friends) and
+ * [`select`](crate::types::select), but no arithmetic.
+ *
+ * Encrypting a value takes `BITS` ciphertexts, so choose the smallest
+ * `BITS` that fits your data. Comparisons cost about `2 * BITS` ciphertext
+ * multiplications and have multiplicative depth `1 + ceil(log2(BITS))`.
+ */
+pub struct BitUnsigned<const BITS: usize> {
+    val: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/**
+ * A two's complement signed integer of `BITS` bits, where each bit is
+ * encrypted in its own ciphertext.
+ *
+ * # Remarks
+ * See [`BitUnsigned`] for the operations this type supports and their
+ * cost.
+ */
+pub struct BitSigned<const BITS: usize> {
+    val: i64,
+}
+
+fn assert_valid_width(bits: usize) {
+    assert!(
+        (1..=64).contains(&bits),
+        "Bit types must have between 1 and 64 bits"
+    );
+}
+
+impl<const BITS: usize> From<u64> for BitUnsigned<BITS> {
+    /**
+     * Create a [`BitUnsigned`] from the given value.
+     *
+     * # Panics
+     * If `val` doesn't fit in `BITS` bits.
+     */
+    fn from(val: u64) -> Self {
+        assert_valid_width(BITS);
+        assert!(
+            BITS == 64 || val >> BITS == 0,
+            "{} doesn't fit in {} bits",
+            val,
+            BITS
+        );
+
+        Self { val }
+    }
+}
+
+impl<const BITS: usize> From<BitUnsigned<BITS>> for u64 {
+    fn from(x: BitUnsigned<BITS>) -> Self {
+        x.val
+    }
+}
+
+impl<const BITS: usize> From<i64> for BitSigned<BITS> {
+    /**
+     * Create a [`BitSigned`] from the given value.
+     *
+     * # Panics
+     * If `val` doesn't fit in `BITS` bits as a two's complement value.
+     */
+    fn from(val: i64) -> Self {
+        assert_valid_width(BITS);
+        assert!(
+            BITS == 64 || (val >> (BITS - 1) == 0 || val >> (BITS - 1) == -1),
+            "{} doesn't fit in {} bits",
+            val,
+            BITS
+        );
+
+        Self { val }
+    }
+}
+
+impl<const BITS: usize> From<BitSigned<BITS>> for i64 {
+    fn from(x: BitSigned<BITS>) -> Self {
+        x.val
+    }
+}
+
+impl<const BITS: usize> std::fmt::Display for BitUnsigned<BITS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl<const BITS: usize> std::fmt::Display for BitSigned<BITS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+fn bit_type_name(name: &str, bits: usize) -> Type {
+    let version = env!("CARGO_PKG_VERSION");
+
+    Type {
+        name: format!("sunscreen::types::{}<{}>", name, bits),
+        version: Version::parse(version).expect("Crate version is not a valid semver"),
+        is_encrypted: false,
+    }
+}
+
+/**
+ * Encodes each of the `BITS` least significant bits of `val` as a
+ * [`Signed`] 0 or 1, least significant bit first.
+ */
+fn encode_bits(
+    val: u64,
+    bits: usize,
+    data_type: Type,
+    params: &Params,
+) -> Result<Plaintext, Error> {
+    let mut plaintexts = Vec::with_capacity(bits);
+
+    for i in 0..bits {
+        let bit = Signed::from(((val >> i) & 0x1) as i64).try_into_plaintext(params)?;
+
+        match bit.inner {
+            InnerPlaintext::Seal(p) => plaintexts.extend(p),
+        }
+    }
+
+    Ok(Plaintext {
+        data_type,
+        inner: InnerPlaintext::Seal(plaintexts),
+    })
+}
+
+/**
+ * Decodes a plaintext created by [`encode_bits`], returning the bits as
+ * the low bits of a [`u64`].
+ */
+fn decode_bits(plaintext: &Plaintext, bits: usize, params: &Params) -> Result<u64, Error> {
+    let InnerPlaintext::Seal(p) = &plaintext.inner;
+
+    if p.len() != bits {
+        return Err(Error::IncorrectCiphertextCount);
+    }
+
+    let mut val = 0u64;
+
+    for (i, p) in p.iter().enumerate() {
+        let bit = Plaintext {
+            data_type: Signed::type_name(),
+            inner: InnerPlaintext::Seal(vec![p.clone()]),
+        };
+
+        match i64::from(Signed::try_from_plaintext(&bit, params)?) {
+            0 => {}
+            1 => val |= 0x1 << i,
+            _ => return Err(Error::fhe_type_error("Bit is neither 0 nor 1")),
+        }
+    }
+
+    Ok(val)
+}
+
+macro_rules! impl_bit_type {
+    ($ty:ident, $name:literal, $signed:literal) => {
+        impl<const BITS: usize> NumCiphertexts for $ty<BITS> {
+            const NUM_CIPHERTEXTS: usize = BITS;
+        }
+
+        impl<const BITS: usize> TypeName for $ty<BITS> {
+            fn type_name() -> Type {
+                bit_type_name($name, BITS)
+            }
+        }
+
+        impl<const BITS: usize> TypeNameInstance for $ty<BITS> {
+            fn type_name_instance(&self) -> Type {
+                Self::type_name()
+            }
+        }
+
+        impl<const BITS: usize> FheProgramInputTrait for $ty<BITS> {}
+        impl<const BITS: usize> FheType for $ty<BITS> {}
+        impl<const BITS: usize> BfvType for $ty<BITS> {}
+
+        impl<const BITS: usize> GraphCipherSelect for $ty<BITS> {
+            type Cond = Signed;
+            type Val = Self;
+
+            fn graph_cipher_select(
+                cond: FheProgramNode<Cipher<Self::Cond>>,
+                a: FheProgramNode<Cipher<Self::Val>>,
+                b: FheProgramNode<Cipher<Self::Val>>,
+            ) -> FheProgramNode<Cipher<Self::Val>> {
+                with_fhe_ctx(|ctx| FheProgramNode::new(&add_mux(ctx, cond.ids[0], a.ids, b.ids)))
+            }
+        }
+
+        impl<const BITS: usize> GraphCipherCompare for $ty<BITS> {
+            type Val = Self;
+
+            fn graph_cipher_lt(
+                a: FheProgramNode<Cipher<Self::Val>>,
+                b: FheProgramNode<Cipher<Self::Val>>,
+            ) -> FheProgramNode<Cipher<Signed>> {
+                with_fhe_ctx(|ctx| FheProgramNode::new(&[ctx.add_less_than(a.ids, b.ids, $signed)]))
+            }
+
+            fn graph_cipher_eq(
+                a: FheProgramNode<Cipher<Self::Val>>,
+                b: FheProgramNode<Cipher<Self::Val>>,
+            ) -> FheProgramNode<Cipher<Signed>> {
+                with_fhe_ctx(|ctx| FheProgramNode::new(&[add_eq(ctx, a.ids, b.ids)]))
+            }
+
+            fn graph_cipher_max(
+                a: FheProgramNode<Cipher<Self::Val>>,
+                b: FheProgramNode<Cipher<Self::Val>>,
+            ) -> FheProgramNode<Cipher<Self::Val>> {
+                with_fhe_ctx(|ctx| {
+                    let lt = ctx.add_less_than(a.ids, b.ids, $signed);
+
+                    FheProgramNode::new(&add_mux(ctx, lt, b.ids, a.ids))
+                })
+            }
+
+            fn graph_cipher_min(
+                a: FheProgramNode<Cipher<Self::Val>>,
+                b: FheProgramNode<Cipher<Self::Val>>,
+            ) -> FheProgramNode<Cipher<Self::Val>> {
+                with_fhe_ctx(|ctx| {
+                    let lt = ctx.add_less_than(a.ids, b.ids, $signed);
+
+                    FheProgramNode::new(&add_mux(ctx, lt, a.ids, b.ids))
+                })
+            }
+        }
+    };
+}
+
+impl_bit_type!(BitUnsigned, "BitUnsigned", false);
+impl_bit_type!(BitSigned, "BitSigned", true);
+
+impl<const BITS: usize> TryIntoPlaintext for BitUnsigned<BITS> {
+    fn try_into_plaintext(&self, params: &Params) -> Result<Plaintext, Error> {
+        encode_bits(self.val, BITS, self.type_name_instance(), params)
+    }
+}
+
+impl<const BITS: usize> TryFromPlaintext for BitUnsigned<BITS> {
+    fn try_from_plaintext(plaintext: &Plaintext, params: &Params) -> Result<Self, Error> {
+        Ok(Self {
+            val: decode_bits(plaintext, BITS, params)?,
+        })
+    }
+}
+
+impl<const BITS: usize> TryIntoPlaintext for BitSigned<BITS> {
+    fn try_into_plaintext(&self, params: &Params) -> Result<Plaintext, Error> {
+        encode_bits(self.val as u64, BITS, self.type_name_instance(), params)
+    }
+}
+
+impl<const BITS: usize> TryFromPlaintext for BitSigned<BITS> {
+    fn try_from_plaintext(plaintext: &Plaintext, params: &Params) -> Result<Self, Error> {
+        let val = decode_bits(plaintext, BITS, params)?;
+
+        // Sign extend.
+        let shift = 64 - BITS;
+
+        Ok(Self {
+            val: ((val << shift) as i64) >> shift,
+        })
+    }
+}
+
+fn add_one(ctx: &mut FheContext) -> NodeIndex {
+    let one = Signed::from(1).try_into_plaintext(&ctx.data).unwrap();
+
+    ctx.add_plaintext_literal(one.inner)
+}
+
+/**
+ * Adds a circuit returning the bits of `a` when `cond` is 1 and the bits of
+ * `b` when `cond` is 0.
+ */
+fn add_mux(
+    ctx: &mut FheContext,
+    cond: NodeIndex,
+    a: &[NodeIndex],
+    b: &[NodeIndex],
+) -> Vec<NodeIndex> {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| ctx.add_select(cond, *a, *b))
+        .collect()
+}
+
+/**
+ * Adds a circuit returning 1 if the bits `a` and `b` are equal and 0
+ * otherwise.
+ */
+fn add_eq(ctx: &mut FheContext, a: &[NodeIndex], b: &[NodeIndex]) -> NodeIndex {
+    let one = add_one(ctx);
+    let ne = ctx.add_not_equal(a, b);
+    let neg_ne = ctx.add_negate(ne);
+
+    ctx.add_addition_plaintext(neg_ne, one)
+}
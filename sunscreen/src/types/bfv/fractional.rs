@@ -1,13 +1,14 @@
+use petgraph::stable_graph::NodeIndex;
 use seal_fhe::Plaintext as SealPlaintext;
 
 use crate::{
-    fhe::{with_fhe_ctx, FheContextOps},
+    fhe::{with_fhe_ctx, FheContext, FheContextOps},
     types::{
         ops::{
             GraphCipherAdd, GraphCipherConstAdd, GraphCipherConstDiv, GraphCipherConstMul,
-            GraphCipherConstSub, GraphCipherMul, GraphCipherNeg, GraphCipherPlainAdd,
-            GraphCipherPlainMul, GraphCipherPlainSub, GraphCipherSub, GraphConstCipherSub,
-            GraphPlainCipherSub,
+            GraphCipherConstSub, GraphCipherDiv, GraphCipherMul, GraphCipherNeg,
+            GraphCipherPlainAdd, GraphCipherPlainMul, GraphCipherPlainSub, GraphCipherSelect,
+            GraphCipherSub, GraphConstCipherSub, GraphPlainCipherSub,
         },
         Cipher,
     },
@@ -202,7 +203,198 @@ impl<const INT_BITS: usize> TypeNameInstance for Fractional<INT_BITS> {
 impl<const INT_BITS: usize> FheType for Fractional<INT_BITS> {}
 impl<const INT_BITS: usize> BfvType for Fractional<INT_BITS> {}
 
-impl<const INT_BITS: usize> Fractional<INT_BITS> {}
+#[derive(Debug, Clone, Copy, PartialEq)]
+/**
+ * Configures the iterative approximation [`Fractional`] uses to divide by
+ * an encrypted value.
+ *
+ * # Remarks
+ * Encrypted division runs `iterations` rounds of Goldschmidt's algorithm,
+ * each of which costs one level of multiplicative depth. The denominator
+ * must lie in `[min, max]` and this range must not contain zero. The
+ * approximation first scales the denominator by `2^-k`, where `2^k` is the
+ * smallest power of two no less than `max(|min|, |max|)`. The relative error
+ * after `i` iterations is then at most `e^(2^i)` where
+ * `e = 1 - |min| / 2^k` (see [`ReciprocalApprox::error_bound`]), so narrow
+ * ranges converge much faster than wide ones.
+ *
+ * Because [`Fractional`] multiplication is carryless, every iteration
+ * increases the magnitude of the polynomial coefficients. Denominators with
+ * few binary digits and a large `plain_modulus` tolerate more iterations
+ * before digits overflow.
+ */
+pub struct ReciprocalApprox {
+    /**
+     * The smallest magnitude the denominator may take.
+     */
+    pub min: f64,
+
+    /**
+     * The largest magnitude the denominator may take.
+     */
+    pub max: f64,
+
+    /**
+     * The number of Goldschmidt iterations to run. Must be at least 1.
+     */
+    pub iterations: usize,
+}
+
+impl Default for ReciprocalApprox {
+    /**
+     * Denominators in `[1, 2]` with 4 iterations, which yields a relative
+     * error of at most `2^-16`. The `/` operator on encrypted [`Fractional`]
+     * values uses these settings.
+     */
+    fn default() -> Self {
+        Self {
+            min: 1.,
+            max: 2.,
+            iterations: 4,
+        }
+    }
+}
+
+impl ReciprocalApprox {
+    /**
+     * The power of two by which we scale the denominator so that its
+     * magnitude lies in `(0, 1]`. Using a power of two rather than the
+     * range's midpoint keeps the scaled denominator's digit count unchanged.
+     */
+    fn initial_scale(&self) -> f64 {
+        assert!(
+            self.min.is_finite() && self.max.is_finite(),
+            "Reciprocal range must be finite."
+        );
+        assert!(
+            self.min <= self.max && (self.min > 0. || self.max < 0.),
+            "Reciprocal range must be ordered and exclude zero."
+        );
+        assert!(
+            self.iterations > 0,
+            "Reciprocal approximation requires at least 1 iteration."
+        );
+
+        let magnitude = f64::max(self.min.abs(), self.max.abs());
+        let scale = 2f64.powi(-(magnitude.log2().ceil() as i32));
+
+        if self.max < 0. {
+            -scale
+        } else {
+            scale
+        }
+    }
+
+    /**
+     * The largest relative error of the approximate reciprocal or quotient
+     * over the denominator's range, ignoring fixed-point rounding. For
+     * example, `[1, 2]` with 4 iterations gives `2^-16`.
+     *
+     * # Panics
+     * If this has no iterations or a range that is unordered or contains
+     * zero.
+     */
+    pub fn error_bound(&self) -> f64 {
+        let scale = self.initial_scale().abs();
+        let smallest = f64::min(self.min.abs(), self.max.abs());
+
+        (1. - smallest * scale).powi(1 << self.iterations)
+    }
+}
+
+impl<const INT_BITS: usize> Fractional<INT_BITS> {
+    /**
+     * Homomorphically computes `a / b` inside an
+     * [`fhe_program`](crate::fhe_program) using the given approximation
+     * settings.
+     *
+     * # Remarks
+     * The `/` operator on two encrypted [`Fractional`] values calls this
+     * method with [`ReciprocalApprox::default()`], which assumes `b` lies in
+     * `[1, 2]`. Use this method directly for denominators outside that
+     * range; the quotient is meaningless otherwise. See [`ReciprocalApprox`]
+     * for accuracy and overflow considerations.
+     *
+     * # Panics
+     * If `approx` has no iterations or a range that is unordered or contains
+     * zero.
+     */
+    pub fn div_approx(
+        a: FheProgramNode<Cipher<Self>>,
+        b: FheProgramNode<Cipher<Self>>,
+        approx: ReciprocalApprox,
+    ) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let n = Self::goldschmidt(ctx, Some(a.ids[0]), b.ids[0], &approx);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+
+    /**
+     * Homomorphically computes `1 / b` inside an
+     * [`fhe_program`](crate::fhe_program) using the given approximation
+     * settings.
+     *
+     * # Panics
+     * If `approx` has no iterations or a range that is unordered or contains
+     * zero.
+     */
+    pub fn reciprocal_approx(
+        b: FheProgramNode<Cipher<Self>>,
+        approx: ReciprocalApprox,
+    ) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let n = Self::goldschmidt(ctx, None, b.ids[0], &approx);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+
+    /**
+     * Runs Goldschmidt's division algorithm. Both the numerator and
+     * denominator get multiplied by `f_i = 2 - d_i` each iteration, driving
+     * the denominator towards 1 and the numerator towards `a / b`. The
+     * numerator and denominator updates are independent, so each iteration
+     * adds one level of multiplicative depth.
+     *
+     * When `a` is `None`, the numerator is the plaintext 1.
+     */
+    fn goldschmidt(
+        ctx: &mut FheContext,
+        a: Option<NodeIndex>,
+        b: NodeIndex,
+        approx: &ReciprocalApprox,
+    ) -> NodeIndex {
+        let scale = approx.initial_scale();
+
+        let scale = Self::from(scale).try_into_plaintext(&ctx.data).unwrap();
+        let scale = ctx.add_plaintext_literal(scale.inner);
+
+        let two = Self::from(2.).try_into_plaintext(&ctx.data).unwrap();
+        let two = ctx.add_plaintext_literal(two.inner);
+
+        let mut d = ctx.add_multiplication_plaintext(b, scale);
+        let mut n = a.map(|a| ctx.add_multiplication_plaintext(a, scale));
+
+        for i in 0..approx.iterations {
+            let f = ctx.add_subtraction_plaintext(d, two);
+            let f = ctx.add_negate(f);
+
+            n = Some(match n {
+                Some(n) => ctx.add_multiplication(n, f),
+                None => ctx.add_multiplication_plaintext(f, scale),
+            });
+
+            // The last iteration's denominator is unused.
+            if i + 1 < approx.iterations {
+                d = ctx.add_multiplication(d, f);
+            }
+        }
+
+        n.unwrap()
+    }
+}
 
 impl<const INT_BITS: usize> GraphCipherAdd for Fractional<INT_BITS> {
     type Left = Fractional<INT_BITS>;
@@ -417,6 +609,18 @@ impl<const INT_BITS: usize> GraphCipherConstDiv for Fractional<INT_BITS> {
     }
 }
 
+impl<const INT_BITS: usize> GraphCipherDiv for Fractional<INT_BITS> {
+    type Left = Fractional<INT_BITS>;
+    type Right = Fractional<INT_BITS>;
+
+    fn graph_cipher_div(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        Self::div_approx(a, b, ReciprocalApprox::default())
+    }
+}
+
 impl<const INT_BITS: usize> GraphCipherNeg for Fractional<INT_BITS> {
     type Val = Fractional<INT_BITS>;
 
@@ -429,6 +633,23 @@ impl<const INT_BITS: usize> GraphCipherNeg for Fractional<INT_BITS> {
     }
 }
 
+impl<const INT_BITS: usize> GraphCipherSelect for Fractional<INT_BITS> {
+    type Cond = Self;
+    type Val = Self;
+
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_select(cond.ids[0], a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
 impl<const INT_BITS: usize> TryIntoPlaintext for Fractional<INT_BITS> {
     fn try_into_plaintext(
         &self,
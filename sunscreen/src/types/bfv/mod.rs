@@ -1,10 +1,12 @@
 mod batched;
+mod bits;
 mod fractional;
 mod rational;
 mod signed;
 mod unsigned;
 
 pub use batched::*;
+pub use bits::*;
 pub use fractional::*;
 pub use rational::*;
 pub use signed::*;
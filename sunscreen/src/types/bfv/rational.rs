@@ -668,6 +668,25 @@ impl GraphCipherNeg for Rational {
     }
 }
 
+impl GraphCipherSelect for Rational {
+    type Cond = Signed;
+    type Val = Self;
+
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            // Select the numerator and denominator independently.
+            let num = ctx.add_select(cond.ids[0], a.ids[0], b.ids[0]);
+            let den = ctx.add_select(cond.ids[0], a.ids[1], b.ids[1]);
+
+            FheProgramNode::new(&[num, den])
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
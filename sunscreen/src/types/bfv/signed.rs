@@ -7,7 +7,8 @@ use crate::{
         ops::{
             GraphCipherAdd, GraphCipherConstAdd, GraphCipherConstMul, GraphCipherConstSub,
             GraphCipherMul, GraphCipherNeg, GraphCipherPlainAdd, GraphCipherPlainMul,
-            GraphCipherPlainSub, GraphCipherSub, GraphConstCipherSub, GraphPlainCipherSub,
+            GraphCipherPlainSub, GraphCipherSelect, GraphCipherSub, GraphConstCipherSub,
+            GraphPlainCipherSub,
         },
         Cipher,
     },
@@ -396,6 +397,23 @@ impl GraphCipherNeg for Signed {
     }
 }
 
+impl GraphCipherSelect for Signed {
+    type Cond = Self;
+    type Val = Self;
+
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_select(cond.ids[0], a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
 impl GraphCipherMul for Signed {
     type Left = Signed;
     type Right = Signed;
@@ -15,7 +15,7 @@ use crate::{
         ops::{
             GraphCipherAdd, GraphCipherConstAdd, GraphCipherConstMul, GraphCipherConstSub,
             GraphCipherMul, GraphCipherPlainAdd, GraphCipherPlainMul, GraphCipherPlainSub,
-            GraphCipherSub, GraphConstCipherSub, GraphPlainCipherSub,
+            GraphCipherSelect, GraphCipherSub, GraphConstCipherSub, GraphPlainCipherSub,
         },
         Cipher,
     },
@@ -284,6 +284,23 @@ impl_graph_cipher_op! {
     (Mul, multiplication)
 }
 
+impl<const LIMBS: usize> GraphCipherSelect for Unsigned<LIMBS> {
+    type Cond = Self;
+    type Val = Self;
+
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_select(cond.ids[0], a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
 impl<const LIMBS: usize> GraphConstCipherSub for Unsigned<LIMBS> {
     type Left = UInt<LIMBS>;
     type Right = Self;
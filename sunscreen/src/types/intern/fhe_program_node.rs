@@ -1,8 +1,8 @@
 use crate::{
     fhe::with_fhe_ctx,
     types::{
-        intern::FheLiteral, ops::*, Cipher, FheType, LaneCount, NumCiphertexts, SwapRows, Type,
-        TypeName,
+        intern::FheLiteral, ops::*, BroadcastLane, Cipher, FheType, LaneCount, NumCiphertexts,
+        SumLanes, SwapRows, Type, TypeName,
     },
     INDEX_ARENA,
 };
@@ -462,6 +462,28 @@ where
     }
 }
 
+impl<T> SumLanes for FheProgramNode<Cipher<T>>
+where
+    T: FheType + GraphCipherSumLanes,
+{
+    type Output = Self;
+
+    fn sum_lanes(self) -> Self::Output {
+        T::graph_cipher_sum_lanes(self)
+    }
+}
+
+impl<T> BroadcastLane for FheProgramNode<Cipher<T>>
+where
+    T: FheType + GraphCipherBroadcastLane,
+{
+    type Output = Self;
+
+    fn broadcast_lane(self, lane: (usize, usize)) -> Self::Output {
+        T::graph_cipher_broadcast_lane(self, lane)
+    }
+}
+
 impl<T> LaneCount for FheProgramNode<Cipher<T>>
 where
     T: FheType + LaneCount,
@@ -25,7 +25,10 @@
  * efficiently as the [`Signed`](crate::types::bfv::Signed) type. This type has complex overflow
  * conditions. This type intrinsically supports homomorphic addition
  * multiplication, and negation. Dividing by an [`f64`] constant is supported.
- * Dividing by ciphertext is not possible.
+ * Dividing by a ciphertext approximates the quotient and assumes the
+ * denominator lies in `[1, 2]`; use
+ * [`Fractional::div_approx`](crate::types::bfv::Fractional::div_approx) for
+ * other ranges.
  * * The [`Rational`](crate::types::bfv::Rational) type allows quasi fixed-point
  * representation. This type interally uses 2 ciphertexts, and is thus requires
  * twice as much space as other types. Its overflow semantics are effectively
@@ -39,20 +42,46 @@
  * Arithmetic operations semantically execute per-lane, enabling high-throughput;
  * e.g. a single addition operation `a + b` will element-wise add the many lanes of a to the
  * many lanes in b.
+ * * The [`BitUnsigned`](crate::types::bfv::BitUnsigned) and
+ * [`BitSigned`](crate::types::bfv::BitSigned) types encrypt each bit of a
+ * fixed-width integer in its own ciphertext. Arithmetic on them is not
+ * supported, but unlike the other types they support encrypted comparisons.
  * Type comparison:
  *
  * | Type       | # ciphertexts | overflow conditions | values            | ops/add        | ops/mul | ops/sub        | ops/neg | ops/div |
  * |------------|---------------|---------------------|-------------------|----------------|---------|----------------|---------|---------|
  * | Signed     | 1             | moderate            | signed integral   | 1 add          | 1 mul   | 1 sub          | 1 neg   | -       |
- * | Fractional | 1             | complex             | signed decimal    | 1 add          | 1 mul   | 1 sub          | 1 neg   | 1 mul*  |
+ * | Fractional | 1             | complex             | signed decimal    | 1 add          | 1 mul   | 1 sub          | 1 neg   | 1 mul*† |
  * | Rational   | 2             | moderate            | signed decimal    | 2 muls + 1 sub | 2 muls  | 2 muls + 1 sub | 1 neg   | 2 muls  |
+ * | BitSigned  | BITS          | none                | signed integral   | -              | -       | -              | -       | -       |
  *
- * `* Division by constant only.`
+ * `* Division by constant.`
+ *
+ * `† Division by ciphertext costs 2 muls per approximation iteration.`
  *
  * The set of feasible computations under FHE with BFV is fairly limited. For
  * example, comparisons, modulus, transcendentals, are generally very difficult
  * and are often infeasible depending on scheme parameters and noise budget.
  * One can sometimes *approximate* operations using Lagrange interpolation.
+ *
+ * In particular, [`Signed`](crate::types::bfv::Signed),
+ * [`Unsigned`](crate::types::bfv::Unsigned) and
+ * [`Fractional`](crate::types::bfv::Fractional) do not support encrypted
+ * comparison or equality. These types store binary digits in polynomial
+ * coefficients and ciphertext multiplication convolves these coefficients
+ * rather than operating on them individually, so techniques such as
+ * polynomial sign approximation don't yield a comparison on the encoded
+ * value.
+ *
+ * Instead, the [`BitUnsigned`](crate::types::bfv::BitUnsigned) and
+ * [`BitSigned`](crate::types::bfv::BitSigned) types encrypt each bit of an
+ * integer in its own ciphertext. These support [`lt`], [`le`], [`gt`],
+ * [`ge`], [`eq`], [`ne`], [`max`], [`min`] and [`select`]. Comparisons
+ * compile to dedicated less-than and inequality operations, which the
+ * backend compiler lowers to arithmetic circuits over the bits before
+ * inserting relinearizations and estimating noise. Comparisons return an
+ * encrypted [`Signed`](crate::types::bfv::Signed) 0 or 1 that you can pass
+ * to [`select`].
  */
 pub mod bfv;
 
@@ -69,12 +98,19 @@ pub mod intern;
  */
 mod ops;
 
+/**
+ * Polynomial evaluation over encrypted values.
+ */
+mod poly;
+
 /**
  * Contains types used in creating zero-knowledge proof R1CS circuits.
  */
 pub mod zkp;
 
-use crate::types::ops::*;
+use crate::types::{bfv::Signed, intern::FheProgramNode, ops::*};
+
+pub use poly::poly_eval;
 
 pub use sunscreen_runtime::{
     BfvType, FheType, NumCiphertexts, TryFromPlaintext, TryIntoPlaintext, Type, TypeName,
@@ -106,6 +142,204 @@ pub trait LaneCount {
     fn lane_count() -> usize;
 }
 
+/**
+ * A trait that allows data types to sum their lanes. E.g.
+ * [`Batched`](crate::types::bfv::Batched)
+ */
+pub trait SumLanes {
+    /**
+     * The result type. Typically, this should just be `Self`.
+     */
+    type Output;
+
+    /**
+     * Sums every lane in both rows and places the result in every lane.
+     */
+    fn sum_lanes(self) -> Self::Output;
+}
+
+/**
+ * A trait that allows data types to copy one lane into every other lane.
+ * E.g. [`Batched`](crate::types::bfv::Batched)
+ */
+pub trait BroadcastLane {
+    /**
+     * The result type. Typically, this should just be `Self`.
+     */
+    type Output;
+
+    /**
+     * Copies the value at `(row, col)` into every lane.
+     */
+    fn broadcast_lane(self, lane: (usize, usize)) -> Self::Output;
+}
+
+/**
+ * Computes the inner product of `a` and `b` over all lanes, placing the
+ * result in every lane. Works both on [`Batched`](crate::types::bfv::Batched)
+ * values and on `Cipher<Batched<_>>` inside an
+ * [`fhe_program`](crate::fhe_program).
+ *
+ * # Remarks
+ * Encrypted, this costs one ciphertext multiplication, `log2(LANES)`
+ * rotations, and one row swap.
+ */
+pub fn dot<T>(a: T, b: T) -> T
+where
+    T: std::ops::Mul<Output = T> + SumLanes<Output = T>,
+{
+    (a * b).sum_lanes()
+}
+
+/**
+ * Homomorphically selects between two ciphertexts inside an
+ * [`fhe_program`](crate::fhe_program). Returns `a` if `cond` encrypts 1 and
+ * `b` if `cond` encrypts 0.
+ *
+ * # Remarks
+ * For [`Batched`](crate::types::bfv::Batched) types, the selection happens
+ * per-lane and each lane of `cond` must be 0 or 1. For
+ * [`Rational`](crate::types::bfv::Rational), `cond` is a
+ * [`Signed`](crate::types::bfv::Signed) value.
+ *
+ * If `cond` encrypts a value other than 0 or 1, the result is
+ * `b + cond * (a - b)`, which is generally not meaningful.
+ *
+ * A select costs one ciphertext multiplication per ciphertext in `T`.
+ */
+pub fn select<T>(
+    cond: FheProgramNode<Cipher<T::Cond>>,
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<T>>
+where
+    T: FheType + GraphCipherSelect<Val = T>,
+{
+    T::graph_cipher_select(cond, a, b)
+}
+
+/**
+ * Homomorphically computes `a < b` inside an
+ * [`fhe_program`](crate::fhe_program), returning an encrypted 1 if true and
+ * 0 otherwise.
+ *
+ * # Remarks
+ * Comparisons are supported on the bitwise types
+ * [`BitUnsigned`](crate::types::bfv::BitUnsigned) and
+ * [`BitSigned`](crate::types::bfv::BitSigned). For `BITS` bits, this costs
+ * about `2 * BITS` ciphertext multiplications with multiplicative depth
+ * `1 + ceil(log2(BITS))`.
+ */
+pub fn lt<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    T::graph_cipher_lt(a, b)
+}
+
+/**
+ * Homomorphically computes `a > b`. See [`lt`].
+ */
+pub fn gt<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    T::graph_cipher_lt(b, a)
+}
+
+/**
+ * Homomorphically computes `a <= b`. See [`lt`].
+ */
+pub fn le<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    1 - T::graph_cipher_lt(b, a)
+}
+
+/**
+ * Homomorphically computes `a >= b`. See [`lt`].
+ */
+pub fn ge<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    1 - T::graph_cipher_lt(a, b)
+}
+
+/**
+ * Homomorphically computes `a == b` inside an
+ * [`fhe_program`](crate::fhe_program), returning an encrypted 1 if true and
+ * 0 otherwise.
+ *
+ * # Remarks
+ * For `BITS` bits, this costs about `2 * BITS` ciphertext multiplications
+ * with multiplicative depth `1 + ceil(log2(BITS))`. See [`lt`] for the
+ * supported types.
+ */
+pub fn eq<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    T::graph_cipher_eq(a, b)
+}
+
+/**
+ * Homomorphically computes `a != b`. See [`eq`].
+ */
+pub fn ne<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<Signed>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    1 - T::graph_cipher_eq(a, b)
+}
+
+/**
+ * Homomorphically computes the larger of `a` and `b`. This costs a [`lt`]
+ * and one ciphertext multiplication per ciphertext in `T`.
+ */
+pub fn max<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<T>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    T::graph_cipher_max(a, b)
+}
+
+/**
+ * Homomorphically computes the smaller of `a` and `b`. This costs a [`lt`]
+ * and one ciphertext multiplication per ciphertext in `T`.
+ */
+pub fn min<T>(
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<T>>
+where
+    T: FheType + GraphCipherCompare<Val = T>,
+{
+    T::graph_cipher_min(a, b)
+}
+
 #[derive(Copy, Clone, Debug)]
 /**
  * Declares a type T as being encrypted in an [`fhe_program`](crate::fhe_program).
@@ -0,0 +1,51 @@
+use crate::types::{
+    bfv::Signed,
+    intern::{FheProgramNode, FheType},
+    Cipher,
+};
+
+/**
+ * Called when the user compares two ciphertexts (e.g. `lt(a, b)`,
+ * `eq(a, b)` or `max(a, b)`).
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
+pub trait GraphCipherCompare {
+    /**
+     * The type of the compared values.
+     */
+    type Val: FheType;
+
+    /**
+     * Returns an encrypted 1 if `a < b` and an encrypted 0 otherwise.
+     */
+    fn graph_cipher_lt(
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Signed>>;
+
+    /**
+     * Returns an encrypted 1 if `a == b` and an encrypted 0 otherwise.
+     */
+    fn graph_cipher_eq(
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Signed>>;
+
+    /**
+     * Returns the larger of `a` and `b`.
+     */
+    fn graph_cipher_max(
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>>;
+
+    /**
+     * Returns the smaller of `a` and `b`.
+     */
+    fn graph_cipher_min(
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>>;
+}
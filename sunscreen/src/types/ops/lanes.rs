@@ -0,0 +1,36 @@
+use crate::types::{intern::FheProgramNode, Cipher, FheType};
+
+/**
+ * Sums every lane of the given ciphertext.
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
+pub trait GraphCipherSumLanes
+where
+    Self: FheType,
+{
+    /**
+     * Sum the lanes in the given ciphertext, placing the result in every lane.
+     */
+    fn graph_cipher_sum_lanes(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>>;
+}
+
+/**
+ * Copies a single lane of the given ciphertext into every lane.
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
+pub trait GraphCipherBroadcastLane
+where
+    Self: FheType,
+{
+    /**
+     * Broadcast the lane at `(row, col)` into every lane.
+     */
+    fn graph_cipher_broadcast_lane(
+        x: FheProgramNode<Cipher<Self>>,
+        lane: (usize, usize),
+    ) -> FheProgramNode<Cipher<Self>>;
+}
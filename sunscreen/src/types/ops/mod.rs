@@ -1,13 +1,19 @@
 mod add;
+mod compare;
 mod div;
+mod lanes;
 mod mul;
 mod neg;
 mod rotate;
+mod select;
 mod sub;
 
 pub use add::*;
+pub use compare::*;
 pub use div::*;
+pub use lanes::*;
 pub use mul::*;
 pub use neg::*;
 pub use rotate::*;
+pub use select::*;
 pub use sub::*;
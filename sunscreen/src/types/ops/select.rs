@@ -0,0 +1,32 @@
+use crate::types::{
+    intern::{FheProgramNode, FheType},
+    Cipher,
+};
+
+/**
+ * Called when the user performs an encrypted select (i.e. `if cond { a } else { b }`)
+ * on ciphertexts.
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
+pub trait GraphCipherSelect {
+    /**
+     * The type of the condition. The condition must encrypt either 0 or 1.
+     */
+    type Cond: FheType;
+
+    /**
+     * The type of the selected values.
+     */
+    type Val: FheType;
+
+    /**
+     * Returns `a` if `cond` encrypts 1 and `b` if `cond` encrypts 0.
+     */
+    fn graph_cipher_select(
+        cond: FheProgramNode<Cipher<Self::Cond>>,
+        a: FheProgramNode<Cipher<Self::Val>>,
+        b: FheProgramNode<Cipher<Self::Val>>,
+    ) -> FheProgramNode<Cipher<Self::Val>>;
+}
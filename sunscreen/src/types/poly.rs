@@ -0,0 +1,166 @@
+use crate::types::{
+    intern::{FheLiteral, FheProgramNode},
+    ops::*,
+    Cipher, FheType,
+};
+
+/**
+ * A partially evaluated polynomial chunk. Chunks whose only nonzero
+ * coefficient is the constant term don't touch the ciphertext.
+ */
+enum Chunk<T: FheType, U> {
+    Const(U),
+    Cipher(FheProgramNode<Cipher<T>>),
+}
+
+/**
+ * Homomorphically evaluates the polynomial
+ * `coeffs[0] + coeffs[1] * x + ... + coeffs[d] * x^d` inside an
+ * [`fhe_program`](crate::fhe_program).
+ *
+ * # Remarks
+ * This function uses the Paterson–Stockmeyer method. It first computes the
+ * "baby step" powers `x, x^2, ..., x^k`, where `k` is a power of two near
+ * `sqrt(d / 2)`, and the "giant step" powers `x^k, x^2k, x^4k, ...` by
+ * repeated squaring. It then recursively splits the polynomial at the
+ * largest giant step as `q(x) * x^(k * 2^j) + r(x)`. Chunks of degree less
+ * than `k` evaluate as a linear combination of baby steps, which costs only
+ * constant multiplications.
+ *
+ * A degree `d` polynomial thus costs roughly `2 * sqrt(d)` ciphertext
+ * multiplications and a multiplicative depth of about `log2(d) + 1`. A naive
+ * power chain, by contrast, costs `d` multiplications and depth `d`. Lower
+ * depth lets [`Compiler`](crate::Compiler) choose smaller scheme parameters.
+ *
+ * Zero coefficients are skipped.
+ *
+ * # Panics
+ * If `coeffs` has fewer than 2 elements, as the result would not depend
+ * on `x` and thus cannot be a ciphertext.
+ */
+pub fn poly_eval<T, U>(x: FheProgramNode<Cipher<T>>, coeffs: &[U]) -> FheProgramNode<Cipher<T>>
+where
+    T: FheType
+        + Copy
+        + GraphCipherAdd<Left = T, Right = T>
+        + GraphCipherMul<Left = T, Right = T>
+        + GraphCipherConstAdd<Left = T, Right = U>
+        + GraphCipherConstMul<Left = T, Right = U>,
+    U: FheLiteral + Copy + Default + PartialEq,
+{
+    assert!(
+        coeffs.len() >= 2,
+        "poly_eval requires a polynomial of at least degree 1."
+    );
+
+    let degree = coeffs.len() - 1;
+
+    // Choose k = 2^l close to sqrt(d / 2), which balances the number of
+    // baby step and giant step multiplications.
+    let mut k = 1;
+
+    while 2 * k * k < degree {
+        k *= 2;
+    }
+
+    // baby_steps[i] = x^(i + 1). We compute x^i as
+    // x^(2^floor(log2(i))) * x^(i - 2^floor(log2(i))), giving each power
+    // depth ceil(log2(i)).
+    let mut baby_steps = vec![x];
+
+    for i in 2..=k {
+        let high = 1 << (usize::BITS - 1 - i.leading_zeros());
+        let low = i - high;
+
+        let next = if low == 0 {
+            let half = baby_steps[high / 2 - 1];
+            T::graph_cipher_mul(half, half)
+        } else {
+            T::graph_cipher_mul(baby_steps[high - 1], baby_steps[low - 1])
+        };
+
+        baby_steps.push(next);
+    }
+
+    // giant_steps[j] = x^(k * 2^j)
+    let mut giant_steps = vec![baby_steps[k - 1]];
+
+    while k << giant_steps.len() <= degree {
+        let last = giant_steps[giant_steps.len() - 1];
+        giant_steps.push(T::graph_cipher_mul(last, last));
+    }
+
+    match eval_chunk(coeffs, &baby_steps, &giant_steps) {
+        Chunk::Cipher(n) => n,
+        // Only happens if every non-constant coefficient is zero.
+        Chunk::Const(c) => {
+            let zero = T::graph_cipher_const_mul(x, U::default());
+            T::graph_cipher_const_add(zero, c)
+        }
+    }
+}
+
+fn eval_chunk<T, U>(
+    coeffs: &[U],
+    baby_steps: &[FheProgramNode<Cipher<T>>],
+    giant_steps: &[FheProgramNode<Cipher<T>>],
+) -> Chunk<T, U>
+where
+    T: FheType
+        + Copy
+        + GraphCipherAdd<Left = T, Right = T>
+        + GraphCipherMul<Left = T, Right = T>
+        + GraphCipherConstAdd<Left = T, Right = U>
+        + GraphCipherConstMul<Left = T, Right = U>,
+    U: FheLiteral + Copy + Default + PartialEq,
+{
+    let zero = U::default();
+
+    if coeffs.len() <= baby_steps.len() + 1 {
+        let mut acc: Option<FheProgramNode<Cipher<T>>> = None;
+
+        for (c, power) in coeffs.iter().skip(1).zip(baby_steps) {
+            if *c == zero {
+                continue;
+            }
+
+            let term = T::graph_cipher_const_mul(*power, *c);
+
+            acc = Some(match acc {
+                Some(acc) => T::graph_cipher_add(acc, term),
+                None => term,
+            });
+        }
+
+        return match acc {
+            Some(acc) if coeffs[0] == zero => Chunk::Cipher(acc),
+            Some(acc) => Chunk::Cipher(T::graph_cipher_const_add(acc, coeffs[0])),
+            None => Chunk::Const(coeffs[0]),
+        };
+    }
+
+    // Split at the largest giant step x^m with m < coeffs.len().
+    let k = baby_steps.len();
+    let mut j = 0;
+
+    while j + 1 < giant_steps.len() && k << (j + 1) < coeffs.len() {
+        j += 1;
+    }
+
+    let m = k << j;
+
+    let low = eval_chunk(&coeffs[..m], baby_steps, giant_steps);
+    let high = eval_chunk(&coeffs[m..], baby_steps, giant_steps);
+
+    let high = match high {
+        Chunk::Const(c) if c == zero => return low,
+        Chunk::Const(c) => T::graph_cipher_const_mul(giant_steps[j], c),
+        Chunk::Cipher(n) => T::graph_cipher_mul(n, giant_steps[j]),
+    };
+
+    match low {
+        Chunk::Const(c) if c == zero => Chunk::Cipher(high),
+        Chunk::Const(c) => Chunk::Cipher(T::graph_cipher_const_add(high, c)),
+        Chunk::Cipher(n) => Chunk::Cipher(T::graph_cipher_add(high, n)),
+    }
+}
@@ -0,0 +1,252 @@
+use std::marker::PhantomData;
+
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_runtime::ZkpProgramInputTrait;
+use sunscreen_zkp_backend::{BackendField, BigInt};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::SignedModulus, AddVar, ConstrainEqVarVar, FixedWidth, IntoProgramNode, MulVar,
+        NativeField, NegVar, NumFieldElements, ProgramNode, SubVar, ToNativeFields, ZkpType,
+    },
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use super::integer::{assert_in_range, assert_mul_fits, assert_width_fits, checked, encode_i64};
+
+use crate as sunscreen;
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed fixed-point number with `INT` integer bits (including the sign)
+ * and `FRAC` fractional bits. Values lie in `[-2^(INT-1), 2^(INT-1))` with
+ * a resolution of `2^-FRAC`.
+ *
+ * # Remarks
+ * We represent `x` as the signed `INT + FRAC`-bit integer
+ * `round(x * 2^FRAC)`. Multiplication rounds towards negative infinity.
+ * Arithmetic on these values fails to prove if it overflows.
+ */
+pub struct ZkpFixed<F: BackendField, const INT: usize, const FRAC: usize> {
+    val: BigInt,
+    _phantom: PhantomData<F>,
+}
+
+// Can't #[derive()] due to PhantomData.
+impl<F: BackendField, const INT: usize, const FRAC: usize> Copy for ZkpFixed<F, INT, FRAC> {}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> From<f64> for ZkpFixed<F, INT, FRAC> {
+    fn from(x: f64) -> Self {
+        assert!(INT > 0, "ZkpFixed must have at least 1 integer bit.");
+        assert_width_fits::<F, Self>();
+
+        let scaled = (x * (FRAC as f64).exp2()).round();
+        let bound = ((INT + FRAC - 1) as f64).exp2().min(2f64.powi(63));
+
+        assert!(
+            scaled >= -bound && scaled < bound,
+            "{x} doesn't fit in a fixed-point value with {INT} integer bits."
+        );
+
+        Self {
+            val: encode_i64::<F>(scaled as i64),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> From<ZkpFixed<F, INT, FRAC>>
+    for NativeField<F>
+{
+    fn from(x: ZkpFixed<F, INT, FRAC>) -> Self {
+        x.val.into()
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> NumFieldElements
+    for ZkpFixed<F, INT, FRAC>
+{
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> ToNativeFields
+    for ZkpFixed<F, INT, FRAC>
+{
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        vec![self.val]
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> ZkpType for ZkpFixed<F, INT, FRAC> {
+    fn constrain_input(input: &ProgramNode<Self>) {
+        assert_in_range::<F, Self>(input.ids[0]);
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> ZkpProgramInputTrait
+    for ZkpFixed<F, INT, FRAC>
+{
+}
+
+/**
+ * Comparisons and conversions treat a fixed-point value as its underlying
+ * `INT + FRAC`-bit integer, which preserves ordering.
+ */
+impl<F: BackendField, const INT: usize, const FRAC: usize> FixedWidth<F>
+    for ZkpFixed<F, INT, FRAC>
+{
+    const WIDTH: usize = INT + FRAC;
+    const SIGNED: bool = true;
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> AddVar for ZkpFixed<F, INT, FRAC> {
+    fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(with_zkp_ctx(|ctx| ctx.add_addition(lhs.ids[0], rhs.ids[0])))
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> SubVar for ZkpFixed<F, INT, FRAC> {
+    fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(with_zkp_ctx(|ctx| {
+            ctx.add_subtraction(lhs.ids[0], rhs.ids[0])
+        }))
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> MulVar for ZkpFixed<F, INT, FRAC> {
+    fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        assert_mul_fits::<F, Self>();
+
+        let prod = with_zkp_ctx(|ctx| ctx.add_multiplication(lhs.ids[0], rhs.ids[0]));
+
+        if FRAC == 0 {
+            return checked::<F, Self>(prod);
+        }
+
+        // The product has 2 * FRAC fractional bits. Divide by 2^FRAC to
+        // restore the scale.
+        let scale =
+            with_zkp_ctx(|ctx| ctx.add_constant(&BigInt::from(BigInt::ONE.shl_vartime(FRAC))));
+
+        let outputs = invoke_gadget(SignedModulus::new(F::FIELD_MODULUS, FRAC), &[prod, scale]);
+
+        checked::<F, Self>(outputs[0])
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> NegVar for ZkpFixed<F, INT, FRAC> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(with_zkp_ctx(|ctx| ctx.add_negate(lhs.ids[0])))
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> ConstrainEqVarVar
+    for ZkpFixed<F, INT, FRAC>
+{
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| {
+            let sub = ctx.add_subtraction(lhs.ids[0], rhs.ids[0]);
+
+            let constraint = ctx.add_constraint(sub, &BigInt::ZERO);
+
+            ProgramNode::new(&[constraint])
+        })
+    }
+}
+
+impl<F: BackendField, const INT: usize, const FRAC: usize> IntoProgramNode
+    for ZkpFixed<F, INT, FRAC>
+{
+    type Output = Self;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::scalar::Scalar;
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::Compiler;
+
+    use super::*;
+
+    type BpField = <BulletproofsBackend as ZkpBackend>::Field;
+    type Fixed = ZkpFixed<BpField, 8, 4>;
+
+    #[test]
+    fn can_encode_fixed_point_values() {
+        assert_eq!(ZkpFixed::<Scalar, 8, 4>::from(1.5).val, BigInt::from(24u32));
+        assert_eq!(
+            ZkpFixed::<Scalar, 8, 4>::from(-0.0625).val,
+            BigInt::from(Scalar::FIELD_MODULUS.wrapping_sub(&BigInt::ONE))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_rejects_out_of_range_values() {
+        let _ = ZkpFixed::<Scalar, 8, 4>::from(128.0);
+    }
+
+    #[test]
+    fn fixed_arithmetic_works() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn fixed<F: BackendField>(
+            a: ZkpFixed<F, 8, 4>,
+            b: ZkpFixed<F, 8, 4>,
+            #[public] sum: ZkpFixed<F, 8, 4>,
+            #[public] prod: ZkpFixed<F, 8, 4>,
+        ) {
+            (a + b).constrain_eq(sum);
+            (a * b).constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(fixed)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(fixed).unwrap();
+
+        let test_case = |a: f64, b: f64, sum: f64, prod: f64, expect_success: bool| {
+            let public = vec![Fixed::from(sum), Fixed::from(prod)];
+
+            let result = runtime.prove(
+                prog,
+                vec![],
+                public.clone(),
+                vec![Fixed::from(a), Fixed::from(b)],
+            );
+
+            let proof = if expect_success {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime.verify(prog, &proof, vec![], public).unwrap();
+        };
+
+        test_case(1.5, -2.25, -0.75, -3.375, true);
+
+        // Products round towards negative infinity.
+        test_case(0.0625, 0.5, 0.5625, 0.0, true);
+        test_case(-0.0625, 0.5, 0.4375, -0.0625, true);
+
+        // Product overflows.
+        test_case(10.0, 20.0, 30.0, 0.0, false);
+    }
+}
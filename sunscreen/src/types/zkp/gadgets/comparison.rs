@@ -0,0 +1,406 @@
+use petgraph::stable_graph::NodeIndex;
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use sunscreen_zkp_backend::{BigInt, Gadget};
+
+use crate::{invoke_gadget, with_zkp_ctx, zkp::ZkpContextOps, ZkpResult};
+
+use super::{Select, ToUInt};
+
+/**
+ * Outputs 1 if the input is zero and 0 otherwise. We do this by:
+ * * Computing hidden inputs `out` and `inv`, where `inv` is `x^-1` if
+ *   `x != 0` and 0 otherwise.
+ * * Constrain x * inv + out = 1
+ * * Constrain x * out = 0
+ */
+pub struct IsZero {
+    field_modulus: BigInt,
+}
+
+impl IsZero {
+    /**
+     * Creates a new [`IsZero`] gadget.
+     *
+     * # Panics
+     * * When `field_modulus == 0`
+     */
+    pub fn new(field_modulus: BigInt) -> Self {
+        assert_ne!(field_modulus, BigInt::ZERO);
+
+        Self { field_modulus }
+    }
+}
+
+impl Gadget for IsZero {
+    fn compute_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let x = gadget_inputs[0];
+
+        let is_zero = x.ct_eq(&BigInt::ZERO);
+
+        // Invert 1 instead of 0 so this runs in constant time.
+        let safe_x = BigInt::conditional_select(&x, &BigInt::ONE, is_zero);
+        let inv = safe_x.inverse_fp(&self.field_modulus);
+
+        let inv = BigInt::conditional_select(&inv, &BigInt::ZERO, is_zero);
+        let out = BigInt::conditional_select(&BigInt::ZERO, &BigInt::ONE, is_zero);
+
+        Ok(vec![inv, out])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let x = gadget_inputs[0];
+        let inv = hidden_inputs[0];
+        let out = hidden_inputs[1];
+
+        with_zkp_ctx(|ctx| {
+            let prod = ctx.add_multiplication(x, inv);
+            let sum = ctx.add_addition(prod, out);
+            ctx.add_constraint(sum, &BigInt::ONE);
+
+            let prod = ctx.add_multiplication(x, out);
+            ctx.add_constraint(prod, &BigInt::ZERO);
+        });
+
+        vec![out]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        1
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        2
+    }
+}
+
+/**
+ * For inputs `a` and `b`, outputs 1 if `a < b` and 0 otherwise. We do this
+ * by decomposing `t = a - b + 2^n` into `n + 1` bits. The top bit is set
+ * exactly when `a >= b`.
+ *
+ * # Remarks
+ * `a` and `b` must lie in `[0, 2^n)`. This gadget doesn't check this;
+ * callers should establish it, e.g. with [`ToUInt`].
+ */
+pub struct LessThan {
+    n: usize,
+}
+
+impl LessThan {
+    /**
+     * Creates a new [`LessThan`] gadget for `n`-bit inputs.
+     *
+     * # Panics
+     * * If n >= 512
+     */
+    pub fn new(n: usize) -> Self {
+        assert!(n < 512, "Cannot compare >= 512 bit values.");
+
+        Self { n }
+    }
+}
+
+impl Gadget for LessThan {
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let a = gadget_inputs[0];
+        let b = gadget_inputs[1];
+
+        let t = with_zkp_ctx(|ctx| {
+            let offset = ctx.add_constant(&BigInt::from(BigInt::ONE.shl_vartime(self.n)));
+
+            let diff = ctx.add_subtraction(a, b);
+            ctx.add_addition(diff, offset)
+        });
+
+        let bits = invoke_gadget(ToUInt::new(self.n + 1), &[t]);
+
+        let lt = with_zkp_ctx(|ctx| {
+            let one = ctx.add_constant(&BigInt::ONE);
+
+            ctx.add_subtraction(one, bits[self.n])
+        });
+
+        vec![lt]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        2
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * For inputs `x`, `lo` and `hi`, proves `lo <= x <= hi` by showing
+ * `x - lo` and `hi - x` both decompose into `n`-bit unsigned values.
+ *
+ * # Remarks
+ * `n` should be the number of bits needed to represent `hi - lo`.
+ */
+pub struct AssertInRange {
+    n: usize,
+}
+
+impl AssertInRange {
+    /**
+     * Creates a new [`AssertInRange`] gadget.
+     *
+     * # Panics
+     * * If n > 512
+     */
+    pub fn new(n: usize) -> Self {
+        assert!(n <= 512, "Cannot range check > 512 bit values.");
+
+        Self { n }
+    }
+}
+
+impl Gadget for AssertInRange {
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let x = gadget_inputs[0];
+        let lo = gadget_inputs[1];
+        let hi = gadget_inputs[2];
+
+        let (above, below) =
+            with_zkp_ctx(|ctx| (ctx.add_subtraction(x, lo), ctx.add_subtraction(hi, x)));
+
+        invoke_gadget(ToUInt::new(self.n), &[above]);
+        invoke_gadget(ToUInt::new(self.n), &[below]);
+
+        vec![]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        3
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * For `n`-bit inputs `a` and `b`, outputs the lesser of the two.
+ *
+ * # Remarks
+ * See [`LessThan`] for the requirements on `a` and `b`.
+ */
+pub struct Min {
+    n: usize,
+}
+
+impl Min {
+    /**
+     * Creates a new [`Min`] gadget for `n`-bit inputs.
+     *
+     * # Panics
+     * * If n >= 512
+     */
+    pub fn new(n: usize) -> Self {
+        assert!(n < 512, "Cannot compare >= 512 bit values.");
+
+        Self { n }
+    }
+}
+
+impl Gadget for Min {
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let a = gadget_inputs[0];
+        let b = gadget_inputs[1];
+
+        let lt = invoke_gadget(LessThan::new(self.n), &[a, b]);
+
+        invoke_gadget(Select, &[lt[0], a, b])
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        2
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * For `n`-bit inputs `a` and `b`, outputs the greater of the two.
+ *
+ * # Remarks
+ * See [`LessThan`] for the requirements on `a` and `b`.
+ */
+pub struct Max {
+    n: usize,
+}
+
+impl Max {
+    /**
+     * Creates a new [`Max`] gadget for `n`-bit inputs.
+     *
+     * # Panics
+     * * If n >= 512
+     */
+    pub fn new(n: usize) -> Self {
+        assert!(n < 512, "Cannot compare >= 512 bit values.");
+
+        Self { n }
+    }
+}
+
+impl Gadget for Max {
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let a = gadget_inputs[0];
+        let b = gadget_inputs[1];
+
+        let lt = invoke_gadget(LessThan::new(self.n), &[a, b]);
+
+        invoke_gadget(Select, &[lt[0], b, a])
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        2
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, BackendField, ZkpBackend};
+
+    use crate::types::zkp::{Compare, ConstrainCmp, NativeField};
+    use crate::{self as sunscreen, Compiler};
+
+    use super::*;
+
+    type BpField = NativeField<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn is_zero_compute_inputs_is_correct() {
+        let field_modulus = <BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS;
+        let gadget = IsZero::new(field_modulus);
+
+        assert_eq!(
+            gadget.compute_inputs(&[BigInt::ZERO]).unwrap(),
+            vec![BigInt::ZERO, BigInt::ONE]
+        );
+
+        let x = BigInt::from(7u32);
+        let outputs = gadget.compute_inputs(&[x]).unwrap();
+
+        assert_eq!(outputs[1], BigInt::ZERO);
+        assert_eq!(
+            BigInt::from(x.wrapping_mul(&outputs[0]).reduce(&field_modulus).unwrap()),
+            BigInt::ONE
+        );
+    }
+
+    #[test]
+    fn comparison_gadgets_work() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn compare<F: BackendField>(
+            a: NativeField<F>,
+            b: NativeField<F>,
+            expected_lt: NativeField<F>,
+            expected_a_is_zero: NativeField<F>,
+        ) {
+            a.is_lt_bounded(b, 8).constrain_eq(expected_lt);
+            a.is_zero().constrain_eq(expected_a_is_zero);
+
+            let min = a.min_bounded(b, 8);
+            let max = a.max_bounded(b, 8);
+
+            // min + max == a + b and min <= max pin both down.
+            (min + max).constrain_eq(a + b);
+            min.constrain_le_bounded(max, 8);
+
+            a.assert_in_range_bounded(min, max, 8);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(compare)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(compare).unwrap();
+
+        let test_case = |a: u8, b: u8, lt: u8, is_zero: u8, expect_success: bool| {
+            let result = runtime.prove(
+                prog,
+                vec![],
+                vec![],
+                vec![
+                    BpField::from(a),
+                    BpField::from(b),
+                    BpField::from(lt),
+                    BpField::from(is_zero),
+                ],
+            );
+
+            let proof = if expect_success {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(prog, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(3, 200, 1, 0, true);
+        test_case(200, 3, 0, 0, true);
+        test_case(0, 0, 0, 1, true);
+        test_case(0, 5, 1, 1, true);
+
+        // Wrong claims fail.
+        test_case(3, 200, 0, 0, false);
+        test_case(4, 4, 1, 0, false);
+        test_case(0, 5, 1, 0, false);
+    }
+}
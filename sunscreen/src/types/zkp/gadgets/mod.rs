@@ -1,5 +1,11 @@
 mod arithmetic;
 mod binary;
+mod comparison;
+mod poseidon;
+mod select;
 
 pub use arithmetic::*;
 pub use binary::*;
+pub use comparison::*;
+pub use poseidon::*;
+pub use select::*;
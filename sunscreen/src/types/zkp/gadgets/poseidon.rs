@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, Gadget};
+
+use crate::{
+    types::zkp::{PoseidonParams, POSEIDON_WIDTH},
+    with_zkp_ctx,
+    zkp::ZkpContextOps,
+    ZkpResult,
+};
+
+/**
+ * Applies the Poseidon permutation to its inputs and outputs the resulting
+ * state.
+ *
+ * # Remarks
+ * The hidden inputs are the state after each round, which we constrain to
+ * equal that round's additions, multiplications and constant
+ * multiplications. Without them, every round would mix its input's linear
+ * combinations into the next, and their size would grow exponentially in
+ * the number of partial rounds.
+ *
+ * See [`PoseidonParams`] for the permutation's parameters.
+ */
+pub struct PoseidonPermutation {
+    params: Arc<PoseidonParams>,
+}
+
+impl PoseidonPermutation {
+    /**
+     * Creates a new [`PoseidonPermutation`] gadget.
+     */
+    pub fn new(params: Arc<PoseidonParams>) -> Self {
+        Self { params }
+    }
+}
+
+impl Gadget for PoseidonPermutation {
+    fn compute_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let params = &self.params;
+
+        let mut state = [BigInt::ZERO; POSEIDON_WIDTH];
+        state.copy_from_slice(gadget_inputs);
+
+        let mut hidden = vec![];
+
+        for round in 0..params.rounds() {
+            state = params.apply_round(round, state);
+            hidden.extend(state);
+        }
+
+        Ok(hidden)
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let params = &self.params;
+
+        with_zkp_ctx(|ctx| {
+            let mut state = gadget_inputs.to_owned();
+
+            for (round, round_outputs) in hidden_inputs.chunks(POSEIDON_WIDTH).enumerate() {
+                for (s, c) in state.iter_mut().zip(params.round_constants(round)) {
+                    let c = ctx.add_constant(c);
+                    *s = ctx.add_addition(*s, c);
+                }
+
+                let sbox_count = if params.is_full_round(round) {
+                    POSEIDON_WIDTH
+                } else {
+                    1
+                };
+
+                for s in state.iter_mut().take(sbox_count) {
+                    let x2 = ctx.add_multiplication(*s, *s);
+                    let x4 = ctx.add_multiplication(x2, x2);
+                    *s = ctx.add_multiplication(x4, *s);
+                }
+
+                for (row, out) in params.mds().iter().zip(round_outputs) {
+                    let terms = state
+                        .iter()
+                        .zip(row.iter())
+                        .map(|(s, m)| {
+                            let m = ctx.add_constant(m);
+                            ctx.add_multiplication(*s, m)
+                        })
+                        .collect::<Vec<NodeIndex>>();
+
+                    let mixed = terms
+                        .iter()
+                        .skip(1)
+                        .fold(terms[0], |acc, t| ctx.add_addition(acc, *t));
+
+                    let diff = ctx.add_subtraction(mixed, *out);
+                    ctx.add_constraint(diff, &BigInt::ZERO);
+                }
+
+                state = round_outputs.to_owned();
+            }
+
+            state
+        })
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        POSEIDON_WIDTH
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        self.params.rounds() * POSEIDON_WIDTH
+    }
+}
@@ -0,0 +1,237 @@
+use petgraph::stable_graph::NodeIndex;
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use sunscreen_zkp_backend::{BigInt, Gadget};
+
+use crate::{invoke_gadget, with_zkp_ctx, zkp::ZkpContextOps, ZkpError, ZkpResult};
+
+use super::AssertBinary;
+
+/**
+ * For inputs `c`, `a` and `b`, outputs `a` if `c == 1` and `b` if
+ * `c == 0`. We do this by:
+ * * Asserting `c` is binary.
+ * * Computing `b + c * (a - b)`.
+ */
+pub struct Select;
+
+impl Gadget for Select {
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        Ok(vec![])
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let c = gadget_inputs[0];
+        let a = gadget_inputs[1];
+        let b = gadget_inputs[2];
+
+        invoke_gadget(AssertBinary, &[c]);
+
+        let out = with_zkp_ctx(|ctx| {
+            let diff = ctx.add_subtraction(a, b);
+            let scaled = ctx.add_multiplication(c, diff);
+
+            ctx.add_addition(b, scaled)
+        });
+
+        vec![out]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        3
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        0
+    }
+}
+
+/**
+ * For input `i`, outputs `table[i]`, where `table` is public. We do this
+ * by:
+ * * Computing hidden one-hot selectors `s_0..s_n`, where `s_i = 1`.
+ * * Asserting each selector is binary.
+ * * Constrain `sum(s_j) = 1`
+ * * Constrain `sum(j * s_j) = i`
+ * * Computing `sum(table[j] * s_j)`.
+ *
+ * # Remarks
+ * The circuit grows linearly with the table's length, so this suits only
+ * small tables.
+ */
+pub struct TableLookup {
+    table: Vec<BigInt>,
+}
+
+impl TableLookup {
+    /**
+     * Creates a new [`TableLookup`] gadget.
+     *
+     * # Panics
+     * * If `table` is empty
+     */
+    pub fn new(table: &[BigInt]) -> Self {
+        assert!(!table.is_empty(), "Cannot look up into an empty table.");
+
+        Self {
+            table: table.to_owned(),
+        }
+    }
+}
+
+impl Gadget for TableLookup {
+    fn compute_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let i = gadget_inputs[0];
+
+        if i >= BigInt::from(self.table.len() as u64) {
+            return Err(ZkpError::gadget_error(&format!(
+                "Table index out of bounds. The table has {} entries.",
+                self.table.len()
+            )));
+        }
+
+        Ok((0..self.table.len())
+            .map(|j| {
+                let is_i = i.ct_eq(&BigInt::from(j as u64));
+
+                BigInt::conditional_select(&BigInt::ZERO, &BigInt::ONE, is_i)
+            })
+            .collect())
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let i = gadget_inputs[0];
+
+        for s in hidden_inputs {
+            invoke_gadget(AssertBinary, &[*s]);
+        }
+
+        let out = with_zkp_ctx(|ctx| {
+            let mut count = hidden_inputs[0];
+            let mut index = ctx.add_constant(&BigInt::ZERO);
+
+            let entry = ctx.add_constant(&self.table[0]);
+            let mut out = ctx.add_multiplication(hidden_inputs[0], entry);
+
+            for (j, s) in hidden_inputs.iter().enumerate().skip(1) {
+                count = ctx.add_addition(count, *s);
+
+                let j_node = ctx.add_constant(&BigInt::from(j as u64));
+                let term = ctx.add_multiplication(*s, j_node);
+                index = ctx.add_addition(index, term);
+
+                let entry = ctx.add_constant(&self.table[j]);
+                let term = ctx.add_multiplication(*s, entry);
+                out = ctx.add_addition(out, term);
+            }
+
+            ctx.add_constraint(count, &BigInt::ONE);
+
+            let diff = ctx.add_subtraction(index, i);
+            ctx.add_constraint(diff, &BigInt::ZERO);
+
+            out
+        });
+
+        vec![out]
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        1
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        self.table.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, BackendField, ZkpBackend};
+
+    use crate::types::zkp::{NativeField, Selection};
+    use crate::{self as sunscreen, Compiler};
+
+    use super::*;
+
+    type BpField = NativeField<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn lookup_compute_inputs_is_one_hot() {
+        let table = [5u32, 7, 11].map(BigInt::from);
+        let gadget = TableLookup::new(&table);
+
+        assert_eq!(
+            gadget.compute_inputs(&[BigInt::from(1u32)]).unwrap(),
+            vec![BigInt::ZERO, BigInt::ONE, BigInt::ZERO]
+        );
+
+        assert!(gadget.compute_inputs(&[BigInt::from(3u32)]).is_err());
+    }
+
+    #[test]
+    fn select_and_lookup_work() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn select_lookup<F: BackendField>(
+            c: NativeField<F>,
+            i: NativeField<F>,
+            expected: NativeField<F>,
+        ) {
+            let table = [3u8, 1, 4, 1, 5].map(NativeField::<F>::from);
+
+            let entry = i.lookup(&table);
+            let x = c.select(entry, NativeField::from(42u8).into_program_node());
+
+            x.constrain_eq(expected);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(select_lookup)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(select_lookup).unwrap();
+
+        let test_case = |c: u8, i: u8, expected: u8, expect_success: bool| {
+            let result = runtime.prove(
+                prog,
+                vec![],
+                vec![],
+                vec![BpField::from(c), BpField::from(i), BpField::from(expected)],
+            );
+
+            let proof = if expect_success {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(prog, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(1, 2, 4, true);
+        test_case(1, 4, 5, true);
+        test_case(0, 4, 42, true);
+
+        // Non-binary condition.
+        test_case(2, 4, 5, false);
+
+        // Out of bounds index.
+        test_case(1, 5, 0, false);
+    }
+}
@@ -0,0 +1,714 @@
+use std::marker::PhantomData;
+
+use petgraph::stable_graph::NodeIndex;
+use subtle::{Choice, ConditionallySelectable};
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_runtime::{Result as RuntimeResult, TryFromNativeFields, ZkpProgramInputTrait};
+use sunscreen_zkp_backend::{BackendField, BigInt};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::{LessThan, Max, Min, ToUInt},
+        AddVar, ConstrainEqVarVar, IntoProgramNode, MulVar, NativeField, NegVar, NumFieldElements,
+        ProgramNode, SubVar, ToNativeFields, ZkpType,
+    },
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use crate as sunscreen;
+
+/**
+ * A ZKP type represented by a single native field element whose values
+ * fit in `WIDTH` bits.
+ *
+ * # Remarks
+ * Unsigned types hold values in `[0, 2^WIDTH)`. Signed types hold values in
+ * `[-2^(WIDTH-1), 2^(WIDTH-1))`, where negative values `-x` are encoded
+ * as `p - x` for field modulus `p`.
+ */
+pub trait FixedWidth<F: BackendField>: ZkpType {
+    /**
+     * The number of bits needed to represent values of this type.
+     */
+    const WIDTH: usize;
+
+    /**
+     * Whether values of this type may be negative.
+     */
+    const SIGNED: bool;
+
+    /**
+     * Reinterprets a native field element as this type, proving its value
+     * is in range.
+     */
+    fn from_native_field(x: ProgramNode<NativeField<F>>) -> ProgramNode<Self> {
+        assert_in_range::<F, Self>(x.ids[0]);
+
+        ProgramNode::new(x.ids)
+    }
+
+    /**
+     * Reinterprets this type as a native field element.
+     */
+    fn to_native_field(x: ProgramNode<Self>) -> ProgramNode<NativeField<F>> {
+        ProgramNode::new(x.ids)
+    }
+}
+
+/**
+ * Proves `x` lies in `T`'s range.
+ */
+pub(crate) fn assert_in_range<F: BackendField, T: FixedWidth<F>>(x: NodeIndex) {
+    assert_width_fits::<F, T>();
+
+    let x = if T::SIGNED {
+        with_zkp_ctx(|ctx| {
+            let offset = ctx.add_constant(&BigInt::from(BigInt::ONE.shl_vartime(T::WIDTH - 1)));
+
+            ctx.add_addition(x, offset)
+        })
+    } else {
+        x
+    };
+
+    invoke_gadget(ToUInt::new(T::WIDTH), &[x]);
+}
+
+/**
+ * Proves `x` lies in `T`'s range and returns it as a `T`.
+ */
+pub(crate) fn checked<F: BackendField, T: FixedWidth<F>>(x: NodeIndex) -> ProgramNode<T> {
+    assert_in_range::<F, T>(x);
+
+    ProgramNode::new(&[x])
+}
+
+/**
+ * Panics if `T`'s values don't fit in the backend field with a bit to
+ * spare. Otherwise, distinct values could share an encoding and range
+ * proofs would be meaningless. The spare bit lets comparisons range check
+ * `WIDTH + 1`-bit differences without wrapping around the modulus.
+ */
+pub(crate) fn assert_width_fits<F: BackendField, T: FixedWidth<F>>() {
+    assert!(
+        T::WIDTH + 1 < F::FIELD_MODULUS.bits_vartime(),
+        "{}-bit values don't fit in the backend field.",
+        T::WIDTH
+    );
+}
+
+/**
+ * Panics if multiplying 2 values of type `T` could wrap around the
+ * field modulus.
+ */
+pub(crate) fn assert_mul_fits<F: BackendField, T: FixedWidth<F>>() {
+    assert!(
+        2 * T::WIDTH < F::FIELD_MODULUS.bits_vartime(),
+        "Multiplying {}-bit values may overflow the backend field.",
+        T::WIDTH
+    );
+}
+
+/**
+ * Encodes `x` as a field element, mapping negative values `-a` to `p - a`.
+ */
+pub(crate) fn encode_i64<F: BackendField>(x: i64) -> BigInt {
+    let is_negative = Choice::from(((x >> 63) & 0x1) as u8);
+
+    // unsigned_abs handles i64::MIN, unlike NativeField::from.
+    let abs_val = BigInt::from(x.unsigned_abs());
+    let neg = BigInt::from(F::FIELD_MODULUS.wrapping_sub(&abs_val));
+
+    BigInt::conditional_select(&abs_val, &neg, is_negative)
+}
+
+/**
+ * Returns the single native field element in `fields` if it encodes a
+ * value of type `T`.
+ */
+fn decode_output<F: BackendField, T: FixedWidth<F>>(fields: &[BigInt]) -> RuntimeResult<BigInt> {
+    let val = match fields {
+        [val] => *val,
+        _ => {
+            return Err(sunscreen_runtime::Error::ZkpOutputMismatch {
+                expected: 1,
+                actual: fields.len(),
+            })
+        }
+    };
+
+    let fits = if T::SIGNED {
+        // Negative values -x are encoded as p - x, so p - val - 1 fits in
+        // WIDTH - 1 bits.
+        let neg = BigInt::from(
+            F::FIELD_MODULUS
+                .wrapping_sub(&val)
+                .wrapping_sub(&BigInt::ONE),
+        );
+
+        val.bits_vartime() < T::WIDTH || (val != BigInt::ZERO && neg.bits_vartime() < T::WIDTH)
+    } else {
+        val.bits_vartime() <= T::WIDTH
+    };
+
+    if val < F::FIELD_MODULUS && fits {
+        Ok(val)
+    } else {
+        Err(sunscreen_runtime::Error::zkp_type_error(&format!(
+            "Value doesn't fit in a {}-bit integer.",
+            T::WIDTH
+        )))
+    }
+}
+
+fn add_node<T: ZkpType>(lhs: ProgramNode<T>, rhs: ProgramNode<T>) -> NodeIndex {
+    with_zkp_ctx(|ctx| ctx.add_addition(lhs.ids[0], rhs.ids[0]))
+}
+
+fn sub_node<T: ZkpType>(lhs: ProgramNode<T>, rhs: ProgramNode<T>) -> NodeIndex {
+    with_zkp_ctx(|ctx| ctx.add_subtraction(lhs.ids[0], rhs.ids[0]))
+}
+
+fn mul_node<T: ZkpType>(lhs: ProgramNode<T>, rhs: ProgramNode<T>) -> NodeIndex {
+    with_zkp_ctx(|ctx| ctx.add_multiplication(lhs.ids[0], rhs.ids[0]))
+}
+
+fn constrain_eq_nodes<T: ZkpType>(lhs: ProgramNode<T>, rhs: ProgramNode<T>) -> ProgramNode<T> {
+    with_zkp_ctx(|ctx| {
+        let sub = ctx.add_subtraction(lhs.ids[0], rhs.ids[0]);
+
+        let constraint = ctx.add_constraint(sub, &BigInt::ZERO);
+
+        ProgramNode::new(&[constraint])
+    })
+}
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * An unsigned `BITS`-bit integer. Arithmetic on these values fails to
+ * prove if it overflows.
+ */
+pub struct ZkpUnsigned<F: BackendField, const BITS: usize> {
+    val: BigInt,
+    _phantom: PhantomData<F>,
+}
+
+// Can't #[derive()] due to PhantomData.
+impl<F: BackendField, const BITS: usize> Copy for ZkpUnsigned<F, BITS> {}
+
+impl<F: BackendField, const BITS: usize> From<u64> for ZkpUnsigned<F, BITS> {
+    fn from(x: u64) -> Self {
+        assert!(BITS > 0, "ZkpUnsigned must have at least 1 bit.");
+        assert_width_fits::<F, Self>();
+        assert!(
+            BITS >= 64 || x >> BITS == 0,
+            "{x} doesn't fit in a {BITS}-bit unsigned integer."
+        );
+
+        Self {
+            val: BigInt::from(x),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<u32> for ZkpUnsigned<F, BITS> {
+    fn from(x: u32) -> Self {
+        u64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<u16> for ZkpUnsigned<F, BITS> {
+    fn from(x: u16) -> Self {
+        u64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<u8> for ZkpUnsigned<F, BITS> {
+    fn from(x: u8) -> Self {
+        u64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<ZkpUnsigned<F, BITS>> for NativeField<F> {
+    fn from(x: ZkpUnsigned<F, BITS>) -> Self {
+        x.val.into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> NumFieldElements for ZkpUnsigned<F, BITS> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: BackendField, const BITS: usize> ToNativeFields for ZkpUnsigned<F, BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        vec![self.val]
+    }
+}
+
+impl<F: BackendField, const BITS: usize> TryFromNativeFields for ZkpUnsigned<F, BITS> {
+    fn num_native_fields() -> usize {
+        Self::NUM_NATIVE_FIELD_ELEMENTS
+    }
+
+    fn try_from_native_fields(fields: &[BigInt]) -> RuntimeResult<Self> {
+        Ok(Self {
+            val: decode_output::<F, Self>(fields)?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ZkpType for ZkpUnsigned<F, BITS> {
+    fn constrain_input(input: &ProgramNode<Self>) {
+        assert_in_range::<F, Self>(input.ids[0]);
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ZkpProgramInputTrait for ZkpUnsigned<F, BITS> {}
+
+impl<F: BackendField, const BITS: usize> FixedWidth<F> for ZkpUnsigned<F, BITS> {
+    const WIDTH: usize = BITS;
+    const SIGNED: bool = false;
+}
+
+impl<F: BackendField, const BITS: usize> AddVar for ZkpUnsigned<F, BITS> {
+    fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(add_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> SubVar for ZkpUnsigned<F, BITS> {
+    fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(sub_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> MulVar for ZkpUnsigned<F, BITS> {
+    fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        assert_mul_fits::<F, Self>();
+
+        checked::<F, Self>(mul_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ConstrainEqVarVar for ZkpUnsigned<F, BITS> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        constrain_eq_nodes(lhs, rhs)
+    }
+}
+
+impl<F: BackendField, const BITS: usize> IntoProgramNode for ZkpUnsigned<F, BITS> {
+    type Output = Self;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val)]))
+    }
+}
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed `BITS`-bit two's complement integer. Arithmetic on these values
+ * fails to prove if it overflows.
+ */
+pub struct ZkpSigned<F: BackendField, const BITS: usize> {
+    val: BigInt,
+    _phantom: PhantomData<F>,
+}
+
+// Can't #[derive()] due to PhantomData.
+impl<F: BackendField, const BITS: usize> Copy for ZkpSigned<F, BITS> {}
+
+impl<F: BackendField, const BITS: usize> From<i64> for ZkpSigned<F, BITS> {
+    fn from(x: i64) -> Self {
+        assert!(BITS > 0, "ZkpSigned must have at least 1 bit.");
+        assert_width_fits::<F, Self>();
+        assert!(
+            BITS >= 64 || (x >> (BITS - 1) == 0 || x >> (BITS - 1) == -1),
+            "{x} doesn't fit in a {BITS}-bit signed integer."
+        );
+
+        Self {
+            val: encode_i64::<F>(x),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<i32> for ZkpSigned<F, BITS> {
+    fn from(x: i32) -> Self {
+        i64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<i16> for ZkpSigned<F, BITS> {
+    fn from(x: i16) -> Self {
+        i64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<i8> for ZkpSigned<F, BITS> {
+    fn from(x: i8) -> Self {
+        i64::from(x).into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> From<ZkpSigned<F, BITS>> for NativeField<F> {
+    fn from(x: ZkpSigned<F, BITS>) -> Self {
+        x.val.into()
+    }
+}
+
+impl<F: BackendField, const BITS: usize> NumFieldElements for ZkpSigned<F, BITS> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: BackendField, const BITS: usize> ToNativeFields for ZkpSigned<F, BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        vec![self.val]
+    }
+}
+
+impl<F: BackendField, const BITS: usize> TryFromNativeFields for ZkpSigned<F, BITS> {
+    fn num_native_fields() -> usize {
+        Self::NUM_NATIVE_FIELD_ELEMENTS
+    }
+
+    fn try_from_native_fields(fields: &[BigInt]) -> RuntimeResult<Self> {
+        Ok(Self {
+            val: decode_output::<F, Self>(fields)?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ZkpType for ZkpSigned<F, BITS> {
+    fn constrain_input(input: &ProgramNode<Self>) {
+        assert_in_range::<F, Self>(input.ids[0]);
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ZkpProgramInputTrait for ZkpSigned<F, BITS> {}
+
+impl<F: BackendField, const BITS: usize> FixedWidth<F> for ZkpSigned<F, BITS> {
+    const WIDTH: usize = BITS;
+    const SIGNED: bool = true;
+}
+
+impl<F: BackendField, const BITS: usize> AddVar for ZkpSigned<F, BITS> {
+    fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(add_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> SubVar for ZkpSigned<F, BITS> {
+    fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        checked::<F, Self>(sub_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> MulVar for ZkpSigned<F, BITS> {
+    fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        assert_mul_fits::<F, Self>();
+
+        checked::<F, Self>(mul_node(lhs, rhs))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> NegVar for ZkpSigned<F, BITS> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // -(-2^(BITS-1)) overflows, so we must check the result.
+        checked::<F, Self>(with_zkp_ctx(|ctx| ctx.add_negate(lhs.ids[0])))
+    }
+}
+
+impl<F: BackendField, const BITS: usize> ConstrainEqVarVar for ZkpSigned<F, BITS> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        constrain_eq_nodes(lhs, rhs)
+    }
+}
+
+impl<F: BackendField, const BITS: usize> IntoProgramNode for ZkpSigned<F, BITS> {
+    type Output = Self;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&self.val)]))
+    }
+}
+
+/**
+ * Comparisons between [`FixedWidth`] values. Unlike
+ * [`ConstrainCmp`](crate::types::zkp::ConstrainCmp), these know the
+ * width of their operands and don't need a bound.
+ */
+pub trait FixedWidthCmp<F: BackendField>
+where
+    Self: Sized,
+{
+    /**
+     * Returns 1 if this value is strictly less than `rhs` and 0
+     * otherwise.
+     */
+    fn is_lt(&self, rhs: Self) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Returns 1 if this value is greater than or equal to `rhs` and 0
+     * otherwise.
+     */
+    fn is_ge(&self, rhs: Self) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Asserts this value is less than or equal to `rhs`.
+     */
+    fn constrain_le(&self, rhs: Self);
+
+    /**
+     * Asserts this value is strictly less than `rhs`.
+     */
+    fn constrain_lt(&self, rhs: Self);
+
+    /**
+     * Returns the lesser of this value and `rhs`.
+     */
+    fn min(&self, rhs: Self) -> Self;
+
+    /**
+     * Returns the greater of this value and `rhs`.
+     */
+    fn max(&self, rhs: Self) -> Self;
+}
+
+impl<F, T> FixedWidthCmp<F> for ProgramNode<T>
+where
+    F: BackendField,
+    T: FixedWidth<F>,
+{
+    fn is_lt(&self, rhs: Self) -> ProgramNode<NativeField<F>> {
+        // Both operands lie in a range of width 2^WIDTH, so their
+        // difference lies in (-2^WIDTH, 2^WIDTH) as LessThan requires.
+        let outputs = invoke_gadget(LessThan::new(T::WIDTH), &[self.ids[0], rhs.ids[0]]);
+
+        ProgramNode::new(&outputs)
+    }
+
+    fn is_ge(&self, rhs: Self) -> ProgramNode<NativeField<F>> {
+        let lt = self.is_lt(rhs);
+
+        NativeField::from(1u8).into_program_node() - lt
+    }
+
+    fn constrain_le(&self, rhs: Self) {
+        let diff = with_zkp_ctx(|ctx| ctx.add_subtraction(rhs.ids[0], self.ids[0]));
+
+        invoke_gadget(ToUInt::new(T::WIDTH), &[diff]);
+    }
+
+    fn constrain_lt(&self, rhs: Self) {
+        let diff = with_zkp_ctx(|ctx| {
+            let one = ctx.add_constant(&BigInt::ONE);
+            let diff = ctx.add_subtraction(rhs.ids[0], self.ids[0]);
+
+            ctx.add_subtraction(diff, one)
+        });
+
+        invoke_gadget(ToUInt::new(T::WIDTH), &[diff]);
+    }
+
+    fn min(&self, rhs: Self) -> Self {
+        let outputs = invoke_gadget(Min::new(T::WIDTH), &[self.ids[0], rhs.ids[0]]);
+
+        ProgramNode::new(&outputs)
+    }
+
+    fn max(&self, rhs: Self) -> Self {
+        let outputs = invoke_gadget(Max::new(T::WIDTH), &[self.ids[0], rhs.ids[0]]);
+
+        ProgramNode::new(&outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::scalar::Scalar;
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::Compiler;
+
+    use super::*;
+
+    type BpField = <BulletproofsBackend as ZkpBackend>::Field;
+
+    #[test]
+    fn can_encode_signed_values() {
+        assert_eq!(ZkpSigned::<Scalar, 8>::from(5i8).val, BigInt::from(5u32));
+        assert_eq!(
+            ZkpSigned::<Scalar, 64>::from(i64::MIN).val,
+            BigInt::from(Scalar::FIELD_MODULUS.wrapping_sub(&BigInt::from(1u64 << 63)))
+        );
+    }
+
+    #[test]
+    fn decodes_outputs_in_range() {
+        let decode_u8 = |x: BigInt| ZkpUnsigned::<Scalar, 8>::try_from_native_fields(&[x]);
+        let decode_i8 = |x: BigInt| ZkpSigned::<Scalar, 8>::try_from_native_fields(&[x]);
+
+        assert!(decode_u8(BigInt::from(255u16)).is_ok());
+        assert!(decode_u8(BigInt::from(256u16)).is_err());
+
+        for x in [-128i64, -1, 0, 127] {
+            assert!(decode_i8(encode_i64::<Scalar>(x)).is_ok());
+        }
+
+        for x in [-129i64, 128] {
+            assert!(decode_i8(encode_i64::<Scalar>(x)).is_err());
+        }
+
+        assert!(decode_i8(Scalar::FIELD_MODULUS).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn unsigned_rejects_out_of_range_values() {
+        let _ = ZkpUnsigned::<Scalar, 8>::from(256u16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn signed_rejects_out_of_range_values() {
+        let _ = ZkpSigned::<Scalar, 8>::from(-129i16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_widths_exceeding_field() {
+        let _ = ZkpUnsigned::<Scalar, 256>::from(1u8);
+    }
+
+    #[test]
+    fn accepts_widest_width_fitting_field() {
+        // Scalar's modulus has 253 bits, leaving room for 251-bit values
+        // and their 252-bit differences.
+        assert_eq!(Scalar::FIELD_MODULUS.bits_vartime(), 253);
+
+        let _ = ZkpUnsigned::<Scalar, 251>::from(1u8);
+        let _ = ZkpSigned::<Scalar, 251>::from(-1i8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_width_without_spare_bit() {
+        let _ = ZkpUnsigned::<Scalar, 252>::from(1u8);
+    }
+
+    #[test]
+    fn integer_arithmetic_checks_overflow() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn signed<F: BackendField>(
+            a: ZkpSigned<F, 8>,
+            b: ZkpSigned<F, 8>,
+            #[public] sum: ZkpSigned<F, 8>,
+            #[public] prod: ZkpSigned<F, 8>,
+        ) {
+            (a + b).constrain_eq(sum);
+            (a * b).constrain_eq(prod);
+            a.min(b).constrain_le(a.max(b));
+        }
+
+        #[zkp_program(backend = "bulletproofs")]
+        fn unsigned<F: BackendField>(a: ZkpUnsigned<F, 8>, b: ZkpUnsigned<F, 8>) {
+            let _ = a - b;
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(signed)
+            .zkp_program(unsigned)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(signed).unwrap();
+
+        let test_case = |a: i8, b: i8, expect_success: bool| {
+            let (a, b) = (i64::from(a), i64::from(b));
+
+            // Claim the wrapped results so only the overflow checks fail.
+            let sum = i64::from((a as i8).wrapping_add(b as i8));
+            let prod = i64::from((a as i8).wrapping_mul(b as i8));
+
+            let public = vec![
+                ZkpSigned::<BpField, 8>::from(sum),
+                ZkpSigned::<BpField, 8>::from(prod),
+            ];
+
+            let result = runtime.prove(
+                prog,
+                vec![],
+                public.clone(),
+                vec![
+                    ZkpSigned::<BpField, 8>::from(a),
+                    ZkpSigned::<BpField, 8>::from(b),
+                ],
+            );
+
+            let proof = if expect_success {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime.verify(prog, &proof, vec![], public).unwrap();
+        };
+
+        test_case(-7, 9, true);
+        test_case(-12, -10, true);
+        test_case(-128, 1, true);
+
+        // Sum overflows.
+        test_case(127, 1, false);
+
+        // Product overflows.
+        test_case(-12, 11, false);
+
+        let prog = app.get_zkp_program(unsigned).unwrap();
+
+        let test_case = |a: u8, b: u8, expect_success: bool| {
+            let result = runtime.prove(
+                prog,
+                vec![],
+                vec![],
+                vec![
+                    ZkpUnsigned::<BpField, 8>::from(a),
+                    ZkpUnsigned::<BpField, 8>::from(b),
+                ],
+            );
+
+            let proof = if expect_success {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(prog, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(200, 3, true);
+
+        // Underflow.
+        test_case(3, 200, false);
+    }
+}
@@ -1,10 +1,16 @@
+mod fixed;
 mod gadgets;
+mod integer;
 mod native_field;
+mod poseidon;
 mod program_node;
 mod rns_polynomial;
 
+pub use fixed::*;
+pub use integer::*;
 pub use native_field::*;
 use petgraph::stable_graph::NodeIndex;
+pub use poseidon::*;
 pub use program_node::*;
 pub use rns_polynomial::*;
 use sunscreen_compiler_common::TypeName;
@@ -190,7 +196,14 @@ pub trait NumFieldElements {
  * Encapsulates all the traits required for a type to be used in ZKP
  * programs.
  */
-pub trait ZkpType: NumFieldElements + Sized + TypeName + ToNativeFields {}
+pub trait ZkpType: NumFieldElements + Sized + TypeName + ToNativeFields {
+    /**
+     * Adds the constraints a program input of this type must satisfy
+     * (e.g. range checks). Types for which any native field elements are
+     * valid needn't override this.
+     */
+    fn constrain_input(_input: &ProgramNode<Self>) {}
+}
 
 /**
  * Methods for coercing ZKP data types.
@@ -214,7 +227,16 @@ where
     const NUM_NATIVE_FIELD_ELEMENTS: usize = T::NUM_NATIVE_FIELD_ELEMENTS * N;
 }
 
-impl<T, const N: usize> ZkpType for [T; N] where T: ZkpType {}
+impl<T, const N: usize> ZkpType for [T; N]
+where
+    T: ZkpType,
+{
+    fn constrain_input(input: &ProgramNode<Self>) {
+        for ids in input.ids.chunks(T::NUM_NATIVE_FIELD_ELEMENTS) {
+            T::constrain_input(&ProgramNode::new(ids));
+        }
+    }
+}
 
 impl<T> Coerce for T
 where
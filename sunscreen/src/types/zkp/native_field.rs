@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use subtle::{Choice, ConditionallySelectable};
 use sunscreen_compiler_macros::TypeName;
-use sunscreen_runtime::ZkpProgramInputTrait;
+use sunscreen_runtime::{Result as RuntimeResult, TryFromNativeFields, ZkpProgramInputTrait};
 use sunscreen_zkp_backend::{BackendField, BigInt};
 
 use crate::{
@@ -12,8 +12,8 @@ use crate::{
 };
 
 use crate::types::zkp::{
-    gadgets::ToUInt, ConstrainEqVarVar, IntoProgramNode, MulVar, NegVar, NumFieldElements,
-    ToNativeFields, ZkpType,
+    gadgets::{AssertInRange, IsZero, LessThan, Max, Min, Select, TableLookup, ToUInt},
+    ConstrainEqVarVar, IntoProgramNode, MulVar, NegVar, NumFieldElements, ToNativeFields, ZkpType,
 };
 
 use crate as sunscreen;
@@ -148,6 +148,25 @@ impl<F: BackendField> ToNativeFields for NativeField<F> {
     }
 }
 
+impl<F: BackendField> TryFromNativeFields for NativeField<F> {
+    fn num_native_fields() -> usize {
+        Self::NUM_NATIVE_FIELD_ELEMENTS
+    }
+
+    fn try_from_native_fields(fields: &[BigInt]) -> RuntimeResult<Self> {
+        match fields {
+            [val] if *val < F::FIELD_MODULUS => Ok(Self::from(*val)),
+            [_] => Err(sunscreen_runtime::Error::zkp_type_error(
+                "Value exceeds the field modulus.",
+            )),
+            _ => Err(sunscreen_runtime::Error::ZkpOutputMismatch {
+                expected: 1,
+                actual: fields.len(),
+            }),
+        }
+    }
+}
+
 impl<F: BackendField> ZkpType for NativeField<F> {}
 impl<F: BackendField> ZkpProgramInputTrait for NativeField<F> {}
 
@@ -317,6 +336,165 @@ impl<F: BackendField> ToBinary<F> for ProgramNode<NativeField<F>> {
     }
 }
 
+/**
+ * Comparisons that produce boolean (i.e. 0 or 1) values rather than
+ * constraints.
+ *
+ * # Remarks
+ * Methods ending in `_bounded` take the number of bits needed to represent
+ * their operands as unsigned values. Fewer bits produce smaller circuits.
+ * This value must be less than the number of bits needed to represent the
+ * field modulus. The `_bounded` comparisons don't check their operands fit
+ * in the given bits; callers should establish this, e.g. with
+ * [`ToBinary::to_unsigned`] or [`Compare::assert_in_range_bounded`].
+ */
+pub trait Compare<F: BackendField> {
+    /**
+     * Returns 1 if this value is zero and 0 otherwise.
+     */
+    fn is_zero(&self) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Returns 1 if this value is strictly less than `rhs` and 0
+     * otherwise.
+     */
+    fn is_lt_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Returns the lesser of this value and `rhs`.
+     */
+    fn min_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Returns the greater of this value and `rhs`.
+     */
+    fn max_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Asserts `lo <= self <= hi`.
+     *
+     * # Remarks
+     * `bits` is the number of bits needed to represent `hi - lo` as an
+     * unsigned value.
+     */
+    fn assert_in_range_bounded(
+        &self,
+        lo: ProgramNode<NativeField<F>>,
+        hi: ProgramNode<NativeField<F>>,
+        bits: usize,
+    );
+}
+
+impl<F: BackendField> Compare<F> for ProgramNode<NativeField<F>> {
+    fn is_zero(&self) -> ProgramNode<NativeField<F>> {
+        let outputs = invoke_gadget(IsZero::new(F::FIELD_MODULUS), self.ids);
+
+        Self::new(&outputs)
+    }
+
+    fn is_lt_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>> {
+        let outputs = invoke_gadget(LessThan::new(bits), &[self.ids[0], rhs.ids[0]]);
+
+        Self::new(&outputs)
+    }
+
+    fn min_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>> {
+        let outputs = invoke_gadget(Min::new(bits), &[self.ids[0], rhs.ids[0]]);
+
+        Self::new(&outputs)
+    }
+
+    fn max_bounded(
+        &self,
+        rhs: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) -> ProgramNode<NativeField<F>> {
+        let outputs = invoke_gadget(Max::new(bits), &[self.ids[0], rhs.ids[0]]);
+
+        Self::new(&outputs)
+    }
+
+    fn assert_in_range_bounded(
+        &self,
+        lo: ProgramNode<NativeField<F>>,
+        hi: ProgramNode<NativeField<F>>,
+        bits: usize,
+    ) {
+        invoke_gadget(
+            AssertInRange::new(bits),
+            &[self.ids[0], lo.ids[0], hi.ids[0]],
+        );
+    }
+}
+
+/**
+ * Methods for choosing between values.
+ */
+pub trait Selection<F: BackendField> {
+    /**
+     * Interpreting this value as a condition, returns `a` if it is 1 and
+     * `b` if it is 0. The proof fails if the condition is neither.
+     */
+    fn select(
+        &self,
+        a: ProgramNode<NativeField<F>>,
+        b: ProgramNode<NativeField<F>>,
+    ) -> ProgramNode<NativeField<F>>;
+
+    /**
+     * Interpreting this value as an index, returns `table[self]`. The
+     * proof fails if the index is out of bounds.
+     *
+     * # Remarks
+     * The circuit size grows linearly with the table's length, so this
+     * suits only small tables.
+     *
+     * # Panics
+     * If `table` is empty.
+     */
+    fn lookup(&self, table: &[NativeField<F>]) -> ProgramNode<NativeField<F>>;
+}
+
+impl<F: BackendField> Selection<F> for ProgramNode<NativeField<F>> {
+    fn select(
+        &self,
+        a: ProgramNode<NativeField<F>>,
+        b: ProgramNode<NativeField<F>>,
+    ) -> ProgramNode<NativeField<F>> {
+        let outputs = invoke_gadget(Select, &[self.ids[0], a.ids[0], b.ids[0]]);
+
+        Self::new(&outputs)
+    }
+
+    fn lookup(&self, table: &[NativeField<F>]) -> ProgramNode<NativeField<F>> {
+        let table = table.iter().map(|x| x.val).collect::<Vec<BigInt>>();
+
+        let outputs = invoke_gadget(TableLookup::new(&table), self.ids);
+
+        Self::new(&outputs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Mul, Neg, Sub};
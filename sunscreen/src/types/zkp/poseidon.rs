@@ -0,0 +1,634 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crypto_bigint::U512;
+use sunscreen_zkp_backend::{BackendField, BigInt};
+
+use crate::invoke_gadget;
+use crate::types::zkp::{
+    gadgets::{PoseidonPermutation, Select},
+    ConstrainEq, IntoProgramNode, NativeField, ProgramNode,
+};
+
+/**
+ * The number of field elements in a Poseidon state.
+ */
+pub const POSEIDON_WIDTH: usize = 3;
+
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 57;
+const ALPHA: u32 = 5;
+
+/**
+ * The constants defining the Poseidon permutation over a given field.
+ *
+ * # Remarks
+ * We use a width 3 state, the `x^5` S-box, 8 full rounds and 57 partial
+ * rounds, which gives 128-bit security over ~255-bit fields. The round
+ * constants and the Cauchy MDS matrix `M[i][j] = 1 / (x_i + y_j)` come from
+ * the Grain LFSR, exactly as in the Poseidon reference implementation's
+ * parameter generation script. Both derive deterministically from the field
+ * modulus, so provers and verifiers agree on them without distributing them,
+ * and over BN254's scalar field they match the reference `x5_254_3`
+ * instance.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonParams {
+    field_modulus: BigInt,
+    round_constants: Vec<BigInt>,
+    mds: [[BigInt; POSEIDON_WIDTH]; POSEIDON_WIDTH],
+}
+
+impl PoseidonParams {
+    /**
+     * Derives the Poseidon constants for the given field.
+     *
+     * # Panics
+     * * If `x^5` isn't a permutation of the field, i.e. if
+     *   `gcd(5, p - 1) != 1`.
+     */
+    pub fn new(field_modulus: BigInt) -> Self {
+        let p_min_1 = field_modulus.wrapping_sub(&U512::ONE);
+
+        assert_ne!(
+            p_min_1.reduce(&U512::from(ALPHA as u64)).unwrap(),
+            U512::ZERO,
+            "x^5 is not a permutation of this field, so Poseidon parameters don't exist."
+        );
+
+        let field_bits = field_modulus.bits_vartime();
+
+        let mut lfsr = GrainLfsr::new(field_bits, POSEIDON_WIDTH, FULL_ROUNDS, PARTIAL_ROUNDS);
+
+        let round_constants = (0..(FULL_ROUNDS + PARTIAL_ROUNDS) * POSEIDON_WIDTH)
+            .map(|_| lfsr.next_field_element(&field_modulus, field_bits))
+            .collect();
+
+        let mds = Self::create_mds(&mut lfsr, &field_modulus, field_bits);
+
+        Self {
+            field_modulus,
+            round_constants,
+            mds,
+        }
+    }
+
+    /**
+     * Draws `x` and `y` from the LFSR until they yield a valid Cauchy
+     * matrix `M[i][j] = 1 / (x_i + y_j)`. Unlike round constants, these
+     * values are reduced modulo the field rather than rejected.
+     */
+    fn create_mds(
+        lfsr: &mut GrainLfsr,
+        field_modulus: &BigInt,
+        field_bits: usize,
+    ) -> [[BigInt; POSEIDON_WIDTH]; POSEIDON_WIDTH] {
+        let reduce = |x: U512| BigInt::from(x.reduce(field_modulus).unwrap());
+
+        loop {
+            let vals = (0..2 * POSEIDON_WIDTH)
+                .map(|_| reduce(lfsr.next_bits(field_bits)))
+                .collect::<Vec<_>>();
+
+            let has_duplicates = vals
+                .iter()
+                .enumerate()
+                .any(|(i, a)| vals[i + 1..].contains(a));
+
+            if has_duplicates {
+                continue;
+            }
+
+            let (xs, ys) = vals.split_at(POSEIDON_WIDTH);
+
+            let sums = xs
+                .iter()
+                .flat_map(|x| ys.iter().map(|y| reduce(x.wrapping_add(y))))
+                .collect::<Vec<_>>();
+
+            if sums.contains(&BigInt::ZERO) {
+                continue;
+            }
+
+            let mut mds = [[BigInt::ZERO; POSEIDON_WIDTH]; POSEIDON_WIDTH];
+
+            for (m, sum) in mds.iter_mut().flatten().zip(sums) {
+                *m = sum.inverse_fp(field_modulus);
+            }
+
+            return mds;
+        }
+    }
+
+    /**
+     * The total number of rounds.
+     */
+    pub fn rounds(&self) -> usize {
+        FULL_ROUNDS + PARTIAL_ROUNDS
+    }
+
+    /**
+     * Whether the given round applies the S-box to the whole state rather
+     * than only the first element.
+     */
+    pub fn is_full_round(&self, round: usize) -> bool {
+        !(FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round)
+    }
+
+    /**
+     * The constants added to the state at the start of the given round.
+     */
+    pub fn round_constants(&self, round: usize) -> &[BigInt] {
+        &self.round_constants[round * POSEIDON_WIDTH..][..POSEIDON_WIDTH]
+    }
+
+    /**
+     * The MDS matrix that mixes the state at the end of each round.
+     */
+    pub fn mds(&self) -> &[[BigInt; POSEIDON_WIDTH]; POSEIDON_WIDTH] {
+        &self.mds
+    }
+
+    /**
+     * Applies the Poseidon permutation to the given state outside of a ZKP
+     * program.
+     */
+    pub fn permute(&self, state: [BigInt; POSEIDON_WIDTH]) -> [BigInt; POSEIDON_WIDTH] {
+        (0..self.rounds()).fold(state, |state, round| self.apply_round(round, state))
+    }
+
+    /**
+     * Applies a single round of the Poseidon permutation to the given state
+     * outside of a ZKP program.
+     */
+    pub fn apply_round(
+        &self,
+        round: usize,
+        state: [BigInt; POSEIDON_WIDTH],
+    ) -> [BigInt; POSEIDON_WIDTH] {
+        let p = &self.field_modulus;
+
+        let add = |a: &BigInt, b: &BigInt| BigInt::from(a.wrapping_add(b).reduce(p).unwrap());
+        let mul = |a: &BigInt, b: &BigInt| BigInt::from(a.wrapping_mul(b).reduce(p).unwrap());
+        let sbox = |x: &BigInt| {
+            let x2 = mul(x, x);
+            let x4 = mul(&x2, &x2);
+
+            mul(&x4, x)
+        };
+
+        let mut state = state;
+
+        for (s, c) in state.iter_mut().zip(self.round_constants(round)) {
+            *s = add(s, c);
+        }
+
+        if self.is_full_round(round) {
+            for s in state.iter_mut() {
+                *s = sbox(s);
+            }
+        } else {
+            state[0] = sbox(&state[0]);
+        }
+
+        let mut mixed = [BigInt::ZERO; POSEIDON_WIDTH];
+
+        for (m, row) in mixed.iter_mut().zip(self.mds.iter()) {
+            for (s, coeff) in state.iter().zip(row.iter()) {
+                *m = add(m, &mul(s, coeff));
+            }
+        }
+
+        mixed
+    }
+}
+
+/**
+ * The Grain LFSR the Poseidon paper uses to generate round constants.
+ */
+struct GrainLfsr {
+    state: [bool; 80],
+}
+
+impl GrainLfsr {
+    fn new(field_bits: usize, width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut bits = vec![];
+
+        let mut push = |val: usize, count: usize| {
+            for i in (0..count).rev() {
+                bits.push((val >> i) & 0x1 == 1);
+            }
+        };
+
+        // Prime field, x^alpha S-box.
+        push(1, 2);
+        push(0, 4);
+        push(field_bits, 12);
+        push(width, 12);
+        push(full_rounds, 10);
+        push(partial_rounds, 10);
+        push((1 << 30) - 1, 30);
+
+        let mut state = [false; 80];
+        state.copy_from_slice(&bits);
+
+        let mut lfsr = Self { state };
+
+        for _ in 0..160 {
+            lfsr.next_bit();
+        }
+
+        lfsr
+    }
+
+    fn next_bit(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+
+        bit
+    }
+
+    /**
+     * Output bits are drawn in pairs, keeping the second bit only when the
+     * first is set.
+     */
+    fn next_output_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_bit();
+            let bit = self.next_bit();
+
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /**
+     * Draws a big-endian `bits`-bit value.
+     */
+    fn next_bits(&mut self, bits: usize) -> U512 {
+        let mut val = U512::ZERO;
+
+        for _ in 0..bits {
+            val = val.shl_vartime(1);
+
+            if self.next_output_bit() {
+                val = val.wrapping_add(&U512::ONE);
+            }
+        }
+
+        val
+    }
+
+    /**
+     * Draws big-endian `field_bits`-bit values, rejecting any that aren't
+     * less than the field modulus.
+     */
+    fn next_field_element(&mut self, field_modulus: &BigInt, field_bits: usize) -> BigInt {
+        loop {
+            let val = self.next_bits(field_bits);
+
+            if val < **field_modulus {
+                return BigInt::from(val);
+            }
+        }
+    }
+}
+
+/**
+ * The Poseidon hash over the native field `F`, usable both inside and
+ * outside ZKP programs.
+ *
+ * # Remarks
+ * Outside a ZKP program, use [`Poseidon::hash`] and [`Poseidon::merkle_root`]
+ * to compute the values a program will check. Inside a ZKP program, use
+ * [`Poseidon::hash_var`] and [`Poseidon::verify_merkle_path`].
+ *
+ * Hashes take the `[0, a, b]` state through the permutation and return its
+ * first element.
+ */
+pub struct Poseidon<F: BackendField> {
+    params: Arc<PoseidonParams>,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: BackendField> Default for Poseidon<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: BackendField> Poseidon<F> {
+    /**
+     * Creates the Poseidon hash for field `F`.
+     *
+     * # Panics
+     * See [`PoseidonParams::new`].
+     */
+    pub fn new() -> Self {
+        Self {
+            params: Arc::new(PoseidonParams::new(F::FIELD_MODULUS)),
+            _phantom: PhantomData,
+        }
+    }
+
+    /**
+     * The underlying permutation's parameters.
+     */
+    pub fn params(&self) -> &PoseidonParams {
+        &self.params
+    }
+
+    /**
+     * Hashes 2 field elements into 1 outside of a ZKP program.
+     */
+    pub fn hash(&self, a: NativeField<F>, b: NativeField<F>) -> NativeField<F> {
+        let out = self.params.permute([BigInt::ZERO, a.val, b.val]);
+
+        NativeField::from(out[0])
+    }
+
+    /**
+     * Computes the root of a Merkle tree outside of a ZKP program from a
+     * leaf and its authentication path. `siblings[i]` is the sibling node
+     * at height `i` and `is_right[i]` indicates whether the current node
+     * is its parent's right child.
+     *
+     * # Panics
+     * If `siblings` and `is_right` have different lengths.
+     */
+    pub fn merkle_root(
+        &self,
+        leaf: NativeField<F>,
+        siblings: &[NativeField<F>],
+        is_right: &[bool],
+    ) -> NativeField<F> {
+        assert_eq!(siblings.len(), is_right.len());
+
+        siblings
+            .iter()
+            .zip(is_right)
+            .fold(leaf, |cur, (sibling, is_right)| {
+                if *is_right {
+                    self.hash(*sibling, cur)
+                } else {
+                    self.hash(cur, *sibling)
+                }
+            })
+    }
+
+    /**
+     * Applies the Poseidon permutation to the given state inside a ZKP
+     * program.
+     */
+    pub fn permute_var(
+        &self,
+        state: [ProgramNode<NativeField<F>>; POSEIDON_WIDTH],
+    ) -> [ProgramNode<NativeField<F>>; POSEIDON_WIDTH] {
+        let inputs = state.iter().map(|x| x.ids[0]).collect::<Vec<_>>();
+
+        let outputs = invoke_gadget(PoseidonPermutation::new(self.params.clone()), &inputs);
+
+        let mut state = state;
+
+        for (s, o) in state.iter_mut().zip(outputs) {
+            *s = ProgramNode::new(&[o]);
+        }
+
+        state
+    }
+
+    /**
+     * Hashes 2 field elements into 1 inside a ZKP program.
+     */
+    pub fn hash_var(
+        &self,
+        a: ProgramNode<NativeField<F>>,
+        b: ProgramNode<NativeField<F>>,
+    ) -> ProgramNode<NativeField<F>> {
+        let zero = NativeField::<F>::from(0u8).into_program_node();
+
+        self.permute_var([zero, a, b])[0]
+    }
+
+    /**
+     * Proves inside a ZKP program that `leaf` is in the Merkle tree with
+     * the given `root`. See [`Poseidon::merkle_root`] for the meaning of
+     * `siblings` and `is_right`. The proof fails if any `is_right` value
+     * isn't 0 or 1.
+     *
+     * # Panics
+     * If `siblings` and `is_right` have different lengths.
+     */
+    pub fn verify_merkle_path(
+        &self,
+        leaf: ProgramNode<NativeField<F>>,
+        siblings: &[ProgramNode<NativeField<F>>],
+        is_right: &[ProgramNode<NativeField<F>>],
+        root: ProgramNode<NativeField<F>>,
+    ) {
+        assert_eq!(siblings.len(), is_right.len());
+
+        let computed = siblings
+            .iter()
+            .zip(is_right)
+            .fold(leaf, |cur, (sibling, is_right)| {
+                let ids = [is_right.ids[0], sibling.ids[0], cur.ids[0]];
+                let left = invoke_gadget(Select, &ids);
+
+                let ids = [is_right.ids[0], cur.ids[0], sibling.ids[0]];
+                let right = invoke_gadget(Select, &ids);
+
+                self.hash_var(ProgramNode::new(&left), ProgramNode::new(&right))
+            });
+
+        computed.constrain_eq(root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::Runtime;
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use super::*;
+    use crate::{self as sunscreen, Compiler};
+
+    type BpField = NativeField<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn params_are_deterministic_and_permutation_mixes() {
+        let poseidon = Poseidon::<<BulletproofsBackend as ZkpBackend>::Field>::new();
+        let other = Poseidon::<<BulletproofsBackend as ZkpBackend>::Field>::new();
+
+        assert_eq!(poseidon.params(), other.params());
+
+        let a = poseidon.hash(BpField::from(1u8), BpField::from(2u8));
+        let b = poseidon.hash(BpField::from(2u8), BpField::from(1u8));
+
+        assert_ne!(a.val, b.val);
+        assert!(a.val < <BulletproofsBackend as ZkpBackend>::Field::FIELD_MODULUS);
+    }
+
+    /**
+     * Parses a big-endian hex string of any length.
+     */
+    fn hex(x: &str) -> BigInt {
+        BigInt::from_be_hex(&format!("{:0>128}", x))
+    }
+
+    #[test]
+    fn permutation_matches_reference_vectors() {
+        // The reference implementation's x5_254_3 instance, which uses our
+        // width, S-box and round counts over BN254's scalar field.
+        let params = PoseidonParams::new(hex(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        ));
+
+        assert_eq!(
+            params.round_constants(0)[0],
+            hex("0ee9a592ba9a9518d05986d656f40c2114c4993c11bb29938d21d47304cd8e6e")
+        );
+        assert_eq!(
+            params.mds()[0][0],
+            hex("109b7f411ba0e4c9b2b70caf5c36a7b194be7c11ad24378bfedb68592ba8118b")
+        );
+
+        let out = params.permute([0, 1, 2].map(BigInt::from_u32));
+
+        assert_eq!(
+            out,
+            [
+                hex("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+                hex("0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29"),
+                hex("0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn can_prove_merkle_membership() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn membership<F: BackendField>(
+            leaf: NativeField<F>,
+            siblings: [NativeField<F>; 3],
+            is_right: [NativeField<F>; 3],
+            #[public] root: NativeField<F>,
+        ) {
+            let poseidon = Poseidon::<F>::new();
+
+            poseidon.verify_merkle_path(leaf, &siblings, &is_right, root);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(membership)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let prog = app.get_zkp_program(membership).unwrap();
+
+        let poseidon = Poseidon::<<BulletproofsBackend as ZkpBackend>::Field>::new();
+
+        let leaf = BpField::from(1234u32);
+        let siblings = [5u8, 6, 7].map(BpField::from);
+        let is_right = [false, true, true];
+
+        let root = poseidon.merkle_root(leaf, &siblings, &is_right);
+
+        let private_inputs = |leaf: BpField| {
+            let mut inputs = vec![leaf];
+            inputs.extend(siblings);
+            inputs.extend(is_right.map(|x| BpField::from(x as u8)));
+
+            inputs
+        };
+
+        let proof = runtime
+            .prove(prog, vec![], vec![root], private_inputs(leaf))
+            .unwrap();
+
+        runtime.verify(prog, &proof, vec![], vec![root]).unwrap();
+
+        // A leaf not in the tree fails.
+        let result = runtime.prove(prog, vec![], vec![root], private_inputs(BpField::from(1u8)));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn gadgets_match_native_implementation() {
+        #[zkp_program(backend = "bulletproofs")]
+        fn hash<F: BackendField>(a: NativeField<F>, b: NativeField<F>) -> NativeField<F> {
+            Poseidon::<F>::new().hash_var(a, b)
+        }
+
+        #[zkp_program(backend = "bulletproofs")]
+        fn membership<F: BackendField>(
+            leaf: NativeField<F>,
+            siblings: [NativeField<F>; 2],
+            is_right: [NativeField<F>; 2],
+            #[public] root: NativeField<F>,
+        ) {
+            Poseidon::<F>::new().verify_merkle_path(leaf, &siblings, &is_right, root);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(hash)
+            .zkp_program(membership)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+        let poseidon = Poseidon::<<BulletproofsBackend as ZkpBackend>::Field>::new();
+
+        let hash_prog = app.get_zkp_program(hash).unwrap();
+
+        for (a, b) in [(0u32, 0u32), (1, 2), (2, 1), (123456, 7890)] {
+            let (a, b) = (BpField::from(a), BpField::from(b));
+
+            let proof = runtime
+                .prove(hash_prog, vec![], vec![], vec![a, b])
+                .unwrap();
+
+            let output: BpField = runtime
+                .verify(hash_prog, &proof, Vec::<BpField>::new(), vec![])
+                .unwrap()
+                .get()
+                .unwrap();
+
+            assert_eq!(output.val, poseidon.hash(a, b).val);
+        }
+
+        let membership_prog = app.get_zkp_program(membership).unwrap();
+
+        let leaf = BpField::from(42u8);
+        let siblings = [BpField::from(5u8), BpField::from(6u8)];
+
+        // The in-circuit path agrees with the native root for every
+        // arrangement of left and right children.
+        for is_right in [[false, false], [false, true], [true, false], [true, true]] {
+            let root = poseidon.merkle_root(leaf, &siblings, &is_right);
+
+            let mut inputs = vec![leaf];
+            inputs.extend(siblings);
+            inputs.extend(is_right.map(|x| BpField::from(x as u8)));
+
+            let proof = runtime
+                .prove(membership_prog, vec![], vec![root], inputs)
+                .unwrap();
+
+            runtime
+                .verify(membership_prog, &proof, vec![], vec![root])
+                .unwrap();
+        }
+    }
+}
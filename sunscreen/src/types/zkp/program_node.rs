@@ -57,6 +57,17 @@ pub trait CreateZkpProgramInput {
     fn constant_input() -> Self;
 }
 
+/**
+ * Trait for revealing values as public outputs of a ZKP program.
+ */
+pub trait Output {
+    /**
+     * Marks this value as a public output. The prover computes its value
+     * and the verifier learns it.
+     */
+    fn output(&self);
+}
+
 impl<T> ProgramNode<T>
 where
     T: ZkpType,
@@ -123,7 +134,10 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_public_input()));
         }
 
-        Self::new(&ids)
+        let input = Self::new(&ids);
+        T::constrain_input(&input);
+
+        input
     }
 
     fn private_input() -> Self {
@@ -133,7 +147,10 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_private_input()));
         }
 
-        Self::new(&ids)
+        let input = Self::new(&ids);
+        T::constrain_input(&input);
+
+        input
     }
 
     fn constant_input() -> Self {
@@ -143,7 +160,32 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_constant_input()));
         }
 
-        Self::new(&ids)
+        let input = Self::new(&ids);
+        T::constrain_input(&input);
+
+        input
+    }
+}
+
+impl<T, const N: usize> Output for [T; N]
+where
+    T: Output,
+{
+    fn output(&self) {
+        for x in self {
+            x.output();
+        }
+    }
+}
+
+impl<T> Output for ProgramNode<T>
+where
+    T: ZkpType,
+{
+    fn output(&self) {
+        for id in self.ids {
+            with_zkp_ctx(|ctx| ctx.add_output(*id));
+        }
     }
 }
 
@@ -52,6 +52,7 @@ pub enum Operation {
     Sub,
     Mul,
     Neg,
+    Output(usize),
 }
 
 impl Hash for Operation {
@@ -89,6 +90,10 @@ impl Hash for Operation {
                 state.write_u8(10);
                 x.hash(state);
             }
+            Self::Output(x) => {
+                state.write_u8(11);
+                x.hash(state);
+            }
         }
     }
 }
@@ -106,6 +111,7 @@ impl PartialEq for Operation {
             (Self::Sub, Self::Sub) => true,
             (Self::Mul, Self::Mul) => true,
             (Self::Neg, Self::Neg) => true,
+            (Self::Output(x), Self::Output(y)) => x == y,
             _ => false,
         }
     }
@@ -127,6 +133,7 @@ impl Debug for Operation {
             Self::Sub => write!(f, "Sub"),
             Self::Mul => write!(f, "Mul"),
             Self::Neg => write!(f, "Neg"),
+            Self::Output(x) => write!(f, "Output({x})"),
         }
     }
 }
@@ -141,7 +148,7 @@ impl OperationTrait for Operation {
     }
 
     fn is_unary(&self) -> bool {
-        matches!(self, Operation::Neg)
+        matches!(self, Operation::Neg | Operation::Output(_))
     }
 
     fn is_unordered(&self) -> bool {
@@ -181,16 +188,21 @@ impl Operation {
     pub fn is_hidden_input(&self) -> bool {
         matches!(self, Operation::HiddenInput(_))
     }
+
+    pub fn is_output(&self) -> bool {
+        matches!(self, Operation::Output(_))
+    }
 }
 
 /**
  * An implementation detail of a ZKP program. During compilation, it
- * tracks how many public and private inputs have been added.
+ * tracks how many public and private inputs and outputs have been added.
  */
 pub struct ZkpData {
     next_public_input: usize,
     next_private_input: usize,
     next_constant_input: usize,
+    next_output: usize,
     // A lookup table to reuse constant nodes. Reduces the size
     // of the graph.
     constant_map: HashMap<BigInt, NodeIndex>,
@@ -205,6 +217,7 @@ impl ZkpData {
             next_private_input: 0,
             next_public_input: 0,
             next_constant_input: 0,
+            next_output: 0,
             constant_map: HashMap::new(),
         }
     }
@@ -291,6 +304,11 @@ pub trait ZkpContextOps {
      * Add a gadget invocation to this context
      */
     fn add_invoke_gadget<G: Gadget>(&mut self, gadget: &Arc<G>) -> NodeIndex;
+
+    /**
+     * Add a public output to this context
+     */
+    fn add_output(&mut self, val: NodeIndex) -> NodeIndex;
 }
 
 impl ZkpContextOps for ZkpContext {
@@ -359,6 +377,13 @@ impl ZkpContextOps for ZkpContext {
     fn add_invoke_gadget<G: Gadget>(&mut self, gadget: &Arc<G>) -> NodeIndex {
         self.add_node(Operation::InvokeGadget(gadget.clone()))
     }
+
+    fn add_output(&mut self, val: NodeIndex) -> NodeIndex {
+        let node = self.add_unary_operation(Operation::Output(self.data.next_output), val);
+        self.data.next_output += 1;
+
+        node
+    }
 }
 
 impl Render for Operation {
@@ -412,6 +437,7 @@ pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
                 Operation::Sub => JitOperation::Sub,
                 Operation::Constraint(x) => JitOperation::Constraint(x),
                 Operation::Constant(x) => JitOperation::Constant(x),
+                Operation::Output(x) => JitOperation::Output(x),
             };
 
             NodeInfo { operation }
@@ -0,0 +1,160 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{BitSigned, BitUnsigned, Signed},
+        eq, ge, gt, le, lt, max, min, ne, Cipher,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+type U4 = BitUnsigned<4>;
+type S4 = BitSigned<4>;
+
+#[test]
+fn can_compare_unsigned() {
+    #[fhe_program(scheme = "bfv")]
+    fn compare(a: Cipher<U4>, b: Cipher<U4>) -> [Cipher<Signed>; 6] {
+        [lt(a, b), le(a, b), gt(a, b), ge(a, b), eq(a, b), ne(a, b)]
+    }
+
+    let app = Compiler::new()
+        .fhe_program(compare)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    for (a, b) in [(3u64, 9u64), (9, 3), (7, 7), (0, 15), (15, 0)] {
+        let a_c = runtime.encrypt(U4::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(U4::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(compare).unwrap(), args, &public_key)
+            .unwrap();
+
+        let actual: [Signed; 6] = runtime.decrypt(&result[0], &private_key).unwrap();
+        let actual = actual.iter().map(|x| i64::from(*x)).collect::<Vec<_>>();
+
+        let expected = [a < b, a <= b, a > b, a >= b, a == b, a != b]
+            .iter()
+            .map(|x| *x as i64)
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected, "a = {a}, b = {b}");
+    }
+}
+
+#[test]
+fn can_compare_signed() {
+    #[fhe_program(scheme = "bfv")]
+    fn compare(a: Cipher<S4>, b: Cipher<S4>) -> [Cipher<Signed>; 2] {
+        [lt(a, b), eq(a, b)]
+    }
+
+    let app = Compiler::new()
+        .fhe_program(compare)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    for (a, b) in [(-3i64, 2i64), (2, -3), (-8, 7), (7, -8), (-5, -5), (-1, 0)] {
+        let a_c = runtime.encrypt(S4::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(S4::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(compare).unwrap(), args, &public_key)
+            .unwrap();
+
+        let [lt_c, eq_c]: [Signed; 2] = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(i64::from(lt_c), (a < b) as i64, "a = {a}, b = {b}");
+        assert_eq!(i64::from(eq_c), (a == b) as i64, "a = {a}, b = {b}");
+    }
+}
+
+#[test]
+fn can_max_min_signed() {
+    #[fhe_program(scheme = "bfv")]
+    fn max_min(a: Cipher<S4>, b: Cipher<S4>) -> [Cipher<S4>; 2] {
+        [max(a, b), min(a, b)]
+    }
+
+    let app = Compiler::new()
+        .fhe_program(max_min)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    for (a, b) in [(-6i64, 5i64), (4, -2), (3, 3)] {
+        let a_c = runtime.encrypt(S4::from(a), &public_key).unwrap();
+        let b_c = runtime.encrypt(S4::from(b), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(max_min).unwrap(), args, &public_key)
+            .unwrap();
+
+        let [max_c, min_c]: [S4; 2] = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(i64::from(max_c), a.max(b));
+        assert_eq!(i64::from(min_c), a.min(b));
+    }
+}
+
+#[test]
+fn can_chain_comparisons() {
+    #[fhe_program(scheme = "bfv")]
+    fn max3(a: Cipher<U4>, b: Cipher<U4>, c: Cipher<U4>) -> Cipher<U4> {
+        max(max(a, b), c)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(max3)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    for (a, b, c) in [(3u64, 9u64, 5u64), (12, 2, 7), (1, 4, 14)] {
+        let args: Vec<FheProgramInput> = vec![
+            runtime.encrypt(U4::from(a), &public_key).unwrap().into(),
+            runtime.encrypt(U4::from(b), &public_key).unwrap().into(),
+            runtime.encrypt(U4::from(c), &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(max3).unwrap(), args, &public_key)
+            .unwrap();
+
+        let actual: U4 = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(u64::from(actual), a.max(b).max(c));
+    }
+}
+
+#[test]
+#[should_panic]
+fn unsigned_rejects_out_of_range_value() {
+    let _ = U4::from(16);
+}
+
+#[test]
+#[should_panic]
+fn signed_rejects_out_of_range_value() {
+    let _ = S4::from(-9);
+}
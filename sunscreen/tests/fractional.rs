@@ -3,7 +3,10 @@
 use float_cmp::ApproxEq;
 use sunscreen::{
     fhe_program,
-    types::{bfv::Fractional, Cipher},
+    types::{
+        bfv::{Fractional, ReciprocalApprox},
+        select, Cipher,
+    },
     Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
 };
 
@@ -393,6 +396,153 @@ fn can_div_cipher_const() {
     test_div(4294967295.);
 }
 
+const DIV_APPROX: ReciprocalApprox = ReciprocalApprox {
+    min: 1.,
+    max: 2.,
+    iterations: 4,
+};
+
+#[test]
+fn can_div_cipher_cipher() {
+    #[fhe_program(scheme = "bfv")]
+    fn div(a: Cipher<Fractional<64>>, b: Cipher<Fractional<64>>) -> Cipher<Fractional<64>> {
+        Fractional::div_approx(a, b, DIV_APPROX)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(div)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1_000_000_000))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let test_div = |a: f64, b: f64| {
+        let a_c = runtime
+            .encrypt(Fractional::<64>::try_from(a).unwrap(), &public_key)
+            .unwrap();
+        let b_c = runtime
+            .encrypt(Fractional::<64>::try_from(b).unwrap(), &public_key)
+            .unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+        let c: f64 = c.into();
+
+        assert!(((c - a / b) / (a / b)).abs() <= DIV_APPROX.error_bound() + 1e-6);
+    };
+
+    test_div(3., 1.5);
+    test_div(-7., 1.25);
+    test_div(1., 2.);
+    test_div(5., 1.);
+}
+
+#[test]
+fn can_div_cipher_cipher_with_default_range() {
+    #[fhe_program(scheme = "bfv")]
+    fn div(a: Cipher<Fractional<64>>, b: Cipher<Fractional<64>>) -> Cipher<Fractional<64>> {
+        a / b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(div)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1_000_000_000))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let approx = ReciprocalApprox::default();
+
+    assert_eq!(approx.error_bound(), 2f64.powi(-16));
+
+    let test_div = |a: f64, b: f64| {
+        let a_c = runtime
+            .encrypt(Fractional::<64>::try_from(a).unwrap(), &public_key)
+            .unwrap();
+        let b_c = runtime
+            .encrypt(Fractional::<64>::try_from(b).unwrap(), &public_key)
+            .unwrap();
+
+        let args: Vec<FheProgramInput> = vec![a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+        let c: f64 = c.into();
+
+        assert!(((c - a / b) / (a / b)).abs() <= approx.error_bound() + 1e-6);
+    };
+
+    // The edges of the default range, [1, 2].
+    test_div(3., 1.);
+    test_div(-3., 1.);
+    test_div(3., 2.);
+    test_div(-3., 2.);
+    test_div(6., 1.5);
+}
+
+const RECIPROCAL_APPROX: ReciprocalApprox = ReciprocalApprox {
+    min: 4.,
+    max: 8.,
+    iterations: 3,
+};
+
+#[test]
+fn reciprocal_error_is_within_bound() {
+    #[fhe_program(scheme = "bfv")]
+    fn reciprocal(b: Cipher<Fractional<64>>) -> Cipher<Fractional<64>> {
+        Fractional::reciprocal_approx(b, RECIPROCAL_APPROX)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(reciprocal)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1_000_000_000))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let test_reciprocal = |b: f64| {
+        let b_c = runtime
+            .encrypt(Fractional::<64>::try_from(b).unwrap(), &public_key)
+            .unwrap();
+
+        let args: Vec<FheProgramInput> = vec![b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(reciprocal).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+        let c: f64 = c.into();
+
+        assert!((c * b - 1.).abs() <= RECIPROCAL_APPROX.error_bound() + 1e-6);
+    };
+
+    // The error is largest at the bottom of the range.
+    assert_eq!(RECIPROCAL_APPROX.error_bound(), 2f64.powi(-8));
+
+    test_reciprocal(4.);
+    test_reciprocal(5.);
+    test_reciprocal(6.);
+    test_reciprocal(8.);
+}
+
 #[test]
 fn can_negate() {
     #[fhe_program(scheme = "bfv")]
@@ -441,3 +591,44 @@ fn can_negate() {
 fn can_create_default() {
     assert_eq!(Into::<f64>::into(Fractional::<64>::default()), 0.0f64);
 }
+
+#[test]
+fn can_select() {
+    #[fhe_program(scheme = "bfv")]
+    fn choose(c: CipherFractional, a: CipherFractional, b: CipherFractional) -> CipherFractional {
+        select(c, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(choose)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(100000))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Fractional::<64>::from(-3.25);
+    let b = Fractional::<64>::from(1234.5);
+
+    for (cond, expected) in [(1., a), (0., b)] {
+        let args: Vec<FheProgramInput> = vec![
+            runtime
+                .encrypt(Fractional::<64>::from(cond), &public_key)
+                .unwrap()
+                .into(),
+            runtime.encrypt(a, &public_key).unwrap().into(),
+            runtime.encrypt(b, &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(choose).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, expected);
+    }
+}
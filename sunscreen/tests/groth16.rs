@@ -0,0 +1,54 @@
+#![cfg(feature = "groth16")]
+
+use rand::rngs::OsRng;
+use sunscreen::{types::zkp::NativeField, zkp_program, Compiler, Runtime};
+use sunscreen_zkp_backend::{groth16::Groth16Backend, BackendField, ZkpBackend};
+
+type G16Field = NativeField<<Groth16Backend as ZkpBackend>::Field>;
+
+#[zkp_program(backend = "groth16")]
+fn add_mul<F: BackendField>(#[public] a: NativeField<F>, b: NativeField<F>, c: NativeField<F>) {
+    let x = a * b + c;
+
+    x.constrain_eq(NativeField::from(42u32))
+}
+
+#[test]
+fn can_prove_and_verify_with_groth16() {
+    let app = Compiler::new()
+        .zkp_backend::<Groth16Backend>()
+        .zkp_program(add_mul)
+        .compile()
+        .unwrap();
+
+    let program = app.get_zkp_program(add_mul).unwrap();
+
+    let backend = Groth16Backend::setup(program, &[], &mut OsRng).unwrap();
+    let runtime = Runtime::new_zkp(&backend).unwrap();
+
+    let proof = runtime
+        .prove(
+            program,
+            vec![],
+            vec![G16Field::from(10u8)],
+            vec![G16Field::from(4u8), G16Field::from(2u8)],
+        )
+        .unwrap();
+
+    runtime
+        .verify(program, &proof, vec![], vec![G16Field::from(10u8)])
+        .unwrap();
+
+    // The proof doesn't hold for a different public input.
+    assert!(runtime
+        .verify(program, &proof, vec![], vec![G16Field::from(11u8)])
+        .is_err());
+
+    // A verifier holding only the verifying key accepts the proof.
+    let verifier =
+        Runtime::new_zkp(&Groth16Backend::from_verifying_key(backend.verifying_key())).unwrap();
+
+    verifier
+        .verify(program, &proof, vec![], vec![G16Field::from(10u8)])
+        .unwrap();
+}
@@ -1,7 +1,7 @@
 use seal_fhe::{CoefficientModulus, SecurityLevel};
 use sunscreen::types::bfv::Signed;
 use sunscreen_fhe_program::SchemeType;
-use sunscreen_runtime::{Ciphertext, Params, Runtime};
+use sunscreen_runtime::{Ciphertext, Params, PublicKey, Runtime};
 
 #[test]
 fn can_roundtrip_ciphertexts_bincode() {
@@ -62,3 +62,178 @@ fn can_roundtrip_ciphertexts_json() {
     let actual: i64 = v.into();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn mod_switched_outputs_are_smaller_and_roundtrip() {
+    use sunscreen::{fhe_program, types::Cipher, Compiler, FheProgramInput};
+
+    #[fhe_program(scheme = "bfv")]
+    fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(add)
+        .with_params(&Params {
+            lattice_dimension: 8192,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|c| c.value())
+                .collect(),
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Bfv,
+        })
+        .modulus_switching(true)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(27), &public_key).unwrap();
+
+    let fresh_len = bincode::serialize(&a).unwrap().len();
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .unwrap();
+
+    let serialized = bincode::serialize(&result[0]).unwrap();
+
+    assert!(serialized.len() < fresh_len);
+
+    let c: Ciphertext = bincode::deserialize(&serialized).unwrap();
+    let v: Signed = runtime.decrypt(&c, &private_key).unwrap();
+
+    let actual: i64 = v.into();
+    assert_eq!(actual, 42);
+}
+
+#[test]
+fn modulus_switched_multiplications_are_correct() {
+    use sunscreen::{fhe_program, types::Cipher, Compiler, FheProgramInput};
+
+    #[fhe_program(scheme = "bfv")]
+    fn mul(a: Cipher<Signed>, b: Cipher<Signed>, c: Cipher<Signed>) -> Cipher<Signed> {
+        a * b * c + a
+    }
+
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .with_params(&Params {
+            lattice_dimension: 8192,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|c| c.value())
+                .collect(),
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Bfv,
+        })
+        .modulus_switching(true)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let args: Vec<FheProgramInput> = [3, -5, 7]
+        .iter()
+        .map(|x| {
+            runtime
+                .encrypt(Signed::from(*x), &public_key)
+                .unwrap()
+                .into()
+        })
+        .collect();
+
+    let result = runtime
+        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .unwrap();
+
+    let v: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    let actual: i64 = v.into();
+    assert_eq!(actual, -102);
+}
+
+#[test]
+fn can_roundtrip_compact_symmetric_ciphertexts() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 8192,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (_, private_key) = runtime.generate_keys().unwrap();
+
+    let expected: i64 = 42;
+
+    let full = runtime
+        .encrypt_symmetric(Signed::from(expected), &private_key)
+        .unwrap();
+    let compact = runtime
+        .encrypt_symmetric_compact(Signed::from(expected), &private_key)
+        .unwrap();
+
+    let full = bincode::serialize(&full).unwrap();
+    let compact = bincode::serialize(&compact).unwrap();
+
+    assert!(compact.len() < full.len());
+
+    for data in [full, compact] {
+        let c: Ciphertext = bincode::deserialize(&data).unwrap();
+
+        let v: Signed = runtime.decrypt(&c, &private_key).unwrap();
+
+        let actual: i64 = v.into();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn can_encrypt_with_expanded_compact_public_key() {
+    let runtime = Runtime::new_fhe(&Params {
+        lattice_dimension: 8192,
+        plain_modulus: 1024,
+        coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+            .unwrap()
+            .iter()
+            .map(|c| c.value())
+            .collect(),
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    })
+    .unwrap();
+
+    let (compact_key, private_key) = runtime.generate_compact_keys().unwrap();
+
+    let public_key: PublicKey =
+        bincode::deserialize(&bincode::serialize(&compact_key).unwrap()).unwrap();
+
+    let expected: i64 = 42;
+
+    let c = runtime
+        .encrypt(Signed::from(expected), &public_key)
+        .unwrap();
+
+    let v: Signed = runtime.decrypt(&c, &private_key).unwrap();
+
+    let actual: i64 = v.into();
+    assert_eq!(actual, expected);
+}
@@ -1,7 +1,7 @@
 use sunscreen::{
     fhe_program,
-    types::{bfv::Signed, Cipher},
-    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+    types::{bfv::Signed, poly_eval, select, Cipher},
+    Compiler, Error, FheProgramInput, FheProgramReportExt, PlainModulusConstraint, Runtime,
 };
 
 use std::ops::*;
@@ -517,3 +517,121 @@ fn can_mul_literal_cipher() {
 fn can_create_default() {
     assert_eq!(Into::<i64>::into(Signed::default()), 0);
 }
+
+#[test]
+fn can_select_cipher_cipher() {
+    #[fhe_program(scheme = "bfv")]
+    fn choose(c: Cipher<Signed>, a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        select(c, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(choose)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Signed::from(15);
+    let b = Signed::from(-5);
+
+    for (cond, expected) in [(1, a), (0, b)] {
+        let c_c = runtime.encrypt(Signed::from(cond), &public_key).unwrap();
+        let a_c = runtime.encrypt(a, &public_key).unwrap();
+        let b_c = runtime.encrypt(b, &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![c_c.into(), a_c.into(), b_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(choose).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, expected);
+    }
+}
+
+#[test]
+fn can_poly_eval() {
+    const COEFFS: [i64; 8] = [1, -2, 0, 3, 0, 1, 0, -1];
+
+    #[fhe_program(scheme = "bfv")]
+    fn poly(x: Cipher<Signed>) -> Cipher<Signed> {
+        poly_eval(x, &COEFFS)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(100_000))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    for x in -2i64..=3 {
+        let x_c = runtime.encrypt(Signed::from(x), &public_key).unwrap();
+
+        let args: Vec<FheProgramInput> = vec![x_c.into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(poly).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        let expected = COEFFS.iter().rev().fold(0i64, |acc, c| acc * x + c);
+
+        assert_eq!(c, Signed::from(expected));
+    }
+}
+
+#[test]
+fn can_report_depth_and_noise() {
+    #[fhe_program(scheme = "bfv")]
+    fn depth(a: Cipher<Signed>, b: Cipher<Signed>) -> (Cipher<Signed>, Cipher<Signed>) {
+        (a * b * b, a + b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(depth)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let report = app.get_fhe_program(depth).unwrap().report().unwrap();
+
+    assert_eq!(report.outputs.len(), 2);
+    assert_eq!(report.outputs[0].depth, 2);
+    assert_eq!(report.outputs[1].depth, 0);
+    assert!(report.outputs[0].measured_noise_budget < report.outputs[1].measured_noise_budget);
+    assert!(report.outputs[0].critical_path.len() > 2);
+}
+
+#[test]
+fn no_params_explains_failure() {
+    #[fhe_program(scheme = "bfv")]
+    fn deep(a: Cipher<Signed>) -> Cipher<Signed> {
+        a * a * a * a
+    }
+
+    let result = Compiler::new()
+        .fhe_program(deep)
+        .additional_noise_budget(1000)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile();
+
+    match result {
+        Err(Error::NoParamsWithReason(reason)) => {
+            assert!(reason.contains("n=32768"));
+            assert!(reason.contains("multiplicative depth 3"));
+        }
+        _ => panic!("Expected Error::NoParamsWithReason"),
+    }
+}
@@ -1,6 +1,6 @@
 use sunscreen::{
     fhe_program,
-    types::{bfv::Batched, Cipher, SwapRows},
+    types::{bfv::Batched, dot, select, BroadcastLane, Cipher, SwapRows},
     Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
 };
 
@@ -301,3 +301,188 @@ fn can_neg_cipher_cipher() {
 
     assert_eq!(c, neg_impl(a));
 }
+
+#[test]
+fn can_dot_and_broadcast_cipher() {
+    #[fhe_program(scheme = "bfv")]
+    fn dot_prod(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        dot(a, b)
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn broadcast(a: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        a.broadcast_lane((1, 2))
+    }
+
+    let app = Compiler::new()
+        .fhe_program(dot_prod)
+        .fhe_program(broadcast)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let b = Batched::<4>::try_from([vec![8, 7, 6, 5], vec![4, 3, 2, 1]]).unwrap();
+
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+    let b_c = runtime.encrypt(b, &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.clone().into(), b_c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(dot_prod).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, dot(a, b));
+    assert_eq!(c, [[120; 4]; 2].into());
+
+    let args: Vec<FheProgramInput> = vec![a_c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(broadcast).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, a.broadcast_lane((1, 2)));
+    assert_eq!(c, [[7; 4]; 2].into());
+}
+
+#[test]
+fn can_generate_keys_for_used_rotations_only() {
+    #[fhe_program(scheme = "bfv")]
+    fn rotate(a: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        (a << 1).swap_rows()
+    }
+
+    let app = Compiler::new()
+        .fhe_program(rotate)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(rotate).unwrap();
+
+    assert_eq!(program.metadata.galois_steps, vec![0, 1]);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (all_keys, _) = runtime.generate_keys().unwrap();
+    let (public_key, private_key) = runtime.generate_keys_for(&[program]).unwrap();
+
+    assert!(public_key.relin_key.is_none());
+    assert!(
+        bincode::serialize(&public_key).unwrap().len()
+            < bincode::serialize(&all_keys).unwrap().len()
+    );
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into()];
+
+    let result = runtime.run(program, args, &public_key).unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, (a << 1).swap_rows());
+}
+
+#[test]
+fn can_mat_vec_mul_cipher() {
+    fn matrix() -> Vec<Vec<i64>> {
+        (0..8)
+            .map(|i| (0..8).map(|j| (i * 3 + j) % 5 - 2).collect())
+            .collect()
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn mat_vec(x: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        Batched::mat_vec_mul(&matrix(), x)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(mat_vec)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let x = (1..=8).collect::<Vec<i64>>();
+
+    let a = Batched::<4>::try_from([x[..4].to_vec(), x[4..].to_vec()]).unwrap();
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a_c.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(mat_vec).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    let expected = matrix()
+        .iter()
+        .map(|row| row.iter().zip(x.iter()).map(|(m, x)| m * x).sum())
+        .collect::<Vec<i64>>();
+
+    let expected =
+        Batched::<4>::try_from([expected[..4].to_vec(), expected[4..].to_vec()]).unwrap();
+
+    assert_eq!(c, expected);
+}
+
+#[test]
+fn can_select_per_lane() {
+    #[fhe_program(scheme = "bfv")]
+    fn choose(
+        c: Cipher<Batched<4>>,
+        a: Cipher<Batched<4>>,
+        b: Cipher<Batched<4>>,
+    ) -> Cipher<Batched<4>> {
+        select(c, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(choose)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let c = Batched::<4>::try_from([vec![1, 0, 0, 1], vec![0, 1, 1, 0]]).unwrap();
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let b = Batched::<4>::try_from([vec![-1, -2, -3, -4], vec![-5, -6, -7, -8]]).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(c, &public_key).unwrap().into(),
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(choose).unwrap(), args, &public_key)
+        .unwrap();
+
+    let actual: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    let expected = [vec![1, -2, -3, 4], vec![-5, 6, 7, -8]];
+
+    assert_eq!(actual, expected.try_into().unwrap());
+}
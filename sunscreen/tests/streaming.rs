@@ -0,0 +1,118 @@
+use sunscreen::{
+    types::{bfv::Signed, Cipher},
+    *,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn mul(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * b
+}
+
+#[fhe_program(scheme = "bfv")]
+fn sum(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+#[fhe_program(scheme = "bfv")]
+fn sum3(a: Cipher<Signed>, b: Cipher<Signed>, c: Cipher<Signed>) -> Cipher<Signed> {
+    a + b + c
+}
+
+#[test]
+fn can_stream_fhe_program() {
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .fhe_program(sum)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let records = (1..=10i64).map(|x| {
+        let a = runtime.encrypt(Signed::from(x), &public_key).unwrap();
+        let b = runtime.encrypt(Signed::from(x), &public_key).unwrap();
+
+        vec![a, b]
+    });
+
+    let results = runtime
+        .run_stream(app.get_fhe_program(mul).unwrap(), records, &public_key)
+        .unwrap();
+
+    for (x, result) in (1..=10i64).zip(results) {
+        let result = result.unwrap();
+        assert_eq!(result.len(), 1);
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+        assert_eq!(<Signed as Into<i64>>::into(c), x * x);
+    }
+}
+
+#[test]
+fn can_reduce_fhe_program_outputs() {
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .fhe_program(sum)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let records = (1..=10i64).map(|x| {
+        let a = runtime.encrypt(Signed::from(x), &public_key).unwrap();
+        let b = runtime.encrypt(Signed::from(x), &public_key).unwrap();
+
+        vec![a, b]
+    });
+
+    let result = runtime
+        .run_reduce(
+            app.get_fhe_program(mul).unwrap(),
+            records,
+            app.get_fhe_program(sum).unwrap(),
+            &public_key,
+        )
+        .unwrap()
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(<Signed as Into<i64>>::into(c), 385);
+
+    let empty: Vec<Vec<Ciphertext>> = vec![];
+
+    let result = runtime
+        .run_reduce(
+            app.get_fhe_program(mul).unwrap(),
+            empty,
+            app.get_fhe_program(sum).unwrap(),
+            &public_key,
+        )
+        .unwrap();
+
+    assert!(result.is_none());
+}
+
+#[test]
+fn reduce_rejects_mismatched_reducer() {
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .fhe_program(sum3)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let records: Vec<Vec<Ciphertext>> = vec![];
+
+    let result = runtime.run_reduce(
+        app.get_fhe_program(mul).unwrap(),
+        records,
+        app.get_fhe_program(sum3).unwrap(),
+        &public_key,
+    );
+
+    assert!(matches!(result, Err(RuntimeError::ArgumentMismatch(_))));
+}
@@ -0,0 +1,238 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Batched, Cipher},
+    Compiler, FheApplication, FheProgramInput, FheRuntime, PartialDecryption,
+    PlainModulusConstraint, PublicKey, Runtime, RuntimeError, ThresholdConfig, ThresholdPrivateKey,
+    SMUDGING_SECURITY_BITS,
+};
+
+const CONFIG: ThresholdConfig = ThresholdConfig {
+    parties: 3,
+    threshold: 2,
+    seed: [7; 32],
+};
+
+/**
+ * The noise budget we claim ciphertexts have left when partially
+ * decrypting them. Programs are compiled to leave a few more bits, since
+ * threshold keys add noise.
+ */
+const NOISE_BUDGET: u32 = 50;
+
+#[fhe_program(scheme = "bfv")]
+fn mul_rotate(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+    (a * b) << 1
+}
+
+fn compile() -> FheApplication {
+    Compiler::new()
+        .fhe_program(mul_rotate)
+        .additional_noise_budget(NOISE_BUDGET + 10)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap()
+}
+
+/**
+ * Runs threshold key generation for every party in `CONFIG`, creating
+ * Galois keys for the given rotation steps.
+ */
+fn generate_threshold_keys(
+    runtime: &FheRuntime,
+    galois_steps: Option<Vec<i32>>,
+) -> (PublicKey, Vec<ThresholdPrivateKey>) {
+    let (key_shares, public_shares): (Vec<_>, Vec<_>) = (0..CONFIG.parties)
+        .map(|party| {
+            runtime
+                .generate_key_share(&CONFIG, party, galois_steps.clone())
+                .unwrap()
+        })
+        .unzip();
+
+    let relin_shares = key_shares
+        .iter()
+        .map(|k| runtime.generate_relin_key_share(k, &public_shares).unwrap())
+        .collect::<Vec<_>>();
+
+    let public_key = runtime
+        .combine_public_key_shares(&CONFIG, &public_shares, &relin_shares)
+        .unwrap();
+
+    let fragments = key_shares
+        .iter()
+        .map(|k| runtime.split_key_share(k).unwrap())
+        .collect::<Vec<_>>();
+
+    let private_keys = key_shares
+        .iter()
+        .enumerate()
+        .map(|(party, k)| {
+            let received = fragments
+                .iter()
+                .map(|f| f[party].clone())
+                .collect::<Vec<_>>();
+
+            assert!(received.iter().all(|f| f.recipient() == party as u32));
+
+            runtime.combine_private_key_fragments(k, &received).unwrap()
+        })
+        .collect();
+
+    (public_key, private_keys)
+}
+
+#[test]
+fn can_threshold_decrypt_program_output() {
+    let app = compile();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let program = app.get_fhe_program(mul_rotate).unwrap();
+    let galois_steps = program.metadata.galois_steps.clone();
+    let (public_key, private_keys) = generate_threshold_keys(&runtime, Some(galois_steps));
+
+    assert!(public_key.relin_key.is_some());
+    assert!(public_key.galois_key.is_some());
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let b = Batched::<4>::try_from([vec![2, 2, 2, 2], vec![-1, -1, -1, -1]]).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime.run(program, args, &public_key).unwrap();
+
+    let expected = Batched::<4>::try_from([vec![4, 6, 8, 2], vec![-6, -7, -8, -5]]).unwrap();
+
+    for parties in [[0, 1], [1, 2]] {
+        let partials = parties
+            .iter()
+            .map(|p| {
+                runtime
+                    .partial_decrypt(
+                        &result[0],
+                        &private_keys[*p as usize],
+                        &parties,
+                        NOISE_BUDGET,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let actual: Batched<4> = runtime.threshold_decrypt(&result[0], &partials).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn threshold_decrypt_requires_threshold_parties() {
+    let app = compile();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_keys) = generate_threshold_keys(&runtime, Some(vec![]));
+
+    assert!(public_key.galois_key.is_none());
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+
+    let partial = runtime
+        .partial_decrypt(&a_c, &private_keys[0], &[0, 2], NOISE_BUDGET)
+        .unwrap();
+
+    assert_eq!(
+        runtime
+            .threshold_decrypt::<Batched<4>>(&a_c, std::slice::from_ref(&partial))
+            .err(),
+        Some(RuntimeError::InsufficientPartialDecryptions)
+    );
+
+    assert_eq!(
+        runtime
+            .partial_decrypt(&a_c, &private_keys[0], &[0], NOISE_BUDGET)
+            .err(),
+        Some(RuntimeError::InsufficientPartialDecryptions)
+    );
+
+    let partials: Vec<PartialDecryption> = vec![
+        partial,
+        runtime
+            .partial_decrypt(&a_c, &private_keys[2], &[0, 2], NOISE_BUDGET)
+            .unwrap(),
+    ];
+
+    let actual: Batched<4> = runtime.threshold_decrypt(&a_c, &partials).unwrap();
+
+    assert_eq!(actual, a);
+}
+
+#[test]
+fn rejects_invalid_threshold_config() {
+    let app = compile();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let config = ThresholdConfig {
+        threshold: 4,
+        ..CONFIG
+    };
+
+    assert_eq!(
+        runtime.generate_key_share(&config, 0, None).err(),
+        Some(RuntimeError::InvalidThresholdParams)
+    );
+
+    let (_, public_share) = runtime.generate_key_share(&CONFIG, 0, None).unwrap();
+
+    assert_eq!(
+        runtime
+            .combine_public_key_shares(&CONFIG, &[public_share], &[])
+            .err(),
+        Some(RuntimeError::MissingKeyShares)
+    );
+}
+
+#[test]
+fn partial_decrypt_requires_noise_budget_for_smudging() {
+    let app = compile();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_keys) = generate_threshold_keys(&runtime, Some(vec![]));
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let a_c = runtime.encrypt(a, &public_key).unwrap();
+
+    assert_eq!(
+        runtime
+            .partial_decrypt(&a_c, &private_keys[0], &[0, 1], SMUDGING_SECURITY_BITS)
+            .err(),
+        Some(RuntimeError::TooMuchNoise)
+    );
+}
+
+#[test]
+fn rejects_public_shares_with_different_galois_steps() {
+    let app = compile();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let public_shares = (0..CONFIG.parties)
+        .map(|party| {
+            let steps = if party == 0 { vec![1] } else { vec![2] };
+
+            runtime
+                .generate_key_share(&CONFIG, party, Some(steps))
+                .unwrap()
+                .1
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        runtime
+            .combine_public_key_shares(&CONFIG, &public_shares, &[])
+            .err(),
+        Some(RuntimeError::InvalidThresholdParams)
+    );
+}
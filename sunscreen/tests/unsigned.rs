@@ -6,7 +6,7 @@ use sunscreen::{
     fhe_program,
     types::{
         bfv::{Unsigned, Unsigned256},
-        Cipher,
+        select, Cipher,
     },
     Compiler, FheApplication, FheProgramInput, FheRuntime, PrivateKey, PublicKey, Runtime,
 };
@@ -34,6 +34,15 @@ fhe_program! {
     (mul, *, Unsigned256)
 }
 
+#[fhe_program(scheme = "bfv")]
+fn choose(
+    c: Cipher<Unsigned256>,
+    a: Cipher<Unsigned256>,
+    b: Cipher<Unsigned256>,
+) -> Cipher<Unsigned256> {
+    select(c, a, b)
+}
+
 struct FheApp {
     app: FheApplication,
     rt: FheRuntime,
@@ -49,6 +58,7 @@ impl FheApp {
             .fhe_program(sub_plain)
             .fhe_program(mul)
             .fhe_program(mul_plain)
+            .fhe_program(choose)
             .compile()
             .unwrap();
         let rt: FheRuntime = Runtime::new_fhe(app.params()).unwrap();
@@ -163,3 +173,29 @@ fn carry_at_limb_boundary() {
     let b = U256::from_words([0, 1, 0, 0]);
     run_with(a, b, U256::wrapping_add, add, add_plain);
 }
+
+#[test]
+fn can_select() {
+    let FheApp { app, rt, pk, sk } = &*FHE_APP;
+
+    let a = U256::MAX;
+    let b = U256::from_u64(42);
+
+    for (cond, expected) in [(1u64, a), (0, b)] {
+        let args: Vec<FheProgramInput> = vec![
+            rt.encrypt(Unsigned256::from(U256::from_u64(cond)), pk)
+                .unwrap()
+                .into(),
+            rt.encrypt(Unsigned256::from(a), pk).unwrap().into(),
+            rt.encrypt(Unsigned256::from(b), pk).unwrap().into(),
+        ];
+
+        let result = rt
+            .run(app.get_fhe_program(choose).unwrap(), args, pk)
+            .unwrap();
+
+        let c: Unsigned256 = rt.decrypt(&result[0], sk).unwrap();
+
+        assert_eq!(expected, c.into());
+    }
+}
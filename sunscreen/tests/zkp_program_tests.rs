@@ -37,6 +37,55 @@ fn can_add_and_mul_native_fields() {
         .unwrap();
 }
 
+#[test]
+fn can_prove_with_shared_generators() {
+    #[zkp_program(backend = "bulletproofs")]
+    fn add_mul<F: BackendField>(a: NativeField<F>, b: NativeField<F>, c: NativeField<F>) {
+        let x = a * b + c;
+
+        x.constrain_eq(NativeField::from(42u32))
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(add_mul)
+        .compile()
+        .unwrap();
+
+    let prover = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+    let verifier = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+    let generators = prover.generators(64);
+
+    let program = app.get_zkp_program(add_mul).unwrap();
+
+    let proof = prover
+        .prove_with_generators(
+            program,
+            &generators,
+            vec![],
+            vec![],
+            vec![BPField::from(10u8), BPField::from(4u8), BPField::from(2u8)],
+        )
+        .unwrap();
+
+    verifier
+        .verify_with_generators(
+            program,
+            &proof,
+            &generators,
+            Vec::<ZkpProgramInput>::new(),
+            vec![],
+        )
+        .unwrap();
+
+    // Proofs made with shared generators verify with freshly created ones.
+    Runtime::new_zkp(&BulletproofsBackend::new())
+        .unwrap()
+        .verify(program, &proof, Vec::<ZkpProgramInput>::new(), vec![])
+        .unwrap();
+}
+
 #[test]
 fn get_input_mismatch_on_incorrect_args() {
     use sunscreen_runtime::Error;
@@ -98,6 +147,131 @@ fn can_use_public_inputs() {
         .unwrap();
 }
 
+#[test]
+fn can_verify_batch_and_report_failing_proof() {
+    use sunscreen_runtime::Error;
+    use sunscreen_zkp_backend::Error as ZkpError;
+
+    #[zkp_program(backend = "bulletproofs")]
+    fn add_mul<F: BackendField>(#[public] a: NativeField<F>, b: NativeField<F>, c: NativeField<F>) {
+        let x = a * b + c;
+
+        x.constrain_eq(NativeField::from(42u32))
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(add_mul)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(add_mul).unwrap();
+
+    let mut batch = [(10u8, 4u8, 2u8), (8, 5, 2), (20, 2, 2)]
+        .iter()
+        .map(|(a, b, c)| {
+            let proof = runtime
+                .prove(
+                    program,
+                    vec![],
+                    vec![BPField::from(*a)],
+                    vec![BPField::from(*b), BPField::from(*c)],
+                )
+                .unwrap();
+
+            (proof, vec![BPField::from(*a)])
+        })
+        .collect::<Vec<_>>();
+
+    runtime.verify_batch(program, vec![], &batch).unwrap();
+
+    // Claim the second proof used a different public input.
+    batch[1].1 = vec![BPField::from(9u8)];
+
+    let result = runtime.verify_batch(program, vec![], &batch);
+
+    match result {
+        Err(Error::ZkpError(ZkpError::BatchVerificationFailed(failure))) => {
+            assert_eq!(failure.index, 1)
+        }
+        _ => panic!("Expected batch verification to fail on proof 1."),
+    }
+}
+
+#[test]
+fn can_return_public_outputs() {
+    use sunscreen::{RuntimeError, ZkpProof};
+    use sunscreen_zkp_backend::BigInt;
+
+    #[zkp_program(backend = "bulletproofs")]
+    fn mul_add<F: BackendField>(
+        #[public] a: NativeField<F>,
+        b: NativeField<F>,
+        c: NativeField<F>,
+    ) -> (NativeField<F>, [NativeField<F>; 2]) {
+        (a * b + c, [b, c + c])
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(mul_add)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(mul_add).unwrap();
+
+    let proof = runtime
+        .prove(
+            program,
+            vec![],
+            vec![BPField::from(10u8)],
+            vec![BPField::from(4u8), BPField::from(2u8)],
+        )
+        .unwrap();
+
+    let outputs = runtime
+        .verify(program, &proof, vec![], vec![BPField::from(10u8)])
+        .unwrap();
+
+    assert_eq!(outputs.as_native_fields(), [42u32, 4, 4].map(BigInt::from));
+
+    let (sum, [b, c]): (BPField, [BPField; 2]) = outputs.get().unwrap();
+
+    assert_eq!(sum.val, BigInt::from(42u32));
+    assert_eq!(b.val, BigInt::from(4u32));
+    assert_eq!(c.val, BigInt::from(4u32));
+
+    // Decoding as a type with the wrong number of fields fails.
+    assert_eq!(
+        outputs.get::<[BPField; 2]>().unwrap_err(),
+        RuntimeError::ZkpOutputMismatch {
+            expected: 2,
+            actual: 3
+        }
+    );
+
+    // Claim the program returned something else.
+    let forged = ZkpProof::new(
+        proof.proof().clone(),
+        [43u32, 4, 4].map(BigInt::from).to_vec(),
+    );
+
+    assert!(runtime
+        .verify(program, &forged, vec![], vec![BPField::from(10u8)])
+        .is_err());
+
+    // Or that it returned a different number of values.
+    let forged = ZkpProof::new(proof.proof().clone(), [42u32, 4].map(BigInt::from).to_vec());
+
+    assert!(runtime
+        .verify(program, &forged, vec![], vec![BPField::from(10u8)])
+        .is_err());
+}
+
 #[test]
 fn can_use_constant_inputs() {
     #[zkp_program(backend = "bulletproofs")]
@@ -166,3 +340,52 @@ fn can_declare_array_inputs() {
         .verify(program, &proof, Vec::<ZkpProgramInput>::new(), vec![])
         .unwrap();
 }
+
+#[test]
+fn can_verify_with_program_and_proof_from_wire_format() {
+    use sunscreen::{types::zkp::ZkpUnsigned, WireFormat, ZkpProof};
+    use sunscreen_zkp_backend::CompiledZkpProgram;
+
+    type Unsigned = ZkpUnsigned<<BulletproofsBackend as ZkpBackend>::Field, 8>;
+
+    #[zkp_program(backend = "bulletproofs")]
+    fn add<F: BackendField>(a: ZkpUnsigned<F, 8>, #[public] b: ZkpUnsigned<F, 8>) {
+        (a + b).constrain_eq(ZkpUnsigned::from(42u8));
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(add)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(&BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(add).unwrap();
+
+    let proof = runtime
+        .prove(
+            program,
+            vec![],
+            vec![Unsigned::from(2u8)],
+            vec![Unsigned::from(40u8)],
+        )
+        .unwrap();
+
+    let program = CompiledZkpProgram::from_wire(&program.to_wire().unwrap()).unwrap();
+    let proof = ZkpProof::from_wire(&proof.to_wire().unwrap()).unwrap();
+
+    runtime
+        .verify(&program, &proof, vec![], vec![Unsigned::from(2u8)])
+        .unwrap();
+
+    // Deserialized programs lose their gadgets, so they can't prove.
+    assert!(runtime
+        .prove(
+            &program,
+            vec![],
+            vec![Unsigned::from(2u8)],
+            vec![Unsigned::from(40u8)],
+        )
+        .is_err());
+}
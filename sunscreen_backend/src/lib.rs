@@ -5,19 +5,26 @@
 //! following useful operations:
 //! * [`compile`] takes either an FHE program from the compiler frontend and applies a set
 //! of transformations.
+//! * [`insert_mod_switches`] modulus switches a compiled FHE program's intermediate values
+//! and outputs for a given set of scheme parameters.
+//! * [`FheProgramReport`] reports the multiplicative depth and noise of every node in an
+//! FHE program.
 
 mod error;
 /**
  * A module for performing noise estimation on FHE programs.
  */
 pub mod noise_model;
+mod report;
 mod transforms;
 
 pub use error::*;
+pub use report::*;
 
 use sunscreen_fhe_program::FheProgram;
+use sunscreen_runtime::Params;
 
-use transforms::transform_intermediate_representation;
+use transforms::{apply_insert_mod_switches, transform_intermediate_representation};
 
 /**
  * Clones the given [`FheProgram`] and compiles it.
@@ -38,3 +45,26 @@ pub fn compile_inplace(mut ir: FheProgram) -> FheProgram {
 
     ir
 }
+
+/**
+ * Inserts modulus switches into the given compiled [`FheProgram`] so long
+ * as each output retains at least `noise_margin_bits` of noise budget under
+ * the given [`Params`].
+ *
+ * # Remarks
+ * Modulus switching drops primes from a ciphertext's coefficient modulus.
+ * Switching before a multiplication makes it faster and switching before an
+ * output makes the output smaller to serialize. Switched outputs live at a
+ * lower level in the modulus switching chain, so they cannot be combined
+ * with fresh ciphertexts in later FHE programs.
+ *
+ * This function runs the program to measure noise, so it can take
+ * substantial time for large programs.
+ */
+pub fn insert_mod_switches(
+    ir: &mut FheProgram,
+    params: &Params,
+    noise_margin_bits: u32,
+) -> Result<()> {
+    apply_insert_mod_switches(ir, params, noise_margin_bits)
+}
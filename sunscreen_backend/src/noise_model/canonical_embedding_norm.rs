@@ -63,6 +63,21 @@ impl CanonicalEmbeddingNormModel {
         val
     }
 
+    /**
+     * Compute q after dropping `level` primes from the data-level
+     * coefficient modulus chain via modulus switching. A level of 0
+     * yields [`total_q`](Self::total_q).
+     */
+    pub fn level_q(&self, level: usize) -> BigUint {
+        let data_len = usize::max(self.params.coeff_modulus.len() - 1, 1);
+
+        self.params
+            .coeff_modulus
+            .iter()
+            .take(usize::max(data_len.saturating_sub(level), 1))
+            .fold(BigUint::from(1u64), |sum, x| sum * (*x))
+    }
+
     /**
      * Compute `q mod t`, where q is `total_q(params)` and `t` is the plain
      * modulus.
@@ -169,6 +184,20 @@ impl NoiseModel for CanonicalEmbeddingNormModel {
         // TODO: Make a real heuristic
         a_invariant_noise + noise_budget_to_noise(8.)
     }
+
+    fn mod_switch(&self, a_invariant_noise: f64, level: usize) -> f64 {
+        let q = self
+            .level_q(level)
+            .to_f64()
+            .expect("Failed to convert BigUInt to f64");
+        let t = self.params.plain_modulus as f64;
+        let n = self.params.lattice_dimension as f64;
+
+        // Switching scales the ciphertext by q' / q and rounds, which
+        // preserves the invariant noise but adds a rounding term
+        // proportional to the canonical embedding norm of the secret key.
+        a_invariant_noise + (t / q) * f64::sqrt(3. * n + 2. * n * n)
+    }
 }
 
 #[cfg(test)]
@@ -450,6 +450,10 @@ impl NoiseModel for MeasuredModel {
     fn shift_right(&self, _a_invariant_noise: f64, _places: i32) -> f64 {
         0.
     }
+
+    fn mod_switch(&self, _a_invariant_noise: f64, _level: usize) -> f64 {
+        0.
+    }
 }
 
 #[test]
@@ -36,12 +36,43 @@ pub const NOISE_MAX: f64 = NOISE_STD_DEV * NOISE_NUM_STD_DEVIATIONS;
  * validate before using this function to ascertain this.
  */
 pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram) -> Vec<f64> {
+    let noise_levels = predict_node_noise(model, fhe_program);
+
+    fhe_program
+        .graph
+        .node_indices()
+        .filter_map(|node_id| match fhe_program.graph[node_id].operation {
+            OutputCiphertext => Some(noise_levels[node_id.index()]),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+ * Returns the predicted noise level of every node in the given
+ * [`FheProgram`], indexed by node index. Plaintext nodes have 0 noise.
+ *
+ * # Remarks
+ * Empirical models such as [`MeasuredModel`] only report noise at
+ * outputs, so intermediate values are only meaningful for predictive
+ * models such as [`CanonicalEmbeddingNormModel`].
+ *
+ * # Panic
+ * Panics if the FHE program is not well formed. You should call
+ * validate before using this function to ascertain this.
+ */
+pub fn predict_node_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram) -> Vec<f64> {
     let mut noise_levels: Vec<AtomicCell<f64>> = Vec::with_capacity(fhe_program.graph.node_count());
 
     for _ in 0..fhe_program.graph.node_count() {
         noise_levels.push(AtomicCell::new(0.));
     }
 
+    // The number of modulus switches applied to each node's ciphertext.
+    let levels: Vec<AtomicCell<usize>> = (0..fhe_program.graph.node_count())
+        .map(|_| AtomicCell::new(0))
+        .collect();
+
     let node_id_to_output_id = fhe_program
         .graph
         .node_indices()
@@ -93,6 +124,14 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
 
                     model.relinearize(noise_levels[x.index()].load())
                 }
+                ModSwitch => {
+                    let x = query.get_unary_operand(node_id).unwrap();
+                    let level = levels[x.index()].load() + 1;
+
+                    levels[node_id.index()].store(level);
+
+                    model.mod_switch(noise_levels[x.index()].load(), level)
+                }
                 Negate => {
                     let x = query.get_unary_operand(node_id).unwrap();
 
@@ -149,6 +188,12 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
 
                     model.swap_rows(noise_levels[x.index()].load())
                 }
+                LessThan { .. } | NotEqual | Select => {
+                    panic!(
+                        "{:#?} should have been lowered during compilation",
+                        node.operation
+                    )
+                }
             };
 
             noise_levels[node_id.index()].store(noise);
@@ -159,14 +204,7 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
     )
     .unwrap(); // No errors returned, so unwrap is safe.
 
-    noise_levels
-        .iter()
-        .zip(fhe_program.graph.node_indices())
-        .filter_map(|(x, node_id)| match fhe_program.graph[node_id].operation {
-            OutputCiphertext => Some(x.load()),
-            _ => None,
-        })
-        .collect()
+    noise_levels.iter().map(|x| x.load()).collect()
 }
 
 /**
@@ -269,6 +307,16 @@ pub trait NoiseModel {
      * Predict the amount of noise after a row swap.
      */
     fn shift_right(&self, a_invariant_noise: f64, places: i32) -> f64;
+
+    /**
+     * Predict the amount of noise after switching a ciphertext to the next
+     * parameters in the modulus switching chain.
+     *
+     * # Remarks
+     * `level` is the number of primes dropped from the coefficient modulus
+     * after this switch (i.e. 1 for the first switch).
+     */
+    fn mod_switch(&self, a_invariant_noise: f64, level: usize) -> f64;
 }
 
 #[test]
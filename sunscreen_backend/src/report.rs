@@ -0,0 +1,318 @@
+use petgraph::{algo::toposort, stable_graph::NodeIndex, visit::NodeIndexable, Direction};
+use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Operation, OutputType, OutputTypeTrait};
+use sunscreen_runtime::Params;
+
+use crate::noise_model::{
+    noise_to_noise_budget, predict_node_noise, predict_noise, CanonicalEmbeddingNormModel,
+    MeasuredModel, TargetNoiseLevel,
+};
+use crate::Result;
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+/**
+ * Diagnostics for a single node in an [`FheProgram`].
+ */
+pub struct NodeReport {
+    /**
+     * The node's index in the FHE program's graph.
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The node's operation.
+     */
+    pub operation: Operation,
+
+    /**
+     * The number of ciphertext-ciphertext multiplications on the longest
+     * path from any input to this node, inclusive.
+     */
+    pub depth: usize,
+
+    /**
+     * The noise budget in bits the
+     * [`CanonicalEmbeddingNormModel`] predicts this node's ciphertext
+     * has remaining. [`None`] for plaintext nodes.
+     */
+    pub predicted_noise_budget: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/**
+ * Diagnostics for a single output of an [`FheProgram`].
+ */
+pub struct OutputReport {
+    /**
+     * The index of this output in the FHE program's return values.
+     */
+    pub output: usize,
+
+    /**
+     * The output node's index in the FHE program's graph.
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The multiplicative depth of this output.
+     */
+    pub depth: usize,
+
+    /**
+     * The noise budget in bits the [`CanonicalEmbeddingNormModel`] predicts
+     * this output has remaining.
+     */
+    pub predicted_noise_budget: f64,
+
+    /**
+     * The noise budget in bits remaining after running the FHE program on
+     * freshly encrypted inputs. Parameter search uses this value.
+     */
+    pub measured_noise_budget: f64,
+
+    /**
+     * The chain of nodes, from an input to this output, that determines
+     * this output's multiplicative depth and thus dominates its noise.
+     * Restructuring the operations along this path (e.g. balancing a
+     * product tree) is the most effective way to reduce noise.
+     */
+    pub critical_path: Vec<NodeIndex>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/**
+ * Depth and noise diagnostics for an [`FheProgram`] under a given set of
+ * [`Params`]. [`Display`] renders a human-readable summary.
+ */
+pub struct FheProgramReport {
+    /**
+     * The lattice dimension this report was generated with.
+     */
+    pub lattice_dimension: u64,
+
+    /**
+     * A report for every node in the FHE program.
+     */
+    pub nodes: Vec<NodeReport>,
+
+    /**
+     * A report for every output in the FHE program, in return order.
+     */
+    pub outputs: Vec<OutputReport>,
+}
+
+impl FheProgramReport {
+    /**
+     * Analyzes the given compiled [`FheProgram`] under the given [`Params`].
+     *
+     * # Remarks
+     * This runs the FHE program to measure output noise, so it can take
+     * substantial time for large programs.
+     */
+    pub fn new(ir: &FheProgram, params: &Params) -> Result<Self> {
+        ir.validate()?;
+
+        let depths = multiplicative_depths(ir);
+
+        let predicted_model = CanonicalEmbeddingNormModel::new(params)?;
+        let predicted = predict_node_noise(&predicted_model, ir);
+
+        let noise_targets = ir
+            .graph
+            .node_weights()
+            .filter_map(|n| match n.operation {
+                Operation::InputCiphertext(_) => Some(TargetNoiseLevel::Fresh),
+                Operation::InputPlaintext(_) => Some(TargetNoiseLevel::NotApplicable),
+                _ => None,
+            })
+            .collect::<Vec<TargetNoiseLevel>>();
+
+        let measured_model = MeasuredModel::new(ir, params, &noise_targets)?;
+        let measured = predict_noise(&measured_model, ir);
+
+        let nodes = ir
+            .graph
+            .node_indices()
+            .map(|node| NodeReport {
+                node,
+                operation: ir.graph[node].operation.clone(),
+                depth: depths[node.index()],
+                predicted_noise_budget: match ir.graph[node].output_type() {
+                    OutputType::Ciphertext => Some(noise_to_noise_budget(predicted[node.index()])),
+                    OutputType::Plaintext => None,
+                },
+            })
+            .collect::<Vec<NodeReport>>();
+
+        let outputs = ir
+            .get_outputs()
+            .zip(measured)
+            .enumerate()
+            .map(|(output, (node, measured))| OutputReport {
+                output,
+                node,
+                depth: depths[node.index()],
+                predicted_noise_budget: noise_to_noise_budget(predicted[node.index()]),
+                measured_noise_budget: noise_to_noise_budget(measured),
+                critical_path: critical_path(ir, &depths, node),
+            })
+            .collect();
+
+        Ok(Self {
+            lattice_dimension: params.lattice_dimension,
+            nodes,
+            outputs,
+        })
+    }
+}
+
+impl Display for FheProgramReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "FHE program report (lattice_dimension={}):",
+            self.lattice_dimension
+        )?;
+
+        for o in &self.outputs {
+            writeln!(
+                f,
+                "  output {}: depth {}, measured noise budget {:.1} bits, predicted {:.1} bits",
+                o.output, o.depth, o.measured_noise_budget, o.predicted_noise_budget
+            )?;
+
+            let path = o
+                .critical_path
+                .iter()
+                .filter_map(|n| self.nodes.iter().find(|x| x.node == *n))
+                .map(|n| format!("{:?}({})", n.operation, n.node.index()))
+                .collect::<Vec<String>>()
+                .join(" -> ");
+
+            writeln!(f, "    critical path: {}", path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Returns the multiplicative depth of every node in the given
+ * [`FheProgram`], indexed by node index. The depth of a node is the
+ * number of ciphertext-ciphertext multiplications on the longest path
+ * from any input to that node, inclusive.
+ *
+ * # Panics
+ * If the FHE program contains a cycle. Validated programs never do.
+ */
+pub fn multiplicative_depths(ir: &FheProgram) -> Vec<usize> {
+    let mut depths = vec![0; ir.graph.node_bound()];
+
+    let order = toposort(&ir.graph.0, None).expect("FHE program contains a cycle");
+
+    for node in order {
+        let parent_depth = ir
+            .graph
+            .neighbors_directed(node, Direction::Incoming)
+            .map(|p| depths[p.index()])
+            .max()
+            .unwrap_or(0);
+
+        depths[node.index()] = match ir.graph[node].operation {
+            Operation::Multiply => parent_depth + 1,
+            _ => parent_depth,
+        };
+    }
+
+    depths
+}
+
+/**
+ * Walks backwards from `node`, at each step following the parent with the
+ * greatest depth.
+ */
+fn critical_path(ir: &FheProgram, depths: &[usize], node: NodeIndex) -> Vec<NodeIndex> {
+    let mut path = vec![node];
+    let mut cur = node;
+
+    while let Some(parent) = ir
+        .graph
+        .neighbors_directed(cur, Direction::Incoming)
+        .filter(|p| ir.graph[*p].output_type() == OutputType::Ciphertext)
+        .max_by_key(|p| depths[p.index()])
+    {
+        path.push(parent);
+        cur = parent;
+    }
+
+    path.reverse();
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seal_fhe::{CoefficientModulus, PlainModulus, SecurityLevel};
+    use sunscreen_fhe_program::SchemeType;
+
+    #[test]
+    fn depth_counts_only_ciphertext_multiplications() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let p = ir.add_input_plaintext(2);
+        let ab = ir.add_multiply(a, b);
+        let abp = ir.add_multiply_plaintext(ab, p);
+        let abb = ir.add_multiply(abp, b);
+        let s = ir.add_add(abb, a);
+        let o = ir.add_output_ciphertext(s);
+
+        let depths = multiplicative_depths(&ir);
+
+        assert_eq!(depths[a.index()], 0);
+        assert_eq!(depths[ab.index()], 1);
+        assert_eq!(depths[abp.index()], 1);
+        assert_eq!(depths[abb.index()], 2);
+        assert_eq!(depths[o.index()], 2);
+
+        let path = critical_path(&ir, &depths, o);
+
+        assert_eq!(path.last(), Some(&o));
+        assert!(path.contains(&ab) && path.contains(&abb));
+    }
+
+    #[test]
+    fn can_report_on_program() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_multiply(a, b);
+        let c = ir.add_relinearize(c);
+        ir.add_output_ciphertext(c);
+        ir.add_output_ciphertext(a);
+
+        let params = Params {
+            lattice_dimension: 8192,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            plain_modulus: PlainModulus::raw(1024).unwrap().value(),
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let report = FheProgramReport::new(&ir, &params).unwrap();
+
+        assert_eq!(report.outputs.len(), 2);
+        assert_eq!(report.outputs[0].depth, 1);
+        assert_eq!(report.outputs[1].depth, 0);
+        assert!(report.outputs[0].measured_noise_budget < report.outputs[1].measured_noise_budget);
+        assert!(report.to_string().contains("output 1: depth 0"));
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use petgraph::{
+    algo::toposort,
+    stable_graph::NodeIndex,
+    visit::{EdgeRef, NodeIndexable},
+    Direction,
+};
+use sunscreen_compiler_common::GraphQuery;
+use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Operation};
+use sunscreen_runtime::Params;
+
+use crate::noise_model::{noise_budget_to_noise, predict_noise, MeasuredModel, TargetNoiseLevel};
+use crate::Result;
+
+/**
+ * Inserts `count` modulus switches between `x` and every node that
+ * consumes it.
+ */
+fn insert_mod_switches_after(ir: &mut FheProgram, x: NodeIndex, count: usize) {
+    if count == 0 {
+        return;
+    }
+
+    let consumers = ir
+        .graph
+        .edges_directed(x, Direction::Outgoing)
+        .map(|e| (e.id(), e.target(), *e.weight()))
+        .collect::<Vec<_>>();
+
+    for (edge, _, _) in &consumers {
+        ir.graph.remove_edge(*edge);
+    }
+
+    let mut prev = x;
+
+    for _ in 0..count {
+        prev = ir.add_mod_switch(prev);
+    }
+
+    for (_, target, info) in consumers {
+        ir.graph.add_edge(prev, target, info);
+    }
+}
+
+/**
+ * Inserts `count` modulus switches on the edge from `x` to `y`.
+ */
+fn insert_mod_switches_between(ir: &mut FheProgram, x: NodeIndex, y: NodeIndex, count: usize) {
+    let edge = ir.graph.find_edge(x, y).unwrap();
+    let info = ir.graph[edge];
+    ir.graph.remove_edge(edge);
+
+    let mut prev = x;
+
+    for _ in 0..count {
+        prev = ir.add_mod_switch(prev);
+    }
+
+    ir.graph.add_edge(prev, y, info);
+}
+
+/**
+ * Inserts modulus switches so that the ciphertext operands of every binary
+ * operation are at the same level in the modulus switching chain. Returns
+ * the largest level of any ciphertext in the program.
+ *
+ * # Remarks
+ * A node's level is the number of modulus switches between it and the
+ * program's inputs. Plaintext operands don't have a level, as SEAL
+ * encodes them against whichever modulus the ciphertext uses.
+ */
+fn align_levels(ir: &mut FheProgram) -> usize {
+    let order = toposort(&ir.graph.0, None).expect("FHE program contains a cycle");
+
+    // None for plaintexts.
+    let mut levels: HashMap<NodeIndex, Option<usize>> = HashMap::new();
+    let mut max_level = 0;
+
+    for node in order {
+        let level = match ir.graph[node].operation {
+            Operation::InputCiphertext(_) => Some(0),
+            Operation::InputPlaintext(_) | Operation::Literal(_) => None,
+            Operation::ModSwitch => {
+                let x = GraphQuery::new(&ir.graph.0)
+                    .get_unary_operand(node)
+                    .unwrap();
+
+                levels[&x].map(|l| l + 1)
+            }
+            Operation::Add | Operation::Sub | Operation::Multiply => {
+                let (left, right) = GraphQuery::new(&ir.graph.0)
+                    .get_binary_operands(node)
+                    .unwrap();
+
+                // Both operands of these operations are ciphertexts.
+                let l = levels[&left].unwrap();
+                let r = levels[&right].unwrap();
+
+                if l < r {
+                    insert_mod_switches_between(ir, left, node, r - l);
+                } else if r < l {
+                    insert_mod_switches_between(ir, right, node, l - r);
+                }
+
+                Some(usize::max(l, r))
+            }
+            _ => ir
+                .graph
+                .neighbors_directed(node, Direction::Incoming)
+                .filter_map(|x| levels[&x])
+                .max(),
+        };
+
+        max_level = usize::max(max_level, level.unwrap_or(0));
+        levels.insert(node, level);
+    }
+
+    max_level
+}
+
+/**
+ * Clones `ir`, inserts `switches[i]` modulus switches after node `i`, and
+ * aligns the levels of binary operations' operands. Returns the new
+ * program and the largest level of any ciphertext in it.
+ */
+fn with_mod_switches(ir: &FheProgram, switches: &[usize]) -> (FheProgram, usize) {
+    let mut ir = ir.clone();
+
+    for (i, count) in switches.iter().enumerate() {
+        insert_mod_switches_after(&mut ir, NodeIndex::new(i), *count);
+    }
+
+    let max_level = align_levels(&mut ir);
+
+    (ir, max_level)
+}
+
+/**
+ * Greedily inserts modulus switches into the given FHE program so long as
+ * every output retains `noise_margin_bits` of noise budget.
+ *
+ * # Remarks
+ * We consider switching the ciphertext operands of multiplications, which
+ * makes the multiplications faster, and the operands of outputs, which
+ * makes the outputs smaller. Each round visits these candidates from the
+ * outputs backwards and keeps one more switch after a candidate if the
+ * program, run under the noise model, still meets the margin. When a
+ * switch leaves the operands of a binary operation at different levels, we
+ * switch the other operand as well.
+ */
+pub fn apply_insert_mod_switches(
+    ir: &mut FheProgram,
+    params: &Params,
+    noise_margin_bits: u32,
+) -> Result<()> {
+    // Fresh ciphertexts contain every prime except the special prime. We can
+    // drop primes until one remains.
+    let max_switches = params.coeff_modulus.len().saturating_sub(2);
+    let target_noise = noise_budget_to_noise(noise_margin_bits as f64);
+
+    let noise_targets = ir
+        .graph
+        .node_weights()
+        .filter_map(|n| match n.operation {
+            Operation::InputCiphertext(_) => Some(TargetNoiseLevel::Fresh),
+            Operation::InputPlaintext(_) => Some(TargetNoiseLevel::NotApplicable),
+            _ => None,
+        })
+        .collect::<Vec<TargetNoiseLevel>>();
+
+    let meets_margin =
+        |candidate: &FheProgram| match MeasuredModel::new(candidate, params, &noise_targets) {
+            Ok(model) => predict_noise(&model, candidate)
+                .iter()
+                .all(|noise| *noise <= target_noise),
+            Err(_) => false,
+        };
+
+    let mut candidates = toposort(&ir.graph.0, None)
+        .expect("FHE program contains a cycle")
+        .into_iter()
+        .filter(|x| {
+            !matches!(
+                ir.graph[*x].operation,
+                Operation::InputPlaintext(_) | Operation::Literal(_)
+            )
+        })
+        .filter(|x| {
+            ir.graph.edges_directed(*x, Direction::Outgoing).any(|e| {
+                matches!(
+                    ir.graph[e.target()].operation,
+                    Operation::Multiply | Operation::OutputCiphertext
+                )
+            })
+        })
+        .collect::<Vec<NodeIndex>>();
+
+    candidates.reverse();
+
+    let mut switches = vec![0usize; ir.graph.node_bound()];
+
+    for _ in 0..max_switches {
+        let mut progressed = false;
+
+        for x in &candidates {
+            switches[x.index()] += 1;
+
+            let (candidate, max_level) = with_mod_switches(ir, &switches);
+
+            if max_level <= max_switches && meets_margin(&candidate) {
+                progressed = true;
+            } else {
+                switches[x.index()] -= 1;
+            }
+        }
+
+        if !progressed {
+            break;
+        }
+    }
+
+    *ir = with_mod_switches(ir, &switches).0;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seal_fhe::{CoefficientModulus, PlainModulus, SecurityLevel};
+    use sunscreen_fhe_program::SchemeType;
+
+    fn make_params() -> Params {
+        let degree = 8192;
+
+        Params {
+            lattice_dimension: degree,
+            coeff_modulus: CoefficientModulus::bfv_default(degree, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            plain_modulus: PlainModulus::raw(1024).unwrap().value(),
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn count_mod_switches(ir: &FheProgram) -> usize {
+        ir.graph
+            .node_weights()
+            .filter(|n| matches!(n.operation, Operation::ModSwitch))
+            .count()
+    }
+
+    #[test]
+    fn inserts_mod_switches_before_outputs() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_add(a, b);
+        let o = ir.add_output_ciphertext(c);
+
+        apply_insert_mod_switches(&mut ir, &make_params(), 20).unwrap();
+
+        assert!(count_mod_switches(&ir) > 0);
+        assert!(ir.validate().is_ok());
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let operand = query.get_unary_operand(o).unwrap();
+
+        assert_eq!(ir.graph[operand].operation, Operation::ModSwitch);
+    }
+
+    #[test]
+    fn inserts_mod_switches_before_multiplications() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+        let ab = ir.add_multiply(a, b);
+        let ab = ir.add_relinearize(ab);
+        let abc = ir.add_multiply(ab, c);
+        let abc = ir.add_relinearize(abc);
+        ir.add_output_ciphertext(abc);
+
+        apply_insert_mod_switches(&mut ir, &make_params(), 20).unwrap();
+
+        assert!(ir.validate().is_ok());
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        let switched_mul = ir.graph.node_indices().any(|x| {
+            ir.graph[x].operation == Operation::Multiply && {
+                let (l, r) = query.get_binary_operands(x).unwrap();
+
+                ir.graph[l].operation == Operation::ModSwitch
+                    || ir.graph[r].operation == Operation::ModSwitch
+            }
+        });
+
+        assert!(switched_mul);
+
+        // Operands of every binary operation are already at the same level.
+        let node_count = ir.graph.node_count();
+        align_levels(&mut ir);
+
+        assert_eq!(ir.graph.node_count(), node_count);
+    }
+
+    #[test]
+    fn aligns_operand_levels() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let s = ir.add_mod_switch(a);
+        let c = ir.add_add(s, b);
+        ir.add_output_ciphertext(c);
+
+        assert_eq!(align_levels(&mut ir), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let (_, right) = query.get_binary_operands(c).unwrap();
+
+        assert_eq!(ir.graph[right].operation, Operation::ModSwitch);
+        assert_eq!(query.get_unary_operand(right).unwrap(), b);
+    }
+
+    #[test]
+    fn no_mod_switches_when_margin_unattainable() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_add(a, b);
+        ir.add_output_ciphertext(c);
+
+        apply_insert_mod_switches(&mut ir, &make_params(), 10_000).unwrap();
+
+        assert_eq!(count_mod_switches(&ir), 0);
+    }
+}
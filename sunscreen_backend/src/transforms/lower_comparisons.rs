@@ -0,0 +1,247 @@
+use sunscreen_compiler_common::{EdgeInfo, GraphQuery, NodeInfo};
+use sunscreen_fhe_program::{FheProgram, Operation};
+
+use petgraph::{
+    stable_graph::{NodeIndex, StableGraph},
+    visit::EdgeRef,
+    Direction,
+};
+
+type FheGraph = StableGraph<NodeInfo<Operation>, EdgeInfo>;
+
+/**
+ * Replaces every [`Operation::LessThan`] and [`Operation::NotEqual`] node
+ * with an equivalent circuit of ciphertext additions, subtractions and
+ * multiplications over the operand bits.
+ *
+ * # Remarks
+ * The circuits only use ciphertext operations, so they don't depend on the
+ * scheme parameters. Later transforms (e.g. relinearization) see the
+ * lowered operations like any other.
+ *
+ * # Panics
+ * If a comparison doesn't have an even, non-zero number of ordered
+ * operands. The compiler frontend never creates such nodes.
+ */
+pub fn apply_lower_comparisons(ir: &mut FheProgram) {
+    let comparisons = ir
+        .graph
+        .node_indices()
+        .filter(|x| {
+            matches!(
+                ir.graph[*x].operation,
+                Operation::LessThan { .. } | Operation::NotEqual
+            )
+        })
+        .collect::<Vec<NodeIndex>>();
+
+    let graph = &mut ir.graph.0;
+
+    for id in comparisons {
+        let operands = GraphQuery::new(graph)
+            .get_ordered_operands(id)
+            .expect("Comparison has malformed operands");
+
+        assert!(
+            !operands.is_empty() && operands.len() % 2 == 0,
+            "Comparison operands must be two equal-length lists of bits"
+        );
+
+        let (a, b) = operands.split_at(operands.len() / 2);
+
+        let result = match graph[id].operation {
+            Operation::LessThan { signed } => lower_less_than(graph, a, b, signed),
+            Operation::NotEqual => lower_not_equal(graph, a, b),
+            _ => unreachable!(),
+        };
+
+        let children = graph
+            .edges_directed(id, Direction::Outgoing)
+            .map(|e| (e.target(), *e.weight()))
+            .collect::<Vec<_>>();
+
+        for (child, edge) in children {
+            graph.add_edge(result, child, edge);
+        }
+
+        graph.remove_node(id);
+    }
+}
+
+fn add_binary(graph: &mut FheGraph, op: Operation, left: NodeIndex, right: NodeIndex) -> NodeIndex {
+    let node = graph.add_node(NodeInfo::new(op));
+
+    graph.add_edge(left, node, EdgeInfo::Left);
+    graph.add_edge(right, node, EdgeInfo::Right);
+
+    node
+}
+
+/**
+ * For bits `x` and `y`, adds a circuit computing `x OR y = x + y - xy`.
+ */
+fn add_or(graph: &mut FheGraph, x: NodeIndex, y: NodeIndex) -> NodeIndex {
+    let x_and_y = add_binary(graph, Operation::Multiply, x, y);
+    let sum = add_binary(graph, Operation::Add, x, y);
+
+    add_binary(graph, Operation::Sub, sum, x_and_y)
+}
+
+/**
+ * Adds a circuit returning 1 if the bits `a` and `b` (least significant
+ * first) differ anywhere and 0 otherwise.
+ */
+fn lower_not_equal(graph: &mut FheGraph, a: &[NodeIndex], b: &[NodeIndex]) -> NodeIndex {
+    // For bits, (a - b)^2 is 1 exactly when a != b.
+    let mut bits_ne = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| {
+            let diff = add_binary(graph, Operation::Sub, *a, *b);
+
+            add_binary(graph, Operation::Multiply, diff, diff)
+        })
+        .collect::<Vec<_>>();
+
+    // OR the bits together as a balanced tree to minimize depth.
+    while bits_ne.len() > 1 {
+        bits_ne = bits_ne
+            .chunks(2)
+            .map(|pair| match pair {
+                [x, y] => add_or(graph, *x, *y),
+                [x] => *x,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    bits_ne[0]
+}
+
+/**
+ * Adds a circuit returning 1 if `a < b` and 0 otherwise, where `a` and `b`
+ * are bits, least significant first. When `signed` is true, the most
+ * significant bit is a two's complement sign bit.
+ *
+ * # Remarks
+ * Each group of consecutive bits yields whether `a < b` and whether
+ * `a != b` over those bits. Adjacent groups combine as
+ * `lt = lt_hi + (1 - ne_hi) * lt_lo` and `ne = ne_hi OR ne_lo`, which we
+ * apply as a balanced tree. The group containing the least significant bit
+ * never needs its inequality, so we don't compute it. Unlike equality,
+ * inequality needs no constant terms, so the circuit has no plaintext
+ * operands.
+ */
+fn lower_less_than(
+    graph: &mut FheGraph,
+    a: &[NodeIndex],
+    b: &[NodeIndex],
+    signed: bool,
+) -> NodeIndex {
+    let msb = a.len() - 1;
+
+    let mut groups = a
+        .iter()
+        .zip(b.iter())
+        .enumerate()
+        .map(|(i, (a, b))| {
+            let a_and_b = add_binary(graph, Operation::Multiply, *a, *b);
+
+            // For bits, a < b is (1 - a) * b = b - ab.
+            let a_lt_b = add_binary(graph, Operation::Sub, *b, a_and_b);
+            let a_gt_b = add_binary(graph, Operation::Sub, *a, a_and_b);
+
+            // A set sign bit makes a value smaller, so the comparison
+            // flips.
+            let lt = if signed && i == msb { a_gt_b } else { a_lt_b };
+
+            let ne = if i == 0 {
+                None
+            } else {
+                Some(add_binary(graph, Operation::Add, a_lt_b, a_gt_b))
+            };
+
+            (lt, ne)
+        })
+        .collect::<Vec<_>>();
+
+    while groups.len() > 1 {
+        groups = groups
+            .chunks(2)
+            .map(|pair| match pair {
+                [(lt_lo, ne_lo), (lt_hi, ne_hi)] => {
+                    let ne_hi = ne_hi.expect("Only the lowest group lacks inequality");
+
+                    let masked = add_binary(graph, Operation::Multiply, ne_hi, *lt_lo);
+                    let lt = add_binary(graph, Operation::Add, *lt_hi, *lt_lo);
+                    let lt = add_binary(graph, Operation::Sub, lt, masked);
+                    let ne = ne_lo.map(|ne_lo| add_or(graph, ne_hi, ne_lo));
+
+                    (lt, ne)
+                }
+                [group] => *group,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    groups[0].0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+
+    fn create_comparison(operation: Operation, bits: usize) -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let inputs = (0..2 * bits)
+            .map(|i| ir.add_input_ciphertext(i))
+            .collect::<Vec<_>>();
+
+        let cmp = ir.add_node(operation);
+
+        for (i, x) in inputs.iter().enumerate() {
+            ir.add_edge(*x, cmp, EdgeInfo::Ordered(i));
+        }
+
+        ir.add_output_ciphertext(cmp);
+
+        ir
+    }
+
+    fn count(ir: &FheProgram, operation: Operation) -> usize {
+        ir.graph
+            .node_weights()
+            .filter(|n| n.operation == operation)
+            .count()
+    }
+
+    #[test]
+    fn lowers_less_than() {
+        // One multiplication per bit, one per merged pair of groups and one
+        // per inequality of merged groups that don't include the lowest bit.
+        for (bits, multiplications) in [(1, 1), (3, 5), (4, 8)] {
+            let mut ir = create_comparison(Operation::LessThan { signed: true }, bits);
+
+            apply_lower_comparisons(&mut ir);
+
+            assert_eq!(count(&ir, Operation::LessThan { signed: true }), 0);
+            assert_eq!(count(&ir, Operation::Multiply), multiplications);
+            assert!(ir.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn lowers_not_equal() {
+        let mut ir = create_comparison(Operation::NotEqual, 4);
+
+        apply_lower_comparisons(&mut ir);
+
+        // One squaring per bit and one multiplication per OR.
+        assert_eq!(count(&ir, Operation::NotEqual), 0);
+        assert_eq!(count(&ir, Operation::Multiply), 4 + 3);
+        assert!(ir.validate().is_ok());
+    }
+}
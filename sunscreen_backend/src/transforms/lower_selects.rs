@@ -0,0 +1,102 @@
+use sunscreen_compiler_common::{EdgeInfo, GraphQuery, NodeInfo};
+use sunscreen_fhe_program::{FheProgram, Operation};
+
+use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
+
+/**
+ * Replaces every [`Operation::Select`] node with `b + c * (a - b)`, where
+ * `c`, `a` and `b` are its operands.
+ *
+ * # Remarks
+ * This costs a single ciphertext multiplication, rather than the two
+ * needed by the naive `c * a + (1 - c) * b`. Since we lower selects before
+ * inserting relinearizations, the relinearization pass and noise model
+ * see exactly the operations that run.
+ *
+ * # Panics
+ * If a select doesn't have exactly 3 ordered operands. The compiler
+ * frontend never creates such nodes.
+ */
+pub fn apply_lower_selects(ir: &mut FheProgram) {
+    let selects = ir
+        .graph
+        .node_indices()
+        .filter(|x| ir.graph[*x].operation == Operation::Select)
+        .collect::<Vec<NodeIndex>>();
+
+    let graph = &mut ir.graph.0;
+
+    for id in selects {
+        let operands = GraphQuery::new(graph)
+            .get_ordered_operands(id)
+            .expect("Select has malformed operands");
+
+        assert_eq!(operands.len(), 3, "Select must have 3 operands");
+
+        let (cond, a, b) = (operands[0], operands[1], operands[2]);
+
+        let diff = graph.add_node(NodeInfo::new(Operation::Sub));
+        graph.add_edge(a, diff, EdgeInfo::Left);
+        graph.add_edge(b, diff, EdgeInfo::Right);
+
+        let masked = graph.add_node(NodeInfo::new(Operation::Multiply));
+        graph.add_edge(cond, masked, EdgeInfo::Left);
+        graph.add_edge(diff, masked, EdgeInfo::Right);
+
+        let result = graph.add_node(NodeInfo::new(Operation::Add));
+        graph.add_edge(b, result, EdgeInfo::Left);
+        graph.add_edge(masked, result, EdgeInfo::Right);
+
+        let children = graph
+            .edges_directed(id, Direction::Outgoing)
+            .map(|e| (e.target(), *e.weight()))
+            .collect::<Vec<_>>();
+
+        for (child, edge) in children {
+            graph.add_edge(result, child, edge);
+        }
+
+        graph.remove_node(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+
+    #[test]
+    fn lowers_select() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let c = ir.add_input_ciphertext(0);
+        let a = ir.add_input_ciphertext(1);
+        let b = ir.add_input_ciphertext(2);
+
+        let select = ir.add_node(Operation::Select);
+
+        for (i, x) in [c, a, b].iter().enumerate() {
+            ir.add_edge(*x, select, EdgeInfo::Ordered(i));
+        }
+
+        let output = ir.add_output_ciphertext(select);
+
+        apply_lower_selects(&mut ir);
+
+        assert!(ir.validate().is_ok());
+        assert_eq!(ir.graph.node_count(), 7);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        let sum = query.get_unary_operand(output).unwrap();
+        assert_eq!(ir.graph[sum].operation, Operation::Add);
+
+        let (left, masked) = query.get_binary_operands(sum).unwrap();
+        assert_eq!(left, b);
+        assert_eq!(ir.graph[masked].operation, Operation::Multiply);
+
+        let (cond, diff) = query.get_binary_operands(masked).unwrap();
+        assert_eq!(cond, c);
+        assert_eq!(query.get_binary_operands(diff).unwrap(), (a, b));
+    }
+}
@@ -1,11 +1,19 @@
+mod insert_mod_switches;
 mod insert_relinearizations;
+mod lower_comparisons;
+mod lower_selects;
 
 use petgraph::stable_graph::NodeIndex;
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait};
 
+pub use insert_mod_switches::apply_insert_mod_switches;
 use insert_relinearizations::apply_insert_relinearizations;
+use lower_comparisons::apply_lower_comparisons;
+use lower_selects::apply_lower_selects;
 
 pub fn transform_intermediate_representation(ir: &mut FheProgram) {
+    apply_lower_comparisons(ir);
+    apply_lower_selects(ir);
     apply_insert_relinearizations(ir);
 
     // Dead code elimination.
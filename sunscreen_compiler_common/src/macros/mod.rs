@@ -145,19 +145,17 @@ pub fn extract_fn_arguments(
 #[derive(Debug)]
 /**
  * Errors that can occur when extracting the return value of an FHE
- * program.
+ * or ZKP program.
  */
 pub enum ExtractReturnTypesError {
     /**
      * The given return type is not allowed.
      *
      * # Remarks
-     * ZKP programs don't return values.
-     *
-     * FHE programs must return either
-     * * nothing (weird, but legal).
-     * * a single FHE type.
-     * * a tuple of FHE types.
+     * FHE and ZKP programs must return either
+     * * nothing.
+     * * a single FHE or ZKP type.
+     * * a tuple of FHE or ZKP types.
      *
      */
     IllegalType(Span),
@@ -217,7 +215,7 @@ pub fn pack_return_type(return_types: &[Type]) -> Type {
 
 /**
  * Emits code to create output nodes for each returned value in an
- * FHE program.
+ * FHE or ZKP program.
  */
 pub fn emit_output_capture(return_types: &[Type]) -> TokenStream2 {
     match return_types.len() {
@@ -223,6 +223,7 @@ impl Parse for FheProgramAttrs {
 
 pub enum BackendType {
     Bulletproofs,
+    Groth16,
 }
 
 impl TryFrom<&AttrValue> for BackendType {
@@ -233,6 +234,7 @@ impl TryFrom<&AttrValue> for BackendType {
 
         match as_str {
             "bulletproofs" => Ok(BackendType::Bulletproofs),
+            "groth16" => Ok(BackendType::Groth16),
             _ => Err(SynError::new(
                 value.span(),
                 format!("Unknown backend `{}`", as_str.to_owned()),
@@ -241,9 +243,8 @@ impl TryFrom<&AttrValue> for BackendType {
     }
 }
 
-#[allow(unused)]
 pub struct ZkpProgramAttrs {
-    backend_type: BackendType,
+    pub backend_type: BackendType,
 }
 
 impl Parse for ZkpProgramAttrs {
@@ -69,7 +69,14 @@ pub fn fhe_program(
 
 #[proc_macro_attribute]
 /**
- * Specifies a function to be a ZKP program. TODO: docs.
+ * Specifies a function to be a ZKP program.
+ *
+ * # Remarks
+ * The required `backend` argument, either `"bulletproofs"` or `"groth16"`,
+ * selects the backend the program compiles for. The program's generic
+ * argument becomes that backend's field, so compiling the program with a
+ * different backend fails to type check. `"groth16"` requires sunscreen's
+ * `groth16` feature.
  */
 pub fn zkp_program(
     metadata: proc_macro::TokenStream,
@@ -1,15 +1,15 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use sunscreen_compiler_common::macros::{
-    create_program_node, emit_signature, extract_fn_arguments, ExtractFnArgumentsError,
-};
-use syn::{
-    parse_macro_input, spanned::Spanned, Generics, ItemFn, Path, ReturnType, Type, TypeParamBound,
+    create_program_node, emit_output_capture, emit_signature, extract_fn_arguments,
+    extract_return_types, lift_type, normalize_type_generic_args, pack_return_type,
+    ExtractFnArgumentsError, ExtractReturnTypesError, ProgramTypeError,
 };
+use syn::{parse_macro_input, spanned::Spanned, Generics, ItemFn, Path, Type, TypeParamBound};
 
 use crate::{
     error::{Error, Result},
-    internals::attr::ZkpProgramAttrs,
+    internals::attr::{BackendType, ZkpProgramAttrs},
 };
 
 enum ArgumentKind {
@@ -72,7 +72,7 @@ fn get_generic_arg(generics: &Generics) -> Result<(Ident, Path)> {
     Ok((generic.ident.clone(), bound.path))
 }
 
-fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenStream> {
+fn parse_inner(attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenStream> {
     let zkp_program_name = &input_fn.sig.ident;
     let vis = &input_fn.vis;
     let body = &input_fn.block;
@@ -81,16 +81,6 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
 
     let (generic_ident, generic_bound) = get_generic_arg(&input_fn.sig.generics)?;
 
-    match ret {
-        ReturnType::Default => {}
-        _ => {
-            return Err(Error::compile_error(
-                ret.span(),
-                "ZKP programs may not return values.",
-            ));
-        }
-    };
-
     let unwrapped_inputs = match extract_fn_arguments(inputs) {
         Ok(args) => {
             args.iter().map(|a| {
@@ -127,8 +117,51 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
         .map(|(_, t, _)| (**t).clone())
         .collect::<Vec<Type>>();
 
+    let return_types = match extract_return_types(ret) {
+        Ok(v) => v,
+        Err(ExtractReturnTypesError::IllegalType(s)) => {
+            return Err(Error::compile_error(s, "ZKP programs may return a single value or a tuple of values. Each type must be a ZKP type or array of such."));
+        }
+    };
+
+    let output_capture = emit_output_capture(&return_types);
+
+    let zkp_program_returns = match return_types
+        .iter()
+        .map(lift_type)
+        .collect::<std::result::Result<Vec<Type>, ProgramTypeError>>()
+    {
+        Ok(v) => v,
+        Err(ProgramTypeError::IllegalType(s)) => {
+            return Err(Error::compile_error(
+                s,
+                "Each return type for a ZKP program must be either an array or named struct type.",
+            ));
+        }
+    };
+
+    // Programs without outputs conventionally end with a `constrain_eq`
+    // expression, whose value we discard.
+    let zkp_program_return = if zkp_program_returns.is_empty() {
+        quote! {}
+    } else {
+        let ret = pack_return_type(&zkp_program_returns);
+
+        quote! { -> #ret }
+    };
+
+    // emit_signature needs FHE-only information about return types, so we
+    // fill in the return types ourselves.
     let signature = emit_signature(&argument_types, &[]);
 
+    let return_type_names = return_types.iter().map(|x| {
+        let x = normalize_type_generic_args(x);
+
+        quote! {
+            <#x as sunscreen::types::TypeName>::type_name(),
+        }
+    });
+
     let var_decl = unwrapped_inputs.iter().map(|t| {
         let input_type = match t.0 {
             ArgumentKind::Private => "private_input",
@@ -139,6 +172,24 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
         create_program_node(&t.2.to_string(), t.1, input_type)
     });
 
+    // The program's generic argument becomes the field of the backend named
+    // in the attribute, so compiling it with another backend fails to
+    // type check.
+    let backend = match attr_params.backend_type {
+        BackendType::Bulletproofs => quote! { sunscreen::BulletproofsBackend },
+        BackendType::Groth16 => quote! { sunscreen::Groth16Backend },
+    };
+
+    let field = quote! { <#backend as sunscreen::ZkpBackend>::Field };
+
+    let field_alias = quote! {
+        #[allow(non_camel_case_types, dead_code)]
+        type #generic_ident = #field;
+
+        fn assert_field_bound<T: #generic_bound>() {}
+        assert_field_bound::<#generic_ident>();
+    };
+
     let zkp_program_struct_name =
         Ident::new(&format!("{}_struct", zkp_program_name), Span::call_site());
 
@@ -155,11 +206,13 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
             }
         }
 
-        impl <#generic_ident: #generic_bound> sunscreen::ZkpProgramFn<#generic_ident> for #zkp_program_struct_name {
+        impl sunscreen::ZkpProgramFn<#field> for #zkp_program_struct_name {
             fn build(&self) -> sunscreen::Result<sunscreen::ZkpFrontendCompilation> {
+                #field_alias
+
                 use std::cell::RefCell;
                 use std::mem::transmute;
-                use sunscreen::{CURRENT_ZKP_CTX, ZkpContext, ZkpData, Error, INDEX_ARENA, Result, types::{zkp::{ProgramNode, CreateZkpProgramInput, ConstrainEq, IntoProgramNode}, TypeName}};
+                use sunscreen::{CURRENT_ZKP_CTX, ZkpContext, ZkpData, Error, INDEX_ARENA, Result, types::{zkp::{ProgramNode, CreateZkpProgramInput, ConstrainEq, IntoProgramNode, Output}, TypeName}};
 
                 let mut context = ZkpContext::new(ZkpData::new());
 
@@ -171,7 +224,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
 
                     #[allow(clippy::type_complexity)]
                     #[forbid(unused_variables)]
-                    let panic_res = std::panic::catch_unwind(|| {
+                    let panic_res = std::panic::catch_unwind(|| #zkp_program_return {
                         #(#var_decl)*
                         #body
                     });
@@ -179,7 +232,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
                     // when panicing or not, we need to clear our indicies arena and
                     // unset the context reference.
                     match panic_res {
-                        Ok(v) => { },
+                        Ok(v) => { #output_capture },
                         Err(err) => {
                             INDEX_ARENA.with(|allocator| {
                                 allocator.borrow_mut().reset()
@@ -203,7 +256,12 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
             }
 
             fn signature(&self) -> sunscreen::CallSignature {
-                #signature
+                #field_alias
+
+                let mut signature = { #signature };
+                signature.returns = vec![#(#return_type_names)*];
+
+                signature
             }
         }
 
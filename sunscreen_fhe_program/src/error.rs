@@ -111,6 +111,12 @@ pub enum NodeError {
      * but got some other number (second argument).
      */
     WrongOperandCount(Box<(usize, usize)>),
+
+    /**
+     * The node's operation only appears in uncompiled FHE programs and the
+     * backend compiler should have lowered it.
+     */
+    NotLowered,
 }
 
 impl std::fmt::Display for NodeError {
@@ -136,6 +142,9 @@ impl std::fmt::Display for NodeError {
                     x.0, x.1
                 )
             }
+            Self::NotLowered => {
+                write!(f, "This operation should have been lowered during compilation")
+            }
         }
     }
 }
@@ -24,13 +24,17 @@ pub use literal::*;
 pub use operation::*;
 pub use seal_fhe::SecurityLevel;
 
-use sunscreen_compiler_common::{CompilationResult, Context, EdgeInfo, NodeInfo};
+use sunscreen_compiler_common::{CompilationResult, Context, EdgeInfo, GraphQuery, NodeInfo};
 
 use std::collections::HashSet;
 
 #[derive(Debug, Clone, Copy, Serialize, Hash, Deserialize, PartialEq, Eq)]
 /**
  * Sunscreen supports the BFV scheme.
+ *
+ * # Remarks
+ * CKKS is not a supported scheme type. SEAL's CKKS implementation is only
+ * available through the low-level `seal_fhe` bindings.
  */
 pub enum SchemeType {
     /**
@@ -214,6 +218,12 @@ pub trait FheProgramTrait {
      */
     fn add_relinearize(&mut self, x: NodeIndex) -> NodeIndex;
 
+    /**
+     * Appends an operation that switches `x` to the next set of parameters
+     * in the modulus switching chain.
+     */
+    fn add_mod_switch(&mut self, x: NodeIndex) -> NodeIndex;
+
     /**
      * Appends an operation that rotates ciphertext `x` left by the literal node at `y` places.
      *
@@ -267,6 +277,13 @@ pub trait FheProgramTrait {
      * operations.
      */
     fn requires_galois_keys(&self) -> bool;
+
+    /**
+     * The sorted, deduplicated rotation steps this FHE program performs, in the form
+     * SEAL's `create_galois_keys` expects. Positive steps rotate left, negative steps
+     * rotate right, and 0 denotes a row swap.
+     */
+    fn galois_steps(&self) -> Vec<i32>;
 }
 
 impl FheProgramTrait for FheProgram {
@@ -310,6 +327,10 @@ impl FheProgramTrait for FheProgram {
         self.add_unary_operation(Operation::Relinearize, x)
     }
 
+    fn add_mod_switch(&mut self, x: NodeIndex) -> NodeIndex {
+        self.add_unary_operation(Operation::ModSwitch, x)
+    }
+
     fn add_rotate_left(&mut self, x: NodeIndex, y: NodeIndex) -> NodeIndex {
         self.add_binary_operation(Operation::ShiftLeft, x, y)
     }
@@ -411,6 +432,35 @@ impl FheProgramTrait for FheProgram {
             )
         })
     }
+
+    fn galois_steps(&self) -> Vec<i32> {
+        let query = GraphQuery::new(&self.graph.0);
+
+        let amount = |i: NodeIndex| {
+            let (_, right) = query.get_binary_operands(i).ok()?;
+
+            match self.graph[right].operation {
+                Operation::Literal(Literal::U64(v)) => Some(v as i32),
+                _ => None,
+            }
+        };
+
+        let mut steps = self
+            .graph
+            .node_indices()
+            .filter_map(|i| match self.graph[i].operation {
+                Operation::ShiftLeft => amount(i),
+                Operation::ShiftRight => amount(i).map(|x| -x),
+                Operation::SwapRows => Some(0),
+                _ => None,
+            })
+            .collect::<Vec<i32>>();
+
+        steps.sort_unstable();
+        steps.dedup();
+
+        steps
+    }
 }
 
 #[cfg(test)]
@@ -527,6 +577,21 @@ mod tests {
         assert!(eq(&pruned, &expected_ir));
     }
 
+    #[test]
+    fn galois_steps_are_sorted_and_deduplicated() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let ct = ir.add_input_ciphertext(0);
+        let three = ir.add_input_literal(Literal::U64(3));
+        let one = ir.add_input_literal(Literal::U64(1));
+        let r1 = ir.add_rotate_left(ct, three);
+        let r2 = ir.append_rotate_right(r1, one);
+        let r3 = ir.add_rotate_left(r2, three);
+        ir.add_output_ciphertext(r3);
+
+        assert_eq!(ir.galois_steps(), vec![-1, 3]);
+    }
+
     #[test]
     fn can_roundtrip_scheme_type() {
         let schemes = [SchemeType::Bfv];
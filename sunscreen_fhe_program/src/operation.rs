@@ -35,6 +35,17 @@ pub enum Operation {
      */
     Relinearize,
 
+    /**
+     * Switches a ciphertext to the next set of parameters in the modulus
+     * switching chain, dropping the last prime from its coefficient modulus.
+     * This shrinks the ciphertext and speeds up later operations at the cost
+     * of some noise budget.
+     *
+     * All ciphertext operands to a binary operation must be at the same
+     * level in the modulus switching chain.
+     */
+    ModSwitch,
+
     /**
      * Multiply two ciphertext values.
      */
@@ -70,6 +81,50 @@ pub enum Operation {
      */
     SubPlaintext,
 
+    /**
+     * Compares two integers whose bits are encrypted in separate
+     * ciphertexts, producing an encrypted 1 if the first is less than the
+     * second and 0 otherwise.
+     *
+     * # Remarks
+     * For `n`-bit integers, this operation has `2n` ordered operands: the
+     * bits of the first integer followed by the bits of the second, each
+     * least significant bit first. When `signed` is true, the most
+     * significant bit is a two's complement sign bit.
+     *
+     * The backend compiler lowers this operation into multiplications and
+     * additions before running any other transforms, so compiled FHE
+     * programs never contain it.
+     */
+    LessThan {
+        /**
+         * Whether the integers are two's complement.
+         */
+        signed: bool,
+    },
+
+    /**
+     * Compares two integers whose bits are encrypted in separate
+     * ciphertexts, producing an encrypted 1 if they differ and 0 otherwise.
+     *
+     * # Remarks
+     * The operands are laid out as in [`Operation::LessThan`] and the
+     * backend compiler likewise lowers this operation.
+     */
+    NotEqual,
+
+    /**
+     * Selects between two ciphertexts. Has 3 ordered operands: an
+     * encrypted condition `c`, followed by `a` and `b`. When `c` encrypts 1,
+     * the result is `a`, and when it encrypts 0, the result is `b`.
+     *
+     * # Remarks
+     * The backend compiler lowers this operation into `b + c * (a - b)`
+     * before running any other transforms, so compiled FHE programs never
+     * contain it.
+     */
+    Select,
+
     /**
      * Represents an input ciphertext for the FHE program.
      */
@@ -122,7 +177,11 @@ impl OperationTrait for Operation {
     fn is_unary(&self) -> bool {
         matches!(
             self,
-            Self::Negate | Self::Relinearize | Self::SwapRows | Self::OutputCiphertext
+            Self::Negate
+                | Self::Relinearize
+                | Self::ModSwitch
+                | Self::SwapRows
+                | Self::OutputCiphertext
         )
     }
 
@@ -131,6 +190,6 @@ impl OperationTrait for Operation {
     }
 
     fn is_ordered(&self) -> bool {
-        false
+        matches!(self, Self::LessThan { .. } | Self::NotEqual | Self::Select)
     }
 }
@@ -71,8 +71,10 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
             InputPlaintext(_) => None,
             OutputCiphertext => Some(validate_unary_op_has_correct_operands(ir, i)),
             Relinearize => Some(validate_unary_op_has_correct_operands(ir, i)),
+            ModSwitch => Some(validate_unary_op_has_correct_operands(ir, i)),
             Literal(_) => None,
             SwapRows => None,
+            LessThan { .. } | NotEqual | Select => Some(vec![NodeError::NotLowered]),
         };
 
         if let Some(node_errors) = node_errors {
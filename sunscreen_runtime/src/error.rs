@@ -121,6 +121,94 @@ pub enum Error {
      */
     #[error("ZKP error: {0}")]
     ZkpError(#[from] ZkpError),
+
+    /**
+     * The type requested from a ZKP program's outputs decodes from a
+     * different number of native field elements than the program returned.
+     */
+    #[error("Expected {expected} ZKP program output fields, found {actual}")]
+    ZkpOutputMismatch {
+        /**
+         * The number of native field elements the requested type decodes
+         * from.
+         */
+        expected: usize,
+
+        /**
+         * The number of native field elements the program returned.
+         */
+        actual: usize,
+    },
+
+    /**
+     * A ZKP program output isn't a valid value of the requested type.
+     */
+    #[error("ZKP type decoding error: {0}")]
+    ZkpTypeError(Box<String>),
+
+    /**
+     * The data isn't in Sunscreen's wire format (e.g. it's truncated or
+     * has the wrong magic bytes).
+     */
+    #[error("Data is not in the Sunscreen wire format")]
+    MalformedWireData,
+
+    /**
+     * The data was encoded with a wire format version this release can't
+     * read.
+     */
+    #[error(
+        "Wire format version {0} is unsupported. This release reads versions {}..={}",
+        crate::MIN_WIRE_FORMAT_VERSION,
+        crate::WIRE_FORMAT_VERSION
+    )]
+    UnsupportedWireVersion(u16),
+
+    /**
+     * The data encodes a different kind of artifact than the one requested.
+     */
+    #[error("Expected a {expected} in wire format, found artifact kind {actual}")]
+    WireKindMismatch {
+        /**
+         * The kind of artifact the caller tried to decode.
+         */
+        expected: crate::ArtifactKind,
+
+        /**
+         * The artifact kind tag found in the data.
+         */
+        actual: u8,
+    },
+
+    /**
+     * The data contains a proof from a proof system this build can't
+     * decode, either because it's unknown to this release or because its
+     * ZKP backend feature is disabled.
+     */
+    #[error("Proof system with tag {0} is unsupported")]
+    UnsupportedProofSystem(u8),
+
+    /**
+     * The threshold parameters are invalid (e.g. the threshold exceeds the
+     * number of parties), or a share was created under different
+     * parameters or for a party that doesn't exist.
+     */
+    #[error("Invalid threshold parameters")]
+    InvalidThresholdParams,
+
+    /**
+     * Combining key shares requires a share from every party, but some
+     * were missing.
+     */
+    #[error("Missing key shares from some parties")]
+    MissingKeyShares,
+
+    /**
+     * Threshold decryption requires a partial decryption from each of at
+     * least `threshold` parties.
+     */
+    #[error("Not enough partial decryptions")]
+    InsufficientPartialDecryptions,
 }
 
 const_assert!(std::mem::size_of::<Error>() <= 24);
@@ -147,6 +235,13 @@ impl Error {
         Self::FheTypeError(Box::new(msg.to_owned()))
     }
 
+    /**
+     * Create an [`Error::ZkpTypeError`].
+     */
+    pub fn zkp_type_error(msg: &str) -> Self {
+        Self::ZkpTypeError(Box::new(msg.to_owned()))
+    }
+
     fn unwrap_argument_mismatch_data(&self) -> &(Vec<Type>, Vec<Type>) {
         match self {
             Self::ArgumentMismatch(d) => d,
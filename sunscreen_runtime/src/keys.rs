@@ -1,4 +1,4 @@
-use crate::serialization::WithContext;
+use crate::serialization::{CompactWithContext, WithContext};
 
 use seal_fhe::{
     GaloisKeys, PublicKey as SealPublicKey, RelinearizationKeys, SecretKey as SealSecretKey,
@@ -46,6 +46,25 @@ pub struct PublicKey {
     pub relin_key: Option<WithContext<RelinearizationKeys>>,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename = "PublicKey")]
+/**
+ * A bundle of public keys in a compact form suitable for uploading over
+ * slow links. See
+ * [`generate_compact_keys`](crate::GenericRuntime::generate_compact_keys).
+ *
+ * # Remarks
+ * Each key stores a random number seed in place of half its data, so
+ * these keys can't be used directly. Instead, serialize them and
+ * deserialize the result as a [`PublicKey`], which expands the seeds
+ * into the full keys.
+ */
+pub struct CompactPublicKey {
+    pub(crate) public_key: CompactWithContext,
+    pub(crate) galois_key: Option<CompactWithContext>,
+    pub(crate) relin_key: Option<CompactWithContext>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 /**
  * The private key used to decrypt ciphertexts.
@@ -188,4 +207,49 @@ mod tests {
         );
         assert_eq!(private_key.0.as_bytes(), private_2.0.as_bytes());
     }
+
+    #[test]
+    fn can_roundtrip_compact_keys() {
+        let runtime = Runtime::new_fhe(&Params {
+            lattice_dimension: 8192,
+            security_level: SecurityLevel::TC128,
+            plain_modulus: PlainModulus::batching(8192, 20).unwrap().value(),
+            scheme_type: SchemeType::Bfv,
+            coeff_modulus: CoefficientModulus::bfv_default(8192, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+        })
+        .unwrap();
+
+        let (full_key, _) = runtime.generate_keys().unwrap();
+        let (compact_key, _) = runtime.generate_compact_keys().unwrap();
+
+        let full_data = bincode::serialize(&full_key).unwrap();
+        let compact_data = bincode::serialize(&compact_key).unwrap();
+
+        assert!(compact_data.len() < full_data.len());
+
+        let public_key: PublicKey = bincode::deserialize(&compact_data).unwrap();
+
+        assert!(public_key.galois_key.is_some());
+        assert!(public_key.relin_key.is_some());
+
+        let public_2: PublicKey =
+            serde_json::from_str(&serde_json::to_string(&compact_key).unwrap()).unwrap();
+
+        assert_eq!(
+            public_key.public_key.data.as_bytes(),
+            public_2.public_key.data.as_bytes()
+        );
+        assert_eq!(
+            public_key.relin_key.unwrap().data.as_bytes(),
+            public_2.relin_key.unwrap().data.as_bytes()
+        );
+        assert_eq!(
+            public_key.galois_key.unwrap().data.as_bytes(),
+            public_2.galois_key.unwrap().data.as_bytes()
+        );
+    }
 }
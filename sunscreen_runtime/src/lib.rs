@@ -11,6 +11,8 @@ mod metadata;
 mod run;
 mod runtime;
 mod serialization;
+mod threshold;
+mod wire;
 
 use std::sync::Arc;
 
@@ -19,11 +21,19 @@ pub use crate::keys::*;
 pub use crate::metadata::*;
 pub use run::*;
 pub use runtime::*;
+pub(crate) use serialization::CompactWithContext;
 pub use serialization::WithContext;
+pub use threshold::{
+    KeyShare, PartialDecryption, PrivateKeyFragment, PublicKeyShare, RelinKeyShare,
+    ThresholdConfig, ThresholdPrivateKey, SMUDGING_SECURITY_BITS,
+};
+pub use wire::*;
+
+pub use logproof::LogProof;
 
 use seal_fhe::{Ciphertext as SealCiphertext, Plaintext as SealPlaintext};
 use serde::{Deserialize, Serialize};
-use sunscreen_zkp_backend::BigInt;
+use sunscreen_zkp_backend::{BigInt, Proof};
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, Eq)]
 /**
@@ -178,6 +188,29 @@ pub struct Ciphertext {
     pub inner: InnerCiphertext,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename = "Ciphertext")]
+/**
+ * A compact symmetric-key encryption of the given data type. See
+ * [`encrypt_symmetric_compact`](crate::GenericRuntime::encrypt_symmetric_compact).
+ *
+ * # Remarks
+ * This type can't be used in FHE programs. Its serialized form matches
+ * that of a [`Ciphertext`], except each SEAL ciphertext stores a seed in
+ * place of one of its polynomials. Deserializing it as a [`Ciphertext`]
+ * expands the seed into the full polynomial.
+ */
+pub struct CompactCiphertext {
+    pub(crate) data_type: Type,
+    pub(crate) inner: CompactInnerCiphertext,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename = "InnerCiphertext")]
+pub(crate) enum CompactInnerCiphertext {
+    Seal(Vec<CompactWithContext>),
+}
+
 /**
  * A trait that denotes this type can be used as an
  * argument to an FHE program.
@@ -199,6 +232,79 @@ pub enum FheProgramInput {
     Plaintext(Box<dyn FheProgramInputTrait>),
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * A proof that a ZKP program's constraints are satisfied, along with the
+ * program's public outputs.
+ */
+pub struct ZkpProof {
+    proof: Proof,
+    outputs: Vec<BigInt>,
+}
+
+impl ZkpProof {
+    /**
+     * Creates a [`ZkpProof`] from a backend proof and the outputs the
+     * prover claims the ZKP program returned.
+     */
+    pub fn new(proof: Proof, outputs: Vec<BigInt>) -> Self {
+        Self { proof, outputs }
+    }
+
+    /**
+     * The backend's proof.
+     */
+    pub fn proof(&self) -> &Proof {
+        &self.proof
+    }
+
+    /**
+     * The public outputs the prover claims the ZKP program returned, as
+     * native field elements.
+     *
+     * # Remarks
+     * These values are unverified. Use the ones returned by
+     * [`verify`](crate::GenericRuntime::verify) instead.
+     */
+    pub fn outputs(&self) -> &[BigInt] {
+        &self.outputs
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * The verified values a ZKP program returned. See
+ * [`verify`](crate::GenericRuntime::verify).
+ */
+pub struct ZkpOutputs(Vec<BigInt>);
+
+impl ZkpOutputs {
+    pub(crate) fn new(outputs: Vec<BigInt>) -> Self {
+        Self(outputs)
+    }
+
+    /**
+     * Decodes the outputs as the ZKP program's return type `T`.
+     *
+     * # Remarks
+     * Returns [`Error::ZkpOutputMismatch`] if `T` doesn't decode from
+     * exactly as many native field elements as the program returned.
+     */
+    pub fn get<T>(&self) -> Result<T>
+    where
+        T: TryFromNativeFields,
+    {
+        T::try_from_native_fields(&self.0)
+    }
+
+    /**
+     * The outputs as native field elements.
+     */
+    pub fn as_native_fields(&self) -> &[BigInt] {
+        &self.0
+    }
+}
+
 /**
  * Denotes this type can be used as an input to a ZKP
  * program.
@@ -278,6 +384,113 @@ where
     }
 }
 
+/**
+ * A trait for converting the native field elements a ZKP program returns
+ * back into values.
+ */
+pub trait TryFromNativeFields
+where
+    Self: Sized,
+{
+    /**
+     * The number of native field elements this type decodes from.
+     */
+    fn num_native_fields() -> usize;
+
+    /**
+     * Attempts to turn native field elements into `Self`.
+     *
+     * # Remarks
+     * Implementations should return [`Error::ZkpOutputMismatch`] unless
+     * `fields` contains exactly
+     * [`num_native_fields`](Self::num_native_fields) elements.
+     */
+    fn try_from_native_fields(fields: &[BigInt]) -> Result<Self>;
+}
+
+impl TryFromNativeFields for () {
+    fn num_native_fields() -> usize {
+        0
+    }
+
+    fn try_from_native_fields(fields: &[BigInt]) -> Result<Self> {
+        check_native_field_count::<Self>(fields)
+    }
+}
+
+impl<T, const N: usize> TryFromNativeFields for [T; N]
+where
+    T: TryFromNativeFields,
+{
+    fn num_native_fields() -> usize {
+        T::num_native_fields() * N
+    }
+
+    fn try_from_native_fields(fields: &[BigInt]) -> Result<Self> {
+        check_native_field_count::<Self>(fields)?;
+
+        let len = T::num_native_fields();
+
+        let items = (0..N)
+            .map(|i| T::try_from_native_fields(&fields[i * len..(i + 1) * len]))
+            .collect::<Result<Vec<T>>>()?;
+
+        // The check above guarantees there are N items.
+        Ok(items.try_into().ok().unwrap())
+    }
+}
+
+fn check_native_field_count<T>(fields: &[BigInt]) -> Result<()>
+where
+    T: TryFromNativeFields,
+{
+    if fields.len() == T::num_native_fields() {
+        Ok(())
+    } else {
+        Err(Error::ZkpOutputMismatch {
+            expected: T::num_native_fields(),
+            actual: fields.len(),
+        })
+    }
+}
+
+macro_rules! impl_try_from_native_fields_tuple {
+    ($($t:ident),+) => {
+        impl<$($t),+> TryFromNativeFields for ($($t,)+)
+        where
+            $($t: TryFromNativeFields),+
+        {
+            fn num_native_fields() -> usize {
+                0 $(+ $t::num_native_fields())+
+            }
+
+            #[allow(non_snake_case, unused_assignments)]
+            fn try_from_native_fields(fields: &[BigInt]) -> Result<Self> {
+                check_native_field_count::<Self>(fields)?;
+
+                let mut fields = fields;
+
+                $(
+                    let (head, tail) = fields.split_at($t::num_native_fields());
+                    let $t = $t::try_from_native_fields(head)?;
+                    fields = tail;
+                )+
+
+                Ok(($($t,)+))
+            }
+        }
+    };
+}
+
+impl_try_from_native_fields_tuple!(A);
+impl_try_from_native_fields_tuple!(A, B);
+impl_try_from_native_fields_tuple!(A, B, C);
+impl_try_from_native_fields_tuple!(A, B, C, D);
+impl_try_from_native_fields_tuple!(A, B, C, D, E);
+impl_try_from_native_fields_tuple!(A, B, C, D, E, G);
+impl_try_from_native_fields_tuple!(A, B, C, D, E, G, H);
+impl_try_from_native_fields_tuple!(A, B, C, D, E, G, H, I);
+
 /**
  * This trait specifies one may attempt to convert a plaintext into this type.
  */
@@ -127,7 +127,9 @@ impl Params {
         let (security_level, rest) = Self::read_i32(rest)?;
         let security_level: SecurityLevel = security_level.try_into()?;
 
-        let coeff_modulus: Vec<u64> = rlp::decode_list(rest);
+        let coeff_modulus: Vec<u64> = rlp::Rlp::new(rest)
+            .as_list()
+            .map_err(|_| Error::ParamDeserializationError)?;
 
         Ok(Self {
             lattice_dimension,
@@ -139,6 +141,10 @@ impl Params {
     }
 
     fn read_u64(bytes: &[u8]) -> Result<(u64, &[u8])> {
+        if bytes.len() < std::mem::size_of::<u64>() {
+            return Err(Error::ParamDeserializationError);
+        }
+
         let (int_bytes, rest) = bytes.split_at(std::mem::size_of::<u64>());
         let val = u64::from_be_bytes(
             int_bytes
@@ -150,6 +156,10 @@ impl Params {
     }
 
     fn read_i32(bytes: &[u8]) -> Result<(i32, &[u8])> {
+        if bytes.len() < std::mem::size_of::<i32>() {
+            return Err(Error::ParamDeserializationError);
+        }
+
         let (int_bytes, rest) = bytes.split_at(std::mem::size_of::<i32>());
         let val = i32::from_be_bytes(
             int_bytes
@@ -161,6 +171,10 @@ impl Params {
     }
 
     fn read_u8(bytes: &[u8]) -> Result<(u8, &[u8])> {
+        if bytes.len() < std::mem::size_of::<u8>() {
+            return Err(Error::ParamDeserializationError);
+        }
+
         let (int_bytes, rest) = bytes.split_at(std::mem::size_of::<u8>());
         let val = u8::from_be_bytes(
             int_bytes
@@ -191,6 +205,15 @@ pub struct FheProgramMetadata {
      * The set of keys required to run the FHE program.
      */
     pub required_keys: Vec<RequiredKeys>,
+
+    /**
+     * The rotation steps the FHE program performs. If the program requires
+     * [`RequiredKeys::Galois`], these are the only steps for which
+     * [`generate_keys_for`](crate::GenericRuntime::generate_keys_for) creates
+     * Galois keys.
+     */
+    #[serde(default)]
+    pub galois_steps: Vec<i32>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
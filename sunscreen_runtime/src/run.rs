@@ -7,11 +7,16 @@ use crossbeam::atomic::AtomicCell;
 use petgraph::{stable_graph::NodeIndex, Direction};
 
 use std::borrow::Cow;
-#[cfg(target_arch = "wasm32")]
+#[cfg(not(target_arch = "wasm32"))]
+use std::cmp::Reverse;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::BinaryHeap;
 use std::collections::VecDeque;
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
 
 use seal_fhe::{
     Ciphertext, Error as SealError, Evaluator, GaloisKeys, Plaintext, RelinearizationKeys,
@@ -66,6 +71,13 @@ pub enum FheProgramRunFailure {
     #[error("Internal error: missing data")]
     MissingData,
 
+    /**
+     * The FHE program contains an operation the backend compiler should
+     * have lowered.
+     */
+    #[error("Encountered an operation that should have been lowered during compilation")]
+    NotLowered,
+
     /**
      * An error occurred when trying to query the graph.
      */
@@ -81,10 +93,94 @@ impl From<SealError> for FheProgramRunFailure {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * The order in which an FHE program's operations get executed. See
+ * [`RunOptions`].
+ */
+pub enum Scheduling {
+    /**
+     * Run each operation in parallel as soon as its operands are ready.
+     */
+    Eager,
+
+    /**
+     * Run operations in parallel, but when more operations are ready than
+     * there are threads, prefer those with the most expensive chain of
+     * dependent operations after them. This tends to reduce latency for
+     * deep programs whose width exceeds the number of threads.
+     */
+    CriticalPathFirst,
+
+    /**
+     * Run operations one at a time on the calling thread in a fixed
+     * topological order. Every run executes operations in the same order,
+     * which makes debugging easier.
+     */
+    Sequential,
+}
+
+impl Default for Scheduling {
+    fn default() -> Self {
+        Self::Eager
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/**
+ * Controls how [`run_program_unchecked_with_options`] executes an FHE
+ * program.
+ */
+pub struct RunOptions {
+    /**
+     * The thread pool on which to run operations. When [`None`], uses
+     * rayon's global thread pool.
+     *
+     * # Remarks
+     * Ignored when [`scheduling`](Self::scheduling) is
+     * [`Scheduling::Sequential`] and on targets without threads (e.g.
+     * wasm32).
+     */
+    pub thread_pool: Option<Arc<rayon::ThreadPool>>,
+
+    /**
+     * The order in which to run operations.
+     */
+    pub scheduling: Scheduling,
+}
+
+/**
+ * You probably should instead use [`Runtime::run()`](crate::Runtime::run).
+ *
+ * Run the given [`FheProgram`] to completion with the given inputs using
+ * the default [`RunOptions`]. See [`run_program_unchecked_with_options`].
+ *
+ * # Safety
+ * Calling this method on a malformed [`FheProgram`] may
+ * result in panics, non-termination, or undefined behavior.
+ */
+pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
+    ir: &FheProgram,
+    inputs: &[SealData],
+    evaluator: &E,
+    relin_keys: &Option<&RelinearizationKeys>,
+    galois_keys: &Option<&GaloisKeys>,
+) -> Result<Vec<Ciphertext>, FheProgramRunFailure> {
+    run_program_unchecked_with_options(
+        ir,
+        inputs,
+        evaluator,
+        relin_keys,
+        galois_keys,
+        &RunOptions::default(),
+    )
+}
+
 /**
  * You probably should instead use [`Runtime::run()`](crate::Runtime::run).
  *
- * Run the given [`FheProgram`] to completion with the given inputs. This
+ * Run the given [`FheProgram`] to completion with the given inputs,
+ * scheduling its operations according to `options`. This
  * method performs no validation. You must verify the program is first valid. Programs produced
  * by the compiler are guaranteed to be valid, but deserialization does not make any such
  * guarantees. Call [`validate()`](sunscreen_fhe_program::FheProgramTrait::validate()) to verify a program's correctness.
@@ -97,12 +193,13 @@ impl From<SealError> for FheProgramRunFailure {
  * Calling this method on a malformed [`FheProgram`] may
  * result in panics, non-termination, or undefined behavior.
  */
-pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
+pub unsafe fn run_program_unchecked_with_options<E: Evaluator + Sync + Send>(
     ir: &FheProgram,
     inputs: &[SealData],
     evaluator: &E,
     relin_keys: &Option<&RelinearizationKeys>,
     galois_keys: &Option<&GaloisKeys>,
+    options: &RunOptions,
 ) -> Result<Vec<Ciphertext>, FheProgramRunFailure> {
     fn get_data(
         data: &[AtomicCell<Option<Arc<SealData>>>],
@@ -157,7 +254,7 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
         data.push(AtomicCell::new(None));
     }
 
-    traverse(
+    traverse_with_options(
         ir,
         |index| {
             let node = &ir.graph[index];
@@ -280,6 +377,15 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
 
                     data[index.index()].store(Some(Arc::new(c.into())));
                 }
+                ModSwitch => {
+                    let input = query.get_unary_operand(index)?;
+
+                    let x = get_ciphertext(&data, input.index())?;
+
+                    let y = evaluator.mod_switch_to_next(x)?;
+
+                    data[index.index()].store(Some(Arc::new(y.into())));
+                }
                 Negate => {
                     let x_id = query.get_unary_operand(index)?;
 
@@ -333,11 +439,15 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
 
                     data[index.index()].store(Some(a.clone()));
                 }
+                LessThan { .. } | NotEqual | Select => {
+                    return Err(FheProgramRunFailure::NotLowered);
+                }
             };
 
             Ok(())
         },
         None,
+        options,
     )?;
 
     // Attempt to copy ciphertexts to our output vector.
@@ -356,14 +466,14 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
     Ok(output)
 }
 
-#[cfg(not(target_arch = "wasm32"))]
 /**
  * Traverses the FheProgram's nodes in topological order, executing
  * callback on each node.
  *
  * # Remarks
- * This implementation executes in parallel and cannot mutate the graph
- * during traversal (as indicated by the lack of `mut` on `ir`).
+ * This implementation executes in parallel on rayon's global thread pool
+ * and cannot mutate the graph during traversal (as indicated by the lack
+ * of `mut` on `ir`). See [`traverse_with_options`].
  *
  * The optional `run_to` specifies to only run the given node and
  * its ancestors, topologically. If not specified, every node in the
@@ -374,6 +484,32 @@ pub fn traverse<F>(
     callback: F,
     run_to: Option<NodeIndex>,
 ) -> Result<(), FheProgramRunFailure>
+where
+    F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
+{
+    traverse_with_options(ir, callback, run_to, &RunOptions::default())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * Traverses the FheProgram's nodes in topological order, executing
+ * callback on each node.
+ *
+ * # Remarks
+ * The given [`RunOptions`] determine the thread pool and the order in
+ * which nodes get visited. Unless `options` specifies
+ * [`Scheduling::Sequential`], this implementation executes in parallel.
+ *
+ * The optional `run_to` specifies to only run the given node and
+ * its ancestors, topologically. If not specified, every node in the
+ * program gets visited.
+ */
+pub fn traverse_with_options<F>(
+    ir: &FheProgram,
+    callback: F,
+    run_to: Option<NodeIndex>,
+    options: &RunOptions,
+) -> Result<(), FheProgramRunFailure>
 where
     F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
 {
@@ -387,30 +523,99 @@ where
 
     let ir = ir.as_ref();
 
-    // Initialize the number of incomplete dependencies.
-    let deps = ir
-        .graph
+    let run = || match options.scheduling {
+        Scheduling::Eager => traverse_eager(ir, &callback),
+        Scheduling::CriticalPathFirst => traverse_critical_path_first(ir, &callback),
+        Scheduling::Sequential => traverse_sequential(ir, &callback),
+    };
+
+    match (&options.thread_pool, options.scheduling) {
+        (Some(pool), Scheduling::Eager | Scheduling::CriticalPathFirst) => pool.install(run),
+        _ => run(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+/**
+ * Traverses the FheProgram's nodes in topological order, executing
+ * callback on each node.
+ *
+ * # Remarks
+ * wasm32 has no threads, so this implementation always executes
+ * sequentially and ignores `options`.
+ *
+ * The optional `run_to` specifies to only run the given node and
+ * its ancestors, topologically. If not specified, every node in the
+ * program gets visited.
+ */
+pub fn traverse_with_options<F>(
+    ir: &FheProgram,
+    callback: F,
+    run_to: Option<NodeIndex>,
+    _options: &RunOptions,
+) -> Result<(), FheProgramRunFailure>
+where
+    F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
+{
+    use sunscreen_fhe_program::FheProgramTrait;
+
+    let ir = if let Some(x) = run_to {
+        Cow::Owned(ir.prune(&[x]))
+    } else {
+        Cow::Borrowed(ir)
+    };
+
+    traverse_sequential(ir.as_ref(), &callback)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * Returns the number of unvisited parents for each node in the graph.
+ */
+fn dependency_counts(ir: &FheProgram) -> Vec<AtomicUsize> {
+    ir.graph
         .node_indices()
         .map(|n| AtomicUsize::new(ir.graph.neighbors_directed(n, Direction::Incoming).count()))
-        .collect::<Vec<AtomicUsize>>();
-
-    // We must eagerly evaluate the iterator (i.e. collect) since
-    // the dependency counts will be changing during iteration. Lazy
-    // iteration causes a race condition between the filter_map closer
-    // evaluating and the deps counts being decremented, potentially
-    // resulting in nodes being run more than once.
-    let initial_ready = deps
-        .iter()
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * Returns the nodes with no dependencies.
+ *
+ * # Remarks
+ * We must eagerly evaluate the iterator (i.e. collect) since
+ * the dependency counts will be changing during iteration. Lazy
+ * iteration causes a race condition between the filter_map closer
+ * evaluating and the deps counts being decremented, potentially
+ * resulting in nodes being run more than once.
+ */
+fn initial_ready(deps: &[AtomicUsize]) -> Vec<NodeIndex> {
+    deps.iter()
         .enumerate()
         .filter_map(|(id, count)| {
             if count.load(Ordering::Relaxed) == 0 {
                 log::trace!("parallel_traverse: Initial node {}", id);
-                Some(id)
+                Some(NodeIndex::from(id as u32))
             } else {
                 None
             }
         })
-        .collect::<Vec<usize>>();
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * Runs each node on the current thread pool as soon as its
+ * dependencies complete.
+ */
+fn traverse_eager<F>(ir: &FheProgram, callback: &F) -> Result<(), FheProgramRunFailure>
+where
+    F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
+{
+    // Initialize the number of incomplete dependencies.
+    let deps = dependency_counts(ir);
+    let initial_ready = initial_ready(&deps);
 
     let returned_result = AtomicCell::new(Ok(()));
 
@@ -456,16 +661,9 @@ where
 
             let deps = &deps;
             let returned_result = &returned_result;
-            let callback = &callback;
 
             s.spawn(move |_| {
-                run_internal(
-                    NodeIndex::from(node_id as u32),
-                    ir,
-                    deps,
-                    returned_result,
-                    callback,
-                );
+                run_internal(node_id, ir, deps, returned_result, callback);
             });
         }
     });
@@ -473,21 +671,181 @@ where
     returned_result.load()
 }
 
-#[cfg(target_arch = "wasm32")]
-pub fn traverse<F>(
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * A rough estimate of the relative time needed to run the given
+ * operation. Only the ratios between these values matter.
+ */
+fn estimated_cost(operation: &sunscreen_fhe_program::Operation) -> u64 {
+    match operation {
+        Multiply => 40,
+        Relinearize | ShiftLeft | ShiftRight | SwapRows => 30,
+        MultiplyPlaintext => 10,
+        ModSwitch => 5,
+        Add | AddPlaintext | Sub | SubPlaintext | Negate => 1,
+        InputCiphertext(_) | InputPlaintext(_) | Literal(_) | OutputCiphertext => 0,
+        // Compiled programs don't contain these.
+        LessThan { .. } | NotEqual | Select => 0,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * For each node, returns the [`estimated_cost`] of the most expensive
+ * path from that node to an output, including the node itself.
+ */
+fn critical_path_costs(ir: &FheProgram) -> Vec<u64> {
+    let mut costs = vec![0; ir.graph.node_count()];
+
+    // Visit nodes in reverse topological order, so each node's children
+    // have their costs computed before it.
+    let mut remaining = ir
+        .graph
+        .node_indices()
+        .map(|n| ir.graph.neighbors_directed(n, Direction::Outgoing).count())
+        .collect::<Vec<usize>>();
+
+    let mut ready = ir
+        .graph
+        .node_indices()
+        .filter(|n| remaining[n.index()] == 0)
+        .collect::<Vec<NodeIndex>>();
+
+    while let Some(node_id) = ready.pop() {
+        let longest_child = ir
+            .graph
+            .neighbors_directed(node_id, Direction::Outgoing)
+            .map(|c| costs[c.index()])
+            .max()
+            .unwrap_or(0);
+
+        costs[node_id.index()] = estimated_cost(&ir.graph[node_id].operation) + longest_child;
+
+        for p in ir.graph.neighbors_directed(node_id, Direction::Incoming) {
+            remaining[p.index()] -= 1;
+
+            if remaining[p.index()] == 0 {
+                ready.push(p);
+            }
+        }
+    }
+
+    costs
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * State shared between the tasks of [`traverse_critical_path_first`].
+ */
+struct CriticalPathScheduler<'a, F> {
+    ir: &'a FheProgram,
+    callback: &'a F,
+    deps: Vec<AtomicUsize>,
+    priorities: Vec<u64>,
+    ready: Mutex<BinaryHeap<(u64, Reverse<usize>)>>,
+    returned_result: AtomicCell<Result<(), FheProgramRunFailure>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a, F> CriticalPathScheduler<'a, F>
+where
+    F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
+{
+    /**
+     * Marks the given node as ready and spawns a task to run the
+     * highest priority ready node.
+     *
+     * # Remarks
+     * Since every ready node gets pushed before its task is spawned and
+     * every task pops exactly one node, a task always finds a node to run.
+     */
+    fn schedule<'s>(&'s self, s: &rayon::Scope<'s>, node_id: NodeIndex) {
+        log::trace!("Node {} ready", node_id.index());
+
+        self.ready
+            .lock()
+            .unwrap()
+            .push((self.priorities[node_id.index()], Reverse(node_id.index())));
+
+        s.spawn(move |s| self.run_next(s));
+    }
+
+    fn run_next<'s>(&'s self, s: &rayon::Scope<'s>) {
+        let node_id = match self.ready.lock().unwrap().pop() {
+            Some((_, Reverse(id))) => NodeIndex::from(id as u32),
+            None => return,
+        };
+
+        log::trace!("parallel_traverse: Running node {}", node_id.index());
+
+        if self.returned_result.load().is_err() {
+            return;
+        }
+
+        let result = (self.callback)(node_id);
+
+        if result.is_err() {
+            self.returned_result.store(result);
+            return;
+        }
+
+        // Check each child's dependency count and mark it as ready if 0.
+        for e in self
+            .ir
+            .graph
+            .neighbors_directed(node_id, Direction::Outgoing)
+        {
+            let old_val = self.deps[e.index()].fetch_sub(1, Ordering::Relaxed);
+
+            // Note is the value prior to atomic subtraction.
+            if old_val == 1 {
+                self.schedule(s, e);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/**
+ * Runs nodes on the current thread pool, preferring ready nodes with the
+ * most expensive remaining path to an output.
+ */
+fn traverse_critical_path_first<F>(
     ir: &FheProgram,
-    callback: F,
-    run_to: Option<NodeIndex>,
+    callback: &F,
 ) -> Result<(), FheProgramRunFailure>
 where
     F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure> + Sync + Send,
 {
-    let ir = if let Some(x) = run_to {
-        Cow::Owned(ir.prune(&vec![x]))
-    } else {
-        Cow::Borrowed(ir)
+    let deps = dependency_counts(ir);
+    let initial_ready = initial_ready(&deps);
+
+    let scheduler = CriticalPathScheduler {
+        ir,
+        callback,
+        deps,
+        priorities: critical_path_costs(ir),
+        ready: Mutex::new(BinaryHeap::new()),
+        returned_result: AtomicCell::new(Ok(())),
     };
 
+    rayon::scope(|s| {
+        for node_id in initial_ready {
+            scheduler.schedule(s, node_id);
+        }
+    });
+
+    scheduler.returned_result.load()
+}
+
+/**
+ * Runs nodes one at a time on the calling thread in breadth-first
+ * topological order.
+ */
+fn traverse_sequential<F>(ir: &FheProgram, callback: &F) -> Result<(), FheProgramRunFailure>
+where
+    F: Fn(NodeIndex) -> Result<(), FheProgramRunFailure>,
+{
     // Initialize the number of incomplete dependencies.
     let mut deps = ir
         .graph
@@ -504,21 +862,9 @@ where
         }
     });
 
-    let mut ready_nodes = VecDeque::new();
-
-    for i in initial_ready {
-        ready_nodes.push_back(i);
-    }
-
-    loop {
-        let node_id = ready_nodes.pop_front();
-        let node_id = match node_id {
-            Some(i) => i,
-            None => {
-                break;
-            }
-        };
+    let mut ready_nodes = initial_ready.collect::<VecDeque<NodeIndex>>();
 
+    while let Some(node_id) = ready_nodes.pop_front() {
         callback(node_id)?;
 
         for e in ir.graph.neighbors_directed(node_id, Direction::Outgoing) {
@@ -622,6 +968,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mod_switch_shrinks_ciphertext() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_add(a, b);
+        let c = ir.add_mod_switch(c);
+        ir.add_output_ciphertext(c);
+
+        let degree = 8192;
+
+        let (_keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+
+        let a = vec![42; degree as usize];
+        let b = vec![-24; degree as usize];
+
+        let pt_0 = encoder.encode_signed(&a).unwrap();
+        let pt_1 = encoder.encode_signed(&b).unwrap();
+
+        let ct_0 = encryptor.encrypt(&pt_0).unwrap();
+        let ct_1 = encryptor.encrypt(&pt_1).unwrap();
+
+        let fresh_len = ct_0.as_bytes().unwrap().len();
+
+        let output = unsafe {
+            run_program_unchecked(&ir, &[ct_0.into(), ct_1.into()], &evaluator, &None, &None)
+                .unwrap()
+        };
+
+        assert_eq!(output.len(), 1);
+        assert!(output[0].as_bytes().unwrap().len() < fresh_len);
+
+        let o_p = decryptor.decrypt(&output[0]).unwrap();
+
+        assert_eq!(
+            encoder.decode_signed(&o_p).unwrap(),
+            vec![42 - 24; degree as usize]
+        );
+    }
+
     #[test]
     fn simple_mul() {
         let mut ir = FheProgram::new(SchemeType::Bfv);
@@ -869,4 +1259,125 @@ mod tests {
 
         assert_eq!(encoder.decode_unsigned(&o_p).unwrap(), expected);
     }
+
+    fn mul_add_program() -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+
+        let m = ir.add_multiply(a, b);
+        let r = ir.add_relinearize(m);
+        let s = ir.add_add(a, b);
+        let res = ir.add_add(r, s);
+
+        ir.add_output_ciphertext(res);
+
+        ir
+    }
+
+    #[test]
+    fn critical_path_costs_include_descendants() {
+        let ir = mul_add_program();
+        let costs = critical_path_costs(&ir);
+
+        // a -> multiply -> relinearize -> add -> output
+        assert_eq!(costs[0], 40 + 30 + 1);
+        assert_eq!(costs[2], 40 + 30 + 1);
+        assert_eq!(costs[3], 30 + 1);
+
+        // a + b -> add -> output
+        assert_eq!(costs[4], 1 + 1);
+        assert_eq!(costs[6], 0);
+    }
+
+    #[test]
+    fn sequential_traversal_is_deterministic() {
+        let ir = mul_add_program();
+
+        let run = || {
+            let order = Mutex::new(vec![]);
+
+            traverse_with_options(
+                &ir,
+                |id| {
+                    order.lock().unwrap().push(id.index());
+                    Ok(())
+                },
+                None,
+                &RunOptions {
+                    thread_pool: None,
+                    scheduling: Scheduling::Sequential,
+                },
+            )
+            .unwrap();
+
+            order.into_inner().unwrap()
+        };
+
+        let order = run();
+
+        assert_eq!(order.len(), ir.graph.node_count());
+        assert_eq!(order, run());
+    }
+
+    #[test]
+    fn can_run_with_each_scheduling() {
+        let ir = mul_add_program();
+
+        let degree = 8192;
+
+        let (keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+        let relin_keys = keygen.create_relinearization_keys().unwrap();
+
+        let pt_0 = encoder.encode_signed(&vec![6; degree as usize]).unwrap();
+        let pt_1 = encoder.encode_signed(&vec![-7; degree as usize]).unwrap();
+
+        let ct_0 = encryptor.encrypt(&pt_0).unwrap();
+        let ct_1 = encryptor.encrypt(&pt_1).unwrap();
+
+        let thread_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+
+        for scheduling in [
+            Scheduling::Eager,
+            Scheduling::CriticalPathFirst,
+            Scheduling::Sequential,
+        ] {
+            for thread_pool in [None, Some(thread_pool.clone())] {
+                let options = RunOptions {
+                    thread_pool,
+                    scheduling,
+                };
+
+                let output = unsafe {
+                    run_program_unchecked_with_options(
+                        &ir,
+                        &[ct_0.clone().into(), ct_1.clone().into()],
+                        &evaluator,
+                        &Some(&relin_keys),
+                        &None,
+                        &options,
+                    )
+                    .unwrap()
+                };
+
+                assert_eq!(output.len(), 1);
+
+                let o_p = decryptor.decrypt(&output[0]).unwrap();
+
+                assert_eq!(
+                    encoder.decode_signed(&o_p).unwrap(),
+                    vec![6 * -7 + 6 - 7; degree as usize]
+                );
+            }
+        }
+    }
 }
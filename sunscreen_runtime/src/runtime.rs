@@ -3,12 +3,17 @@ use std::time::Instant;
 
 use crate::error::*;
 use crate::metadata::*;
-use crate::ZkpProgramInput;
+use crate::threshold::{
+    self, KeyShare, PartialDecryption, PrivateKeyFragment, PublicKeyShare, RelinKeyShare,
+    ThresholdConfig, ThresholdPrivateKey,
+};
 use crate::{
-    run_program_unchecked, serialization::WithContext, Ciphertext, FheProgramInput,
-    InnerCiphertext, InnerPlaintext, Plaintext, PrivateKey, PublicKey, SealCiphertext, SealData,
+    run_program_unchecked_with_options, serialization::WithContext, Ciphertext, CompactCiphertext,
+    CompactInnerCiphertext, CompactPublicKey, CompactWithContext, FheProgramInput, InnerCiphertext,
+    InnerPlaintext, Plaintext, PrivateKey, PublicKey, RunOptions, SealCiphertext, SealData,
     SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance,
 };
+use crate::{ZkpOutputs, ZkpProgramInput, ZkpProof};
 
 use log::trace;
 use sunscreen_fhe_program::FheProgramTrait;
@@ -22,7 +27,7 @@ use seal_fhe::{
 pub use sunscreen_compiler_common::{Type, TypeName};
 use sunscreen_zkp_backend::BigInt;
 use sunscreen_zkp_backend::CompiledZkpProgram;
-use sunscreen_zkp_backend::Proof;
+use sunscreen_zkp_backend::Error as ZkpError;
 use sunscreen_zkp_backend::ZkpBackend;
 
 enum Context {
@@ -68,6 +73,7 @@ impl marker::Zkp for FheZkp {}
 struct FheRuntimeData {
     params: Params,
     context: Context,
+    run_options: RunOptions,
 }
 
 struct ZkpRuntimeData;
@@ -93,6 +99,20 @@ impl RuntimeData {
             _ => panic!("Expected RuntimeData::Fhe or RuntimeData::FheZkp."),
         }
     }
+
+    /**
+     * Mutable version of [`unwrap_fhe`](Self::unwrap_fhe).
+     *
+     * # Panics
+     * * If this value isn't a [`RuntimeData::Fhe`].
+     */
+    fn unwrap_fhe_mut(&mut self) -> &mut FheRuntimeData {
+        match self {
+            Self::Fhe(x) => x,
+            Self::FheZkp(x, _) => x,
+            _ => panic!("Expected RuntimeData::Fhe or RuntimeData::FheZkp."),
+        }
+    }
 }
 
 /**
@@ -119,53 +139,72 @@ where
     where
         P: TryFromPlaintext + TypeName,
     {
-        let expected_type = Type {
-            is_encrypted: true,
-            ..P::type_name()
-        };
-
-        if expected_type != ciphertext.data_type {
-            return Err(Error::type_mismatch(&expected_type, &ciphertext.data_type));
-        }
+        check_ciphertext_type::<P>(ciphertext)?;
 
         let fhe_data = self.runtime_data.unwrap_fhe();
 
-        let val = match (&fhe_data.context, &ciphertext.inner) {
+        match (&fhe_data.context, &ciphertext.inner) {
             (Context::Seal(context), InnerCiphertext::Seal(ciphertexts)) => {
                 let decryptor = Decryptor::new(context, &private_key.0)?;
 
-                let plaintexts = ciphertexts
-                    .iter()
-                    .map(|c| {
-                        if decryptor
-                            .invariant_noise_budget(c)
-                            .map_err(Error::SealError)?
-                            == 0
-                        {
-                            return Err(Error::TooMuchNoise);
-                        }
+                self.decrypt_with(&decryptor, ciphertexts.iter().map(|c| &c.data))
+            }
+        }
+    }
 
-                        decryptor.decrypt(c).map_err(Error::SealError)
-                    })
-                    .collect::<Result<Vec<SealPlaintext>>>()?
-                    .drain(0..)
-                    .map(|p| WithContext {
-                        params: fhe_data.params.clone(),
-                        data: p,
-                    })
-                    .collect();
+    /**
+     * Decrypts the given SEAL ciphertexts with `decryptor` and decodes the
+     * resulting plaintexts as a `P`.
+     */
+    fn decrypt_with<'a, P>(
+        &self,
+        decryptor: &Decryptor,
+        ciphertexts: impl Iterator<Item = &'a SealCiphertext>,
+    ) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        let plaintexts = ciphertexts
+            .map(|c| {
+                if decryptor
+                    .invariant_noise_budget(c)
+                    .map_err(Error::SealError)?
+                    == 0
+                {
+                    return Err(Error::TooMuchNoise);
+                }
 
-                P::try_from_plaintext(
-                    &Plaintext {
-                        data_type: P::type_name(),
-                        inner: InnerPlaintext::Seal(plaintexts),
-                    },
-                    &fhe_data.params,
-                )?
-            }
-        };
+                decryptor.decrypt(c).map_err(Error::SealError)
+            })
+            .collect::<Result<Vec<SealPlaintext>>>()?;
 
-        Ok(val)
+        self.plaintexts_into(plaintexts)
+    }
+
+    /**
+     * Converts the decrypted plaintexts of a [`Ciphertext`] into a `P`.
+     */
+    fn plaintexts_into<P>(&self, plaintexts: Vec<SealPlaintext>) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let plaintexts = plaintexts
+            .into_iter()
+            .map(|p| WithContext {
+                params: fhe_data.params.clone(),
+                data: p,
+            })
+            .collect();
+
+        P::try_from_plaintext(
+            &Plaintext {
+                data_type: P::type_name(),
+                inner: InnerPlaintext::Seal(plaintexts),
+            },
+            &fhe_data.params,
+        )
     }
 
     /**
@@ -203,42 +242,207 @@ where
      * can't require these associated keys and so long as the FHE program was compiled using the
      * search algorithm, it won't.
      *
+     * The returned [`PrivateKey`] decrypts every ciphertext encrypted under
+     * the [`PublicKey`]. To instead split decryption among several parties,
+     * see [`generate_key_share`](Self::generate_key_share).
+     *
      * See [`PublicKey`] for more information.
      */
     pub fn generate_keys(&self) -> Result<(PublicKey, PrivateKey)> {
+        self.generate_keys_internal(true, None)
+    }
+
+    /**
+     * Generates a tuple of public/private keys sufficient to run the given FHE programs.
+     *
+     * # Remarks
+     * Unlike [`generate_keys`](Self::generate_keys), this only creates relinearization
+     * keys if some program requires them and only creates Galois keys for the rotation
+     * steps the programs actually use (see [`FheProgramMetadata::galois_steps`]). For
+     * programs that rotate by only a few amounts, this results in much smaller public keys
+     * and faster key generation.
+     *
+     * The resulting [`PublicKey`] may fail to run other FHE programs.
+     */
+    pub fn generate_keys_for(
+        &self,
+        fhe_programs: &[&CompiledFheProgram],
+    ) -> Result<(PublicKey, PrivateKey)> {
+        let (create_relin, galois_steps) = Self::required_keys(fhe_programs);
+
+        self.generate_keys_internal(create_relin, Some(galois_steps))
+    }
+
+    /**
+     * Generates a tuple of compact public keys and a private key.
+     *
+     * # Remarks
+     * This creates the same keys as [`generate_keys`](Self::generate_keys),
+     * but the [`CompactPublicKey`] serializes to roughly half the size of a
+     * [`PublicKey`]. Deserialize it as a [`PublicKey`] to use it.
+     */
+    pub fn generate_compact_keys(&self) -> Result<(CompactPublicKey, PrivateKey)> {
+        self.generate_compact_keys_internal(true, None)
+    }
+
+    /**
+     * Generates a tuple of compact public keys and a private key sufficient
+     * to run the given FHE programs. See
+     * [`generate_keys_for`](Self::generate_keys_for) and
+     * [`generate_compact_keys`](Self::generate_compact_keys).
+     */
+    pub fn generate_compact_keys_for(
+        &self,
+        fhe_programs: &[&CompiledFheProgram],
+    ) -> Result<(CompactPublicKey, PrivateKey)> {
+        let (create_relin, galois_steps) = Self::required_keys(fhe_programs);
+
+        self.generate_compact_keys_internal(create_relin, Some(galois_steps))
+    }
+
+    /**
+     * Returns whether the given FHE programs require relinearization keys
+     * and the deduplicated Galois rotation steps they use.
+     */
+    fn required_keys(fhe_programs: &[&CompiledFheProgram]) -> (bool, Vec<i32>) {
+        let requires = |key: RequiredKeys| {
+            fhe_programs
+                .iter()
+                .any(|p| p.metadata.required_keys.contains(&key))
+        };
+
+        let mut galois_steps = fhe_programs
+            .iter()
+            .filter(|p| p.metadata.required_keys.contains(&RequiredKeys::Galois))
+            .flat_map(|p| p.metadata.galois_steps.iter().copied())
+            .collect::<Vec<i32>>();
+
+        galois_steps.sort_unstable();
+        galois_steps.dedup();
+
+        (requires(RequiredKeys::Relin), galois_steps)
+    }
+
+    /**
+     * Generates keys. When `galois_steps` is `None`, creates Galois keys for every
+     * power of 2 rotation. When it's an empty list, creates no Galois keys.
+     */
+    fn generate_keys_internal(
+        &self,
+        create_relin: bool,
+        galois_steps: Option<Vec<i32>>,
+    ) -> Result<(PublicKey, PrivateKey)> {
+        let params = &self.runtime_data.unwrap_fhe().params;
+
+        let (public_key, relin_key, galois_key, private_key) = self.generate_keys_with(
+            create_relin,
+            galois_steps,
+            |keygen| Ok(keygen.create_public_key()),
+            KeyGenerator::create_relinearization_keys,
+            KeyGenerator::create_galois_keys,
+            KeyGenerator::create_galois_keys_from_steps,
+        )?;
+
+        let public_key = PublicKey {
+            public_key: WithContext {
+                params: params.clone(),
+                data: public_key,
+            },
+            galois_key: galois_key.map(|data| WithContext {
+                params: params.clone(),
+                data,
+            }),
+            relin_key: relin_key.map(|data| WithContext {
+                params: params.clone(),
+                data,
+            }),
+        };
+
+        Ok((public_key, private_key))
+    }
+
+    /**
+     * Generates compact keys. See [`generate_keys_internal`](Self::generate_keys_internal)
+     * for the meaning of `create_relin` and `galois_steps`.
+     */
+    fn generate_compact_keys_internal(
+        &self,
+        create_relin: bool,
+        galois_steps: Option<Vec<i32>>,
+    ) -> Result<(CompactPublicKey, PrivateKey)> {
+        let params = &self.runtime_data.unwrap_fhe().params;
+
+        let (public_key, relin_key, galois_key, private_key) = self.generate_keys_with(
+            create_relin,
+            galois_steps,
+            |keygen| keygen.create_compact_public_key().as_bytes(),
+            |keygen| keygen.create_compact_relinearization_keys()?.as_bytes(),
+            |keygen| keygen.create_compact_galois_keys()?.as_bytes(),
+            |keygen, steps| {
+                keygen
+                    .create_compact_galois_keys_from_steps(steps)?
+                    .as_bytes()
+            },
+        )?;
+
+        let compact = |data: Vec<u8>| CompactWithContext {
+            params: params.clone(),
+            data,
+        };
+
+        let public_key = CompactPublicKey {
+            public_key: compact(public_key),
+            galois_key: galois_key.map(compact),
+            relin_key: relin_key.map(compact),
+        };
+
+        Ok((public_key, private_key))
+    }
+
+    /**
+     * Generates a private key and returns it with the public, relinearization
+     * and Galois keys created from it by the given functions. These choose
+     * how the keys are represented (e.g. as SEAL keys or as compact bytes).
+     *
+     * # Remarks
+     * When `galois_steps` is `None`, creates Galois keys for every power of 2
+     * rotation with `galois`. When it's an empty list, creates no Galois
+     * keys. Relinearization keys and Galois keys for every power of 2 are
+     * omitted if the scheme parameters don't support them.
+     */
+    fn generate_keys_with<P, R, G>(
+        &self,
+        create_relin: bool,
+        galois_steps: Option<Vec<i32>>,
+        public: impl FnOnce(&KeyGenerator) -> seal_fhe::Result<P>,
+        relin: impl FnOnce(&KeyGenerator) -> seal_fhe::Result<R>,
+        galois: impl FnOnce(&KeyGenerator) -> seal_fhe::Result<G>,
+        galois_from_steps: impl FnOnce(&KeyGenerator, &[i32]) -> seal_fhe::Result<G>,
+    ) -> Result<(P, Option<R>, Option<G>, PrivateKey)> {
         let fhe_data = self.runtime_data.unwrap_fhe();
 
         let keys = match &fhe_data.context {
             Context::Seal(context) => {
                 let keygen = KeyGenerator::new(context)?;
 
-                let galois_keys = keygen.create_galois_keys().ok().map(|v| WithContext {
-                    params: fhe_data.params.clone(),
-                    data: v,
-                });
-
-                let relin_keys = keygen
-                    .create_relinearization_keys()
-                    .ok()
-                    .map(|v| WithContext {
-                        params: fhe_data.params.clone(),
-                        data: v,
-                    });
+                let galois_keys = match galois_steps {
+                    None => galois(&keygen).ok(),
+                    Some(steps) if steps.is_empty() => None,
+                    Some(steps) => Some(galois_from_steps(&keygen, &steps)?),
+                };
 
-                let public_keys = PublicKey {
-                    public_key: WithContext {
-                        params: fhe_data.params.clone(),
-                        data: keygen.create_public_key(),
-                    },
-                    galois_key: galois_keys,
-                    relin_key: relin_keys,
+                let relin_keys = if create_relin {
+                    relin(&keygen).ok()
+                } else {
+                    None
                 };
+
                 let private_key = PrivateKey(WithContext {
                     params: fhe_data.params.clone(),
                     data: keygen.secret_key(),
                 });
 
-                (public_keys, private_key)
+                (public(&keygen)?, relin_keys, galois_keys, private_key)
             }
         };
 
@@ -254,135 +458,172 @@ where
         &fhe_data.params
     }
 
+    /**
+     * Sets the thread pool and scheduling policy used when running FHE
+     * programs.
+     *
+     * # Remarks
+     * By default, the runtime runs FHE programs on rayon's global thread
+     * pool, executing each operation as soon as its operands are ready.
+     * Servers sharing a machine with other work can pass a dedicated
+     * [`rayon::ThreadPool`] to bound the threads FHE programs use, and
+     * [`Scheduling::Sequential`](crate::Scheduling::Sequential) runs programs deterministically on the
+     * calling thread, which helps when debugging.
+     */
+    pub fn with_run_options(mut self, run_options: RunOptions) -> Self {
+        self.runtime_data.unwrap_fhe_mut().run_options = run_options;
+
+        self
+    }
+
+    /**
+     * Returns the options used when running FHE programs. See
+     * [`with_run_options`](Self::with_run_options).
+     */
+    pub fn run_options(&self) -> &RunOptions {
+        &self.runtime_data.unwrap_fhe().run_options
+    }
+
     /**
      * Validates and runs the given FHE program. Unless you can guarantee your FHE program is valid,
-     * you should use this method rather than [`run_program_unchecked`].
+     * you should use this method rather than [`run_program_unchecked`](crate::run_program_unchecked).
      */
     pub fn run<I>(
         &self,
         fhe_program: &CompiledFheProgram,
-        mut arguments: Vec<I>,
+        arguments: Vec<I>,
         public_key: &PublicKey,
     ) -> Result<Vec<Ciphertext>>
     where
         I: Into<FheProgramInput>,
     {
-        // We're going to call run_program_unchecked, which
-        // can result in undefined behavior, non-termination,
-        // or panics on malformed programs. Since this method is safe,
-        // it must guard against calling run_program_unchecked with
-        // inputs that result in undefined behavior.
-        fhe_program.fhe_program_fn.validate()?;
-
-        // Aside from FHE program correctness, check that the required keys are given.
-        if public_key.relin_key.is_none() && fhe_program.fhe_program_fn.requires_relin_keys() {
-            return Err(Error::MissingRelinearizationKeys);
-        }
+        validate_program(fhe_program, public_key)?;
 
-        if public_key.galois_key.is_none() && fhe_program.fhe_program_fn.requires_galois_keys() {
-            return Err(Error::MissingGaloisKeys);
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let evaluator = BFVEvaluator::new(context)?;
+
+                run_validated_program(fhe_data, fhe_program, arguments, &evaluator, public_key)
+            }
         }
+    }
+
+    /**
+     * Validates the given FHE program and returns an iterator that runs it
+     * once for each list of arguments in `batches`.
+     *
+     * # Remarks
+     * The returned [`FheProgramStream`] lazily pulls one list of arguments
+     * from `batches` each time you call [`next`](Iterator::next), so only
+     * the current arguments and outputs need to be in memory. This lets you
+     * process datasets too large to hold at once, provided `batches` itself
+     * produces (e.g. reads or encrypts) its items on demand. Validation
+     * and evaluator setup happen once rather than per item.
+     */
+    pub fn run_stream<'a, I, S>(
+        &'a self,
+        fhe_program: &'a CompiledFheProgram,
+        batches: S,
+        public_key: &'a PublicKey,
+    ) -> Result<FheProgramStream<'a, S::IntoIter>>
+    where
+        S: IntoIterator<Item = Vec<I>>,
+        I: Into<FheProgramInput>,
+    {
+        validate_program(fhe_program, public_key)?;
 
-        let mut arguments: Vec<FheProgramInput> = arguments.drain(0..).map(|a| a.into()).collect();
+        let fhe_data = self.runtime_data.unwrap_fhe();
 
-        let expected_args = &fhe_program.metadata.signature.arguments;
+        let evaluator = match &fhe_data.context {
+            Context::Seal(context) => BFVEvaluator::new(context)?,
+        };
 
-        // Check the arguments match the signature.
-        if expected_args.len() != arguments.len() {
-            return Err(Error::IncorrectCiphertextCount);
-        }
+        Ok(FheProgramStream {
+            fhe_data,
+            fhe_program,
+            public_key,
+            evaluator,
+            batches: batches.into_iter(),
+        })
+    }
 
-        // Check the passed arguments' types match the signature.
-        if arguments
+    /**
+     * Runs `fhe_program` on each list of arguments in `batches` and
+     * combines the outputs with `reducer`. Returns [`None`] if `batches`
+     * is empty.
+     *
+     * # Remarks
+     * `reducer` takes the accumulated outputs followed by the outputs of
+     * the next run of `fhe_program` and returns the new accumulated
+     * outputs. For example, if `fhe_program` returns a single
+     * `Cipher<Signed>`, a `reducer` that sums its 2 `Cipher<Signed>`
+     * arguments totals the outputs across all batches. Both programs must
+     * be compiled with the same parameters (e.g. by compiling them
+     * together).
+     *
+     * Like [`run_stream`](Self::run_stream), this only holds the
+     * accumulated outputs and the current batch in memory.
+     *
+     * Each reduction adds noise to the accumulated outputs, so the
+     * compiler's noise budget for `reducer` must account for the number
+     * of batches.
+     */
+    pub fn run_reduce<I, S>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        batches: S,
+        reducer: &CompiledFheProgram,
+        public_key: &PublicKey,
+    ) -> Result<Option<Vec<Ciphertext>>>
+    where
+        S: IntoIterator<Item = Vec<I>>,
+        I: Into<FheProgramInput>,
+    {
+        let returns = &fhe_program.metadata.signature.returns;
+
+        let expected_args = returns
             .iter()
-            .enumerate()
-            .any(|(i, a)| a.type_name_instance() != expected_args[i])
-        {
+            .chain(returns.iter())
+            .cloned()
+            .collect::<Vec<Type>>();
+
+        if reducer.metadata.signature.arguments != expected_args {
             return Err(Error::argument_mismatch(
-                expected_args,
-                &arguments
-                    .iter()
-                    .map(|a| a.type_name_instance())
-                    .collect::<Vec<Type>>(),
+                &expected_args,
+                &reducer.metadata.signature.arguments,
             ));
         }
 
-        if fhe_program.metadata.signature.num_ciphertexts.len()
-            != fhe_program.metadata.signature.returns.len()
-        {
-            return Err(Error::ReturnTypeMetadataError);
+        if reducer.metadata.signature.returns != *returns {
+            return Err(Error::argument_mismatch(
+                returns,
+                &reducer.metadata.signature.returns,
+            ));
         }
 
-        let fhe_data = self.runtime_data.unwrap_fhe();
-
-        match &fhe_data.context {
-            Context::Seal(context) => {
-                let evaluator = BFVEvaluator::new(context)?;
-
-                let mut inputs: Vec<SealData> = vec![];
-
-                for i in arguments.drain(0..) {
-                    match i {
-                        FheProgramInput::Ciphertext(c) => match c.inner {
-                            InnerCiphertext::Seal(mut c) => {
-                                for j in c.drain(0..) {
-                                    inputs.push(SealData::Ciphertext(j.data));
-                                }
-                            }
-                        },
-                        FheProgramInput::Plaintext(p) => {
-                            let p = p.try_into_plaintext(&fhe_data.params)?;
-
-                            match p.inner {
-                                InnerPlaintext::Seal(mut p) => {
-                                    for j in p.drain(0..) {
-                                        inputs.push(SealData::Plaintext(j.data));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
-                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
-
-                let mut raw_ciphertexts = unsafe {
-                    run_program_unchecked(
-                        &fhe_program.fhe_program_fn,
-                        &inputs,
-                        &evaluator,
-                        &relin_key,
-                        &galois_key,
-                    )
-                }?;
+        validate_program(reducer, public_key)?;
 
-                let mut packed_ciphertexts = vec![];
+        let mut stream = self.run_stream(fhe_program, batches, public_key)?;
+        let mut acc: Option<Vec<Ciphertext>> = None;
 
-                for (i, ciphertext_count) in fhe_program
-                    .metadata
-                    .signature
-                    .num_ciphertexts
-                    .iter()
-                    .enumerate()
-                {
-                    packed_ciphertexts.push(Ciphertext {
-                        data_type: fhe_program.metadata.signature.returns[i].clone(),
-                        inner: InnerCiphertext::Seal(
-                            raw_ciphertexts
-                                .drain(0..*ciphertext_count)
-                                .map(|c| WithContext {
-                                    params: fhe_data.params.clone(),
-                                    data: c,
-                                })
-                                .collect(),
-                        ),
-                    });
-                }
+        while let Some(outputs) = stream.next() {
+            let outputs = outputs?;
 
-                Ok(packed_ciphertexts)
-            }
+            acc = Some(match acc {
+                None => outputs,
+                Some(acc) => run_validated_program(
+                    stream.fhe_data,
+                    reducer,
+                    acc.into_iter().chain(outputs).collect(),
+                    &stream.evaluator,
+                    public_key,
+                )?,
+            });
         }
+
+        Ok(acc)
     }
 
     /**
@@ -392,6 +633,91 @@ where
      * scheme.
      */
     pub fn encrypt<P>(&self, val: P, public_key: &PublicKey) -> Result<Ciphertext>
+    where
+        P: TryIntoPlaintext + TypeName,
+    {
+        let (data_type, ciphertexts) = self.encrypt_internal(
+            val,
+            |ctx| Encryptor::with_public_key(ctx, &public_key.public_key.data),
+            |encryptor, p| encryptor.encrypt(p),
+        )?;
+
+        Ok(self.make_ciphertext(data_type, ciphertexts))
+    }
+
+    /**
+     * Encrypts the given [`FheType`](crate::FheType) using the given private key
+     * (i.e. symmetric-key encryption).
+     *
+     * # Remarks
+     * Decrypting the result requires the same private key, so this is
+     * only useful when the party encrypting data also owns the private key.
+     * See [`encrypt_symmetric_compact`](Self::encrypt_symmetric_compact)
+     * to produce smaller ciphertexts for upload.
+     */
+    pub fn encrypt_symmetric<P>(&self, val: P, private_key: &PrivateKey) -> Result<Ciphertext>
+    where
+        P: TryIntoPlaintext + TypeName,
+    {
+        let (data_type, ciphertexts) = self.encrypt_internal(
+            val,
+            |ctx| Encryptor::with_secret_key(ctx, &private_key.0.data),
+            |encryptor, p| encryptor.encrypt_symmetric(p),
+        )?;
+
+        Ok(self.make_ciphertext(data_type, ciphertexts))
+    }
+
+    /**
+     * Encrypts the given [`FheType`](crate::FheType) using the given private key
+     * and returns a [`CompactCiphertext`].
+     *
+     * # Remarks
+     * The returned value serializes to roughly half the size of a
+     * [`Ciphertext`] because one polynomial in each SEAL ciphertext is
+     * replaced by the seed used to generate it. Deserialize the result as
+     * a [`Ciphertext`] to use it.
+     */
+    pub fn encrypt_symmetric_compact<P>(
+        &self,
+        val: P,
+        private_key: &PrivateKey,
+    ) -> Result<CompactCiphertext>
+    where
+        P: TryIntoPlaintext + TypeName,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let (data_type, ciphertexts) = self.encrypt_internal(
+            val,
+            |ctx| Encryptor::with_secret_key(ctx, &private_key.0.data),
+            |encryptor, p| encryptor.encrypt_symmetric_compact(p)?.as_bytes(),
+        )?;
+
+        let ciphertexts = ciphertexts
+            .into_iter()
+            .map(|data| CompactWithContext {
+                params: fhe_data.params.clone(),
+                data,
+            })
+            .collect();
+
+        Ok(CompactCiphertext {
+            data_type,
+            inner: CompactInnerCiphertext::Seal(ciphertexts),
+        })
+    }
+
+    /**
+     * Encodes `val` and encrypts each of the resulting plaintexts with
+     * `encrypt`. Returns the encrypted data type and the ciphertexts.
+     */
+    fn encrypt_internal<P, C>(
+        &self,
+        val: P,
+        make_encryptor: impl FnOnce(&SealContext) -> seal_fhe::Result<Encryptor>,
+        encrypt: impl Fn(&Encryptor, &SealPlaintext) -> seal_fhe::Result<C>,
+    ) -> Result<(Type, Vec<C>)>
     where
         P: TryIntoPlaintext + TypeName,
     {
@@ -399,32 +725,457 @@ where
 
         let plaintext = val.try_into_plaintext(&fhe_data.params)?;
 
-        let ciphertext = match (&fhe_data.context, plaintext.inner) {
+        let ciphertexts = match (&fhe_data.context, plaintext.inner) {
             (Context::Seal(context), InnerPlaintext::Seal(inner_plain)) => {
-                let encryptor = Encryptor::with_public_key(context, &public_key.public_key.data)?;
+                let encryptor = make_encryptor(context)?;
 
-                let ciphertexts = inner_plain
+                inner_plain
                     .iter()
-                    .map(|p| encryptor.encrypt(p).map_err(Error::SealError))
-                    .collect::<Result<Vec<SealCiphertext>>>()?
-                    .drain(0..)
+                    .map(|p| encrypt(&encryptor, &p.data).map_err(Error::SealError))
+                    .collect::<Result<Vec<C>>>()?
+            }
+        };
+
+        let data_type = Type {
+            is_encrypted: true,
+            ..P::type_name()
+        };
+
+        Ok((data_type, ciphertexts))
+    }
+
+    fn make_ciphertext(&self, data_type: Type, ciphertexts: Vec<SealCiphertext>) -> Ciphertext {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let ciphertexts = ciphertexts
+            .into_iter()
+            .map(|c| WithContext {
+                params: fhe_data.params.clone(),
+                data: c,
+            })
+            .collect();
+
+        Ciphertext {
+            data_type,
+            inner: InnerCiphertext::Seal(ciphertexts),
+        }
+    }
+}
+
+/**
+ * An iterator that runs an FHE program on each list of arguments in a
+ * sequence. See [`GenericRuntime::run_stream`].
+ */
+pub struct FheProgramStream<'a, S> {
+    fhe_data: &'a FheRuntimeData,
+    fhe_program: &'a CompiledFheProgram,
+    public_key: &'a PublicKey,
+    evaluator: BFVEvaluator,
+    batches: S,
+}
+
+impl<'a, I, S> Iterator for FheProgramStream<'a, S>
+where
+    S: Iterator<Item = Vec<I>>,
+    I: Into<FheProgramInput>,
+{
+    type Item = Result<Vec<Ciphertext>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let arguments = self.batches.next()?;
+
+        Some(run_validated_program(
+            self.fhe_data,
+            self.fhe_program,
+            arguments,
+            &self.evaluator,
+            self.public_key,
+        ))
+    }
+}
+
+/**
+ * Checks that `fhe_program` is well-formed and that `public_key` contains
+ * the keys it needs.
+ */
+fn validate_program(fhe_program: &CompiledFheProgram, public_key: &PublicKey) -> Result<()> {
+    // We're going to call run_program_unchecked, which
+    // can result in undefined behavior, non-termination,
+    // or panics on malformed programs. Since running is safe,
+    // it must guard against calling run_program_unchecked with
+    // inputs that result in undefined behavior.
+    fhe_program.fhe_program_fn.validate()?;
+
+    // Aside from FHE program correctness, check that the required keys are given.
+    if public_key.relin_key.is_none() && fhe_program.fhe_program_fn.requires_relin_keys() {
+        return Err(Error::MissingRelinearizationKeys);
+    }
+
+    if public_key.galois_key.is_none() && fhe_program.fhe_program_fn.requires_galois_keys() {
+        return Err(Error::MissingGaloisKeys);
+    }
+
+    if fhe_program.metadata.signature.num_ciphertexts.len()
+        != fhe_program.metadata.signature.returns.len()
+    {
+        return Err(Error::ReturnTypeMetadataError);
+    }
+
+    Ok(())
+}
+
+/**
+ * Returns an error unless `ciphertext` encrypts a `P`.
+ */
+fn check_ciphertext_type<P>(ciphertext: &Ciphertext) -> Result<()>
+where
+    P: TypeName,
+{
+    let expected_type = Type {
+        is_encrypted: true,
+        ..P::type_name()
+    };
+
+    if expected_type != ciphertext.data_type {
+        return Err(Error::type_mismatch(&expected_type, &ciphertext.data_type));
+    }
+
+    Ok(())
+}
+
+/**
+ * Runs an FHE program that has passed [`validate_program`] on the given
+ * arguments.
+ */
+fn run_validated_program<I>(
+    fhe_data: &FheRuntimeData,
+    fhe_program: &CompiledFheProgram,
+    arguments: Vec<I>,
+    evaluator: &BFVEvaluator,
+    public_key: &PublicKey,
+) -> Result<Vec<Ciphertext>>
+where
+    I: Into<FheProgramInput>,
+{
+    let mut arguments: Vec<FheProgramInput> = arguments.into_iter().map(|a| a.into()).collect();
+
+    let expected_args = &fhe_program.metadata.signature.arguments;
+
+    // Check the arguments match the signature.
+    if expected_args.len() != arguments.len() {
+        return Err(Error::IncorrectCiphertextCount);
+    }
+
+    // Check the passed arguments' types match the signature.
+    if arguments
+        .iter()
+        .enumerate()
+        .any(|(i, a)| a.type_name_instance() != expected_args[i])
+    {
+        return Err(Error::argument_mismatch(
+            expected_args,
+            &arguments
+                .iter()
+                .map(|a| a.type_name_instance())
+                .collect::<Vec<Type>>(),
+        ));
+    }
+
+    let mut inputs: Vec<SealData> = vec![];
+
+    for i in arguments.drain(0..) {
+        match i {
+            FheProgramInput::Ciphertext(c) => match c.inner {
+                InnerCiphertext::Seal(mut c) => {
+                    for j in c.drain(0..) {
+                        inputs.push(SealData::Ciphertext(j.data));
+                    }
+                }
+            },
+            FheProgramInput::Plaintext(p) => {
+                let p = p.try_into_plaintext(&fhe_data.params)?;
+
+                match p.inner {
+                    InnerPlaintext::Seal(mut p) => {
+                        for j in p.drain(0..) {
+                            inputs.push(SealData::Plaintext(j.data));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
+    let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
+
+    let mut raw_ciphertexts = unsafe {
+        run_program_unchecked_with_options(
+            &fhe_program.fhe_program_fn,
+            &inputs,
+            evaluator,
+            &relin_key,
+            &galois_key,
+            &fhe_data.run_options,
+        )
+    }?;
+
+    let mut packed_ciphertexts = vec![];
+
+    for (i, ciphertext_count) in fhe_program
+        .metadata
+        .signature
+        .num_ciphertexts
+        .iter()
+        .enumerate()
+    {
+        packed_ciphertexts.push(Ciphertext {
+            data_type: fhe_program.metadata.signature.returns[i].clone(),
+            inner: InnerCiphertext::Seal(
+                raw_ciphertexts
+                    .drain(0..*ciphertext_count)
                     .map(|c| WithContext {
                         params: fhe_data.params.clone(),
                         data: c,
                     })
-                    .collect();
-
-                Ciphertext {
-                    data_type: Type {
-                        is_encrypted: true,
-                        ..P::type_name()
-                    },
-                    inner: InnerCiphertext::Seal(ciphertexts),
+                    .collect(),
+            ),
+        });
+    }
+
+    Ok(packed_ciphertexts)
+}
+
+impl<T, B> GenericRuntime<T, B>
+where
+    T: self::marker::Fhe,
+{
+    /**
+     * Generates party `party`'s share of a threshold key, where any
+     * `config.threshold` of `config.parties` parties can decrypt together.
+     * Returns the party's secret [`KeyShare`] and the [`PublicKeyShare`] it
+     * sends to every other party.
+     *
+     * # Remarks
+     * Threshold key generation takes the following steps, where every party
+     * uses a runtime with the same parameters and agrees on `config`:
+     * 1. Each party calls this function and sends its [`PublicKeyShare`] to
+     *    the others.
+     * 2. Each party calls
+     *    [`generate_relin_key_share`](Self::generate_relin_key_share) with
+     *    every party's [`PublicKeyShare`] and sends the result to the
+     *    others.
+     * 3. Anyone calls
+     *    [`combine_public_key_shares`](Self::combine_public_key_shares) to
+     *    get the joint [`PublicKey`], which works like any other.
+     * 4. Each party calls [`split_key_share`](Self::split_key_share) and
+     *    privately sends each [`PrivateKeyFragment`] to its
+     *    [`recipient`](PrivateKeyFragment::recipient).
+     * 5. Each party calls
+     *    [`combine_private_key_fragments`](Self::combine_private_key_fragments)
+     *    with the fragments it received to get its [`ThresholdPrivateKey`].
+     *    It may then discard its [`KeyShare`].
+     *
+     * To decrypt, each of a set of at least `threshold` parties calls
+     * [`partial_decrypt`](Self::partial_decrypt) and anyone combines the
+     * results with [`threshold_decrypt`](Self::threshold_decrypt).
+     *
+     * The joint keys are the sums of every party's keys, so their noise
+     * is roughly `parties` times larger than that of a single party's key.
+     * Ciphertexts under a joint public key thus start with about
+     * `log2(parties)` fewer bits of noise budget, and relinearization adds
+     * more noise than usual. Programs should leave some slack in their
+     * noise budget when compiled for use with threshold keys.
+     *
+     * When `galois_steps` is `None`, the joint public key has Galois keys
+     * for every power of 2 rotation. Otherwise, it only has keys for the
+     * given steps (e.g. those in [`FheProgramMetadata::galois_steps`]), and
+     * none if the list is empty. Every party must pass the same steps.
+     *
+     * This implements the passively secure protocol, which assumes every
+     * party follows it. A malicious party can make key generation or
+     * decryption fail.
+     */
+    pub fn generate_key_share(
+        &self,
+        config: &ThresholdConfig,
+        party: u32,
+        galois_steps: Option<Vec<i32>>,
+    ) -> Result<(KeyShare, PublicKeyShare)> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match &fhe_data.context {
+            Context::Seal(context) => threshold::generate_key_share(
+                &fhe_data.params,
+                context,
+                config,
+                party,
+                galois_steps,
+            ),
+        }
+    }
+
+    /**
+     * Generates a party's share of the joint relinearization keys from the
+     * [`PublicKeyShare`] of every party, including its own. See
+     * [`generate_key_share`](Self::generate_key_share).
+     */
+    pub fn generate_relin_key_share(
+        &self,
+        key_share: &KeyShare,
+        public_shares: &[PublicKeyShare],
+    ) -> Result<RelinKeyShare> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match &fhe_data.context {
+            Context::Seal(context) => threshold::generate_relin_key_share(
+                &fhe_data.params,
+                context,
+                key_share,
+                public_shares,
+            ),
+        }
+    }
+
+    /**
+     * Combines the [`PublicKeyShare`] and [`RelinKeyShare`] of every party
+     * into the joint public key. See
+     * [`generate_key_share`](Self::generate_key_share).
+     *
+     * # Remarks
+     * Returns [`Error::MissingKeyShares`] if some party's shares are
+     * missing. When the parameters have a single prime, there are no
+     * relinearization keys and `relin_shares` is ignored.
+     */
+    pub fn combine_public_key_shares(
+        &self,
+        config: &ThresholdConfig,
+        public_shares: &[PublicKeyShare],
+        relin_shares: &[RelinKeyShare],
+    ) -> Result<PublicKey> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match &fhe_data.context {
+            Context::Seal(context) => threshold::combine_public_key_shares(
+                &fhe_data.params,
+                context,
+                config,
+                public_shares,
+                relin_shares,
+            ),
+        }
+    }
+
+    /**
+     * Splits a party's [`KeyShare`] into a [`PrivateKeyFragment`] for each
+     * party, including itself. See
+     * [`generate_key_share`](Self::generate_key_share).
+     */
+    pub fn split_key_share(&self, key_share: &KeyShare) -> Result<Vec<PrivateKeyFragment>> {
+        threshold::split_key_share(&self.runtime_data.unwrap_fhe().params, key_share)
+    }
+
+    /**
+     * Combines the [`PrivateKeyFragment`] every party sent to the owner of
+     * `key_share` into its [`ThresholdPrivateKey`]. See
+     * [`generate_key_share`](Self::generate_key_share).
+     */
+    pub fn combine_private_key_fragments(
+        &self,
+        key_share: &KeyShare,
+        fragments: &[PrivateKeyFragment],
+    ) -> Result<ThresholdPrivateKey> {
+        threshold::combine_private_key_fragments(
+            &self.runtime_data.unwrap_fhe().params,
+            key_share,
+            fragments,
+        )
+    }
+
+    /**
+     * Computes this party's partial decryption of `ciphertext` for
+     * decrypting together with `parties`, which must include this party
+     * and number at least the threshold.
+     *
+     * # Remarks
+     * Every party in `parties` must use the same list, and
+     * [`threshold_decrypt`](Self::threshold_decrypt) needs a partial
+     * decryption from each of them.
+     *
+     * Each partial decryption includes smudging noise `2^λ` times larger
+     * than the ciphertext's noise, where `λ` is
+     * [`SMUDGING_SECURITY_BITS`](crate::SMUDGING_SECURITY_BITS). The
+     * caller bounds the ciphertext's noise with `noise_budget`, a lower
+     * bound on its remaining noise budget in bits (e.g. the additional
+     * noise budget its FHE program was compiled with). Returns [`Error::TooMuchNoise`] if the smudging
+     * noise doesn't fit in that budget, which needs somewhat more than
+     * `λ + log2(parties)` bits.
+     */
+    pub fn partial_decrypt(
+        &self,
+        ciphertext: &Ciphertext,
+        private_key: &ThresholdPrivateKey,
+        parties: &[u32],
+        noise_budget: u32,
+    ) -> Result<PartialDecryption> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match (&fhe_data.context, &ciphertext.inner) {
+            (Context::Seal(context), InnerCiphertext::Seal(ciphertexts)) => {
+                if ciphertexts.iter().any(|c| c.params != fhe_data.params) {
+                    return Err(Error::ParameterMismatch);
                 }
+
+                let ciphertexts = ciphertexts.iter().map(|c| &c.data).collect::<Vec<_>>();
+
+                threshold::partial_decrypt(
+                    &fhe_data.params,
+                    context,
+                    &ciphertexts,
+                    private_key,
+                    parties,
+                    noise_budget,
+                )
             }
-        };
+        }
+    }
+
+    /**
+     * Decrypts the given ciphertext into the type P by combining partial
+     * decryptions from every party in the set passed to
+     * [`partial_decrypt`](Self::partial_decrypt).
+     *
+     * # Remarks
+     * Returns [`Error::InsufficientPartialDecryptions`] if a partial
+     * decryption is missing or the set has fewer than `threshold` parties,
+     * and [`Error::TooMuchNoise`] if the ciphertext and smudging noise
+     * together exceed the noise budget.
+     */
+    pub fn threshold_decrypt<P>(
+        &self,
+        ciphertext: &Ciphertext,
+        partial_decryptions: &[PartialDecryption],
+    ) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        check_ciphertext_type::<P>(ciphertext)?;
 
-        Ok(ciphertext)
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match (&fhe_data.context, &ciphertext.inner) {
+            (Context::Seal(_), InnerCiphertext::Seal(ciphertexts)) => {
+                let ciphertexts = ciphertexts.iter().map(|c| &c.data).collect::<Vec<_>>();
+
+                let plaintexts = threshold::combine_partial_decryptions(
+                    &fhe_data.params,
+                    &ciphertexts,
+                    partial_decryptions,
+                )?;
+
+                self.plaintexts_into(plaintexts)
+            }
+        }
     }
 }
 
@@ -434,7 +1185,8 @@ where
     B: ZkpBackend,
 {
     /**
-     * Prove the given `inputs` satisfy `program`.
+     * Prove the given `inputs` satisfy `program`. The returned proof
+     * carries the values `program` returns.
      */
     pub fn prove<I>(
         &self,
@@ -442,7 +1194,7 @@ where
         constant_inputs: Vec<I>,
         public_inputs: Vec<I>,
         private_inputs: Vec<I>,
-    ) -> Result<Proof>
+    ) -> Result<ZkpProof>
     where
         I: Into<ZkpProgramInput>,
     {
@@ -465,7 +1217,7 @@ where
 
         let now = Instant::now();
 
-        let prog =
+        let (prog, outputs) =
             backend.jit_prover(program, &constant_inputs, &public_inputs, &private_inputs)?;
 
         trace!("Prover JIT time {}s", now.elapsed().as_secs_f64());
@@ -474,19 +1226,25 @@ where
 
         trace!("Starting backend prove...");
 
-        Ok(backend.prove(&prog, &inputs)?)
+        let proof = backend.prove(&prog, &inputs)?;
+
+        Ok(ZkpProof { proof, outputs })
     }
 
     /**
      * Verify that the given `proof` satisfies the given `program`.
+     *
+     * # Remarks
+     * On success, returns the values `program` returns. Decode them with
+     * [`ZkpOutputs::get`].
      */
     pub fn verify<I>(
         &self,
         program: &CompiledZkpProgram,
-        proof: &Proof,
+        proof: &ZkpProof,
         constant_inputs: Vec<I>,
         public_inputs: Vec<I>,
-    ) -> Result<()>
+    ) -> Result<ZkpOutputs>
     where
         I: Into<ZkpProgramInput>,
     {
@@ -505,12 +1263,141 @@ where
 
         let now = Instant::now();
 
-        let prog = backend.jit_verifier(program, &constant_inputs, &public_inputs)?;
+        let prog =
+            backend.jit_verifier(program, &constant_inputs, &public_inputs, &proof.outputs)?;
 
         trace!("Verifier JIT time {}s", now.elapsed().as_secs_f64());
         trace!("Starting backend verify...");
 
-        Ok(backend.verify(&prog, proof)?)
+        backend.verify(&prog, &proof.proof)?;
+
+        Ok(ZkpOutputs::new(proof.outputs.clone()))
+    }
+
+    /**
+     * Returns generators that can prove and verify circuits with up to
+     * `capacity` multiplication gates, creating them if this runtime's
+     * backend hasn't cached a large enough set.
+     *
+     * # Remarks
+     * Pass the result to another runtime's
+     * [`prove_with_generators`](Self::prove_with_generators) or
+     * [`verify_with_generators`](Self::verify_with_generators) to share
+     * precomputed generators.
+     */
+    pub fn generators(&self, capacity: usize) -> B::Generators {
+        self.zkp_backend.generators(capacity)
+    }
+
+    /**
+     * Like [`prove`](Self::prove), but first adds the given generators to
+     * the backend's cache.
+     *
+     * # Remarks
+     * The runtime keeps the generators, so later proofs and verifications
+     * of programs they fit reuse them. If `generators` are too small for
+     * `program`, the backend creates and caches a larger set.
+     */
+    pub fn prove_with_generators<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        generators: &B::Generators,
+        constant_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+        private_inputs: Vec<I>,
+    ) -> Result<ZkpProof>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.zkp_backend.add_generators(generators.clone());
+
+        self.prove(program, constant_inputs, public_inputs, private_inputs)
+    }
+
+    /**
+     * Like [`verify`](Self::verify), but first adds the given generators
+     * to the backend's cache.
+     *
+     * # Remarks
+     * See [`prove_with_generators`](Self::prove_with_generators).
+     */
+    pub fn verify_with_generators<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        proof: &ZkpProof,
+        generators: &B::Generators,
+        constant_inputs: Vec<I>,
+        public_inputs: Vec<I>,
+    ) -> Result<ZkpOutputs>
+    where
+        I: Into<ZkpProgramInput>,
+    {
+        self.zkp_backend.add_generators(generators.clone());
+
+        self.verify(program, proof, constant_inputs, public_inputs)
+    }
+
+    /**
+     * Verify that each proof in `batch` satisfies `program` with its
+     * accompanying public inputs. All proofs share the given
+     * `constant_inputs`.
+     *
+     * # Remarks
+     * If any proof fails, returns a
+     * [`BatchVerificationFailed`](sunscreen_zkp_backend::Error::BatchVerificationFailed)
+     * error containing the index of the offending proof. Otherwise,
+     * returns each proof's outputs.
+     *
+     * Proofs are verified one at a time, so this is no faster than
+     * calling [`verify`](Self::verify) on each of them.
+     */
+    pub fn verify_batch<I>(
+        &self,
+        program: &CompiledZkpProgram,
+        constant_inputs: Vec<I>,
+        batch: &[(ZkpProof, Vec<I>)],
+    ) -> Result<Vec<ZkpOutputs>>
+    where
+        I: Into<ZkpProgramInput> + Clone,
+    {
+        let constant_inputs = constant_inputs
+            .into_iter()
+            .flat_map(|x| I::into(x).0.to_native_fields())
+            .collect::<Vec<BigInt>>();
+
+        let backend = &self.zkp_backend;
+
+        trace!("Starting JIT (batch verifier)");
+
+        let now = Instant::now();
+
+        let jitted = batch
+            .iter()
+            .enumerate()
+            .map(|(i, (proof, public_inputs))| {
+                let public_inputs = public_inputs
+                    .iter()
+                    .cloned()
+                    .flat_map(|x| I::into(x).0.to_native_fields())
+                    .collect::<Vec<BigInt>>();
+
+                let prog = backend
+                    .jit_verifier(program, &constant_inputs, &public_inputs, &proof.outputs)
+                    .map_err(|e| ZkpError::batch_verification_failed(i, e))?;
+
+                Ok::<_, Error>((prog, &proof.proof))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        trace!("Verifier JIT time {}s", now.elapsed().as_secs_f64());
+        trace!("Starting backend batch verify...");
+
+        backend.verify_batch(&jitted)?;
+
+        Ok(batch
+            .iter()
+            .map(|(proof, _)| ZkpOutputs::new(proof.outputs.clone()))
+            .collect())
     }
 }
 
@@ -546,6 +1433,7 @@ impl GenericRuntime<(), ()> {
                 Ok(FheRuntimeData {
                     params: params.clone(),
                     context: Context::Seal(context),
+                    run_options: RunOptions::default(),
                 })
             }
         }
@@ -602,6 +1490,17 @@ impl GenericRuntime<(), ()> {
 
 /**
  * A runtime capable of both FHE and ZKP operations.
+ *
+ * # Remarks
+ * This runtime does not yet prove that a ciphertext is a well-formed BFV
+ * encryption. Such a proof (e.g. a `logproof` Short Discrete Log Proof
+ * of `(c0, c1) = (p0 * u + e1 + Δm, p1 * u + e2)`) needs the ephemeral
+ * key `u` and the noise polynomials `e1`, `e2` chosen during encryption,
+ * along with the RNS coefficients of the public key and ciphertext.
+ * `seal_fhe::Encryptor::encrypt` only returns the finished ciphertext and
+ * neither `seal_fhe::PublicKey` nor `seal_fhe::Ciphertext` expose
+ * their coefficients, so the SEAL bindings must first grow an
+ * encryption entry point that returns these components.
  */
 pub type FheZkpRuntime<B> = GenericRuntime<FheZkp, B>;
 
@@ -28,6 +28,26 @@ where
     pub data: T,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename = "WithContext")]
+/**
+ * The serialized form of SEAL data that stores a random number seed in
+ * place of some of its contents (e.g. a compact key or ciphertext). This
+ * serializes the same way as a [`WithContext`], so deserializing it as one
+ * expands the seed.
+ */
+pub(crate) struct CompactWithContext {
+    /**
+     * The scheme parameters under which this data is valid.
+     */
+    pub params: Params,
+
+    /**
+     * The serialized SEAL data.
+     */
+    pub data: Vec<u8>,
+}
+
 impl<T> std::ops::Deref for WithContext<T>
 where
     T: ToBytes + FromBytes + PartialEq,
@@ -177,7 +197,9 @@ where
         .set_poly_modulus_degree(params.lattice_dimension)
         .build()?;
 
-    let seal_context = Context::new(&encryption_params, false, params.security_level)?;
+    // Expand the modulus switching chain so we can deserialize ciphertexts
+    // that have been modulus switched.
+    let seal_context = Context::new(&encryption_params, true, params.security_level)?;
 
     let data = T::from_bytes(&seal_context, data)?;
 
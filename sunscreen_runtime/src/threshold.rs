@@ -0,0 +1,1102 @@
+use merlin::Transcript;
+use num::{BigInt, BigUint, Integer, One, ToPrimitive, Zero};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use seal_fhe::{
+    BFVEvaluator, Ciphertext as SealCiphertext, Context as SealContext, Evaluator, GaloisKeys,
+    KeyGenerator, Plaintext as SealPlaintext, SecretKey as SealSecretKey,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::serialization::WithContext;
+use crate::{Error, Params, PublicKey, Result};
+
+/**
+ * The label every party seeds the transcript deriving the common random
+ * polynomials with.
+ */
+const CRS_LABEL: &[u8] = b"sunscreen-threshold-crs";
+
+/**
+ * The statistical security parameter `λ`, in bits, of the smudging noise
+ * in each [`PartialDecryption`].
+ *
+ * # Remarks
+ * Each party's smudging noise is `2^λ` times larger than a bound on the
+ * ciphertext's noise, so a partial decryption's distribution is within
+ * statistical distance about `2^-λ` of one that doesn't depend on the
+ * ciphertext's noise.
+ */
+pub const SMUDGING_SECURITY_BITS: u32 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+/**
+ * The parameters every party in a threshold key generation must agree on.
+ */
+pub struct ThresholdConfig {
+    /**
+     * The number of parties holding a share of the private key. Parties
+     * are numbered `0..parties`.
+     */
+    pub parties: u32,
+
+    /**
+     * The number of parties that must take part to decrypt a ciphertext.
+     */
+    pub threshold: u32,
+
+    /**
+     * A seed from which the parties derive the common random polynomials
+     * in their key shares. This need not be secret, but parties must agree
+     * on it before generating their shares and no party should be able to
+     * choose it alone (e.g. derive it from a commit-then-reveal of random
+     * values from every party).
+     */
+    pub seed: [u8; 32],
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * A party's secret share of a threshold key. Never share this with other
+ * parties.
+ */
+pub struct KeyShare {
+    pub(crate) config: ThresholdConfig,
+    pub(crate) party: u32,
+    pub(crate) secret: WithContext<SealSecretKey>,
+    pub(crate) ephemeral: Option<WithContext<SealSecretKey>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/**
+ * A party's contribution to the joint public key, which it sends to every
+ * other party.
+ */
+pub struct PublicKeyShare {
+    pub(crate) config: ThresholdConfig,
+    pub(crate) party: u32,
+    pub(crate) public_key: Vec<u64>,
+    pub(crate) relin_keys: Option<Vec<(Vec<u64>, Vec<u64>)>>,
+    pub(crate) galois_steps: Option<Vec<i32>>,
+    pub(crate) galois_keys: Vec<Vec<Vec<u64>>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/**
+ * A party's contribution to the joint relinearization keys, created from
+ * every party's [`PublicKeyShare`].
+ */
+pub struct RelinKeyShare {
+    pub(crate) config: ThresholdConfig,
+    pub(crate) party: u32,
+    pub(crate) relin_keys: Vec<Vec<u64>>,
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * A Shamir share of one party's [`KeyShare`], addressed to another party.
+ * Send this only to its recipient over a private channel.
+ */
+pub struct PrivateKeyFragment {
+    pub(crate) config: ThresholdConfig,
+    pub(crate) from: u32,
+    pub(crate) to: u32,
+    pub(crate) data: Vec<u64>,
+}
+
+impl PrivateKeyFragment {
+    /**
+     * The party this fragment should be sent to.
+     */
+    pub fn recipient(&self) -> u32 {
+        self.to
+    }
+}
+
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * A party's share of the private key for a joint public key. Any
+ * `threshold` parties can decrypt together, while fewer can't.
+ */
+pub struct ThresholdPrivateKey {
+    pub(crate) config: ThresholdConfig,
+    pub(crate) party: u32,
+    pub(crate) data: Vec<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/**
+ * A party's partial decryption of a ciphertext.
+ */
+pub struct PartialDecryption {
+    pub(crate) party: u32,
+    pub(crate) parties: Vec<u32>,
+    pub(crate) threshold: u32,
+    pub(crate) data: Vec<Vec<u64>>,
+}
+
+/**
+ * Creates a party's key share and the public share it sends to the other
+ * parties. See [`create_galois_keys`] for the meaning of `galois_steps`.
+ */
+pub(crate) fn generate_key_share(
+    params: &Params,
+    context: &SealContext,
+    config: &ThresholdConfig,
+    party: u32,
+    galois_steps: Option<Vec<i32>>,
+) -> Result<(KeyShare, PublicKeyShare)> {
+    validate_config(config)?;
+
+    if party >= config.parties {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    let moduli = &params.coeff_modulus;
+    let keygen = KeyGenerator::new(context)?;
+    let secret = keygen.secret_key();
+    let s = secret.data()?;
+
+    let a = common_random_poly(params, config, b"public-key", &[]);
+    let public_key = add(
+        &neg(&mul(&a, &s, moduli), moduli),
+        &noise(&keygen, &s, moduli)?,
+        moduli,
+    );
+
+    // SEAL can't switch keys without a special prime, so parameters with
+    // a single prime don't get relinearization or Galois keys.
+    let (ephemeral, relin_keys) = if moduli.len() > 1 {
+        let ephemeral = KeyGenerator::new(context)?.secret_key();
+        let u = ephemeral.data()?;
+
+        let relin_keys = (0..moduli.len() - 1)
+            .map(|j| {
+                let a = common_random_poly(params, config, b"relin", &[j as u64]);
+
+                let h0 = add(
+                    &neg(&mul(&u, &a, moduli), moduli),
+                    &gadget(params, &s, j),
+                    moduli,
+                );
+                let h0 = add(&h0, &noise(&keygen, &s, moduli)?, moduli);
+                let h1 = add(&mul(&s, &a, moduli), &noise(&keygen, &s, moduli)?, moduli);
+
+                Ok((h0, h1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let ephemeral = WithContext {
+            params: params.clone(),
+            data: ephemeral,
+        };
+
+        (Some(ephemeral), Some(relin_keys))
+    } else {
+        (None, None)
+    };
+
+    // Re-randomize each of our own Galois keys (g0, g1), where
+    // g0 + g1 * s = gadget * galois(s) + e, to use the common random
+    // polynomial a in place of g1.
+    let galois_keys = match create_galois_keys(&keygen, &galois_steps)? {
+        Some(keys) => keys
+            .key_lists()?
+            .iter()
+            .enumerate()
+            .map(|(idx, keys)| {
+                keys.iter()
+                    .enumerate()
+                    .map(|(j, key)| {
+                        let data = key.data()?;
+                        let (g0, g1) = data.split_at(data.len() / 2);
+                        let a =
+                            common_random_poly(params, config, b"galois", &[idx as u64, j as u64]);
+
+                        Ok(add(g0, &mul(&sub(g1, &a, moduli), &s, moduli), moduli))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => vec![],
+    };
+
+    let key_share = KeyShare {
+        config: *config,
+        party,
+        secret: WithContext {
+            params: params.clone(),
+            data: secret,
+        },
+        ephemeral,
+    };
+
+    let public_share = PublicKeyShare {
+        config: *config,
+        party,
+        public_key,
+        relin_keys,
+        galois_steps,
+        galois_keys,
+    };
+
+    Ok((key_share, public_share))
+}
+
+/**
+ * Creates a party's share of the relinearization keys from every party's
+ * public share.
+ *
+ * # Remarks
+ * With `h0 = sum(h0_i) = -u * a + s * g` and `h1 = sum(h1_i) = s * a`
+ * (ignoring noise), where `s` and `u` are the sums of every party's
+ * secret and ephemeral keys, party `i` returns
+ * `r_i = s_i * h0 + (u_i - s_i) * h1`. Summing these gives
+ * `-s^2 * a + s^2 * g`, so `(sum(r_i), h1)` is a key switching key from
+ * `s^2` to `s`.
+ */
+pub(crate) fn generate_relin_key_share(
+    params: &Params,
+    context: &SealContext,
+    key_share: &KeyShare,
+    public_shares: &[PublicKeyShare],
+) -> Result<RelinKeyShare> {
+    check_shares(&key_share.config, public_shares, |s| (&s.config, s.party))?;
+
+    let relin_keys = match &key_share.ephemeral {
+        Some(ephemeral) => {
+            let moduli = &params.coeff_modulus;
+            let keygen = KeyGenerator::new_from_secret_key(context, &key_share.secret)?;
+            let s = key_share.secret.data()?;
+            let u_minus_s = sub(&ephemeral.data()?, &s, moduli);
+
+            (0..moduli.len() - 1)
+                .map(|j| {
+                    let (h0, h1) = sum_relin_round_1(params, public_shares, j)?;
+
+                    let r = add(&mul(&s, &h0, moduli), &mul(&u_minus_s, &h1, moduli), moduli);
+
+                    Ok(add(&r, &noise(&keygen, &s, moduli)?, moduli))
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        None => vec![],
+    };
+
+    Ok(RelinKeyShare {
+        config: key_share.config,
+        party: key_share.party,
+        relin_keys,
+    })
+}
+
+/**
+ * Combines every party's public shares into the joint public key.
+ */
+pub(crate) fn combine_public_key_shares(
+    params: &Params,
+    context: &SealContext,
+    config: &ThresholdConfig,
+    public_shares: &[PublicKeyShare],
+    relin_shares: &[RelinKeyShare],
+) -> Result<PublicKey> {
+    validate_config(config)?;
+    check_shares(config, public_shares, |s| (&s.config, s.party))?;
+
+    // Every party must create Galois keys for the same steps.
+    let galois_steps = &public_shares[0].galois_steps;
+
+    if public_shares
+        .iter()
+        .any(|s| &s.galois_steps != galois_steps)
+    {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    // We overwrite the data in keys from a throwaway generator, which gives
+    // SEAL's key metadata (e.g. the NTT form and parameter ids) for free.
+    let keygen = KeyGenerator::new(context)?;
+
+    let b = sum(
+        params,
+        public_shares.iter().map(|s| s.public_key.as_slice()),
+    )?;
+    let a = common_random_poly(params, config, b"public-key", &[]);
+
+    let mut public_key = keygen.create_public_key();
+    public_key.set_data(&[b, a].concat())?;
+
+    let relin_key = if public_shares.iter().any(|s| s.relin_keys.is_some()) {
+        check_shares(config, relin_shares, |s| (&s.config, s.party))?;
+
+        let mut keys = keygen.create_relinearization_keys()?;
+        let mut key_lists = keys.key_lists()?;
+
+        for (j, key) in key_lists[0].iter_mut().enumerate() {
+            let (_, h1) = sum_relin_round_1(params, public_shares, j)?;
+            let r = sum(
+                params,
+                relin_shares
+                    .iter()
+                    .map(|s| s.relin_keys.get(j).map(Vec::as_slice).unwrap_or_default()),
+            )?;
+
+            key.set_data(&[r, h1].concat())?;
+        }
+
+        keys.set_key_lists(&key_lists)?;
+
+        Some(keys)
+    } else {
+        None
+    };
+
+    let galois_key = match create_galois_keys(&keygen, galois_steps)? {
+        Some(mut keys) => {
+            let mut key_lists = keys.key_lists()?;
+
+            for (idx, keys) in key_lists.iter_mut().enumerate() {
+                for (j, key) in keys.iter_mut().enumerate() {
+                    let g0 = sum(
+                        params,
+                        public_shares.iter().map(|s| {
+                            s.galois_keys
+                                .get(idx)
+                                .and_then(|k| k.get(j))
+                                .map(Vec::as_slice)
+                                .unwrap_or_default()
+                        }),
+                    )?;
+                    let a = common_random_poly(params, config, b"galois", &[idx as u64, j as u64]);
+
+                    key.set_data(&[g0, a].concat())?;
+                }
+            }
+
+            keys.set_key_lists(&key_lists)?;
+
+            Some(keys)
+        }
+        None => None,
+    };
+
+    Ok(PublicKey {
+        public_key: WithContext {
+            params: params.clone(),
+            data: public_key,
+        },
+        galois_key: galois_key.map(|data| WithContext {
+            params: params.clone(),
+            data,
+        }),
+        relin_key: relin_key.map(|data| WithContext {
+            params: params.clone(),
+            data,
+        }),
+    })
+}
+
+/**
+ * Splits a party's secret key into one Shamir share for each party,
+ * including itself.
+ */
+pub(crate) fn split_key_share(
+    params: &Params,
+    key_share: &KeyShare,
+) -> Result<Vec<PrivateKeyFragment>> {
+    let moduli = &params.coeff_modulus;
+    let n = params.lattice_dimension as usize;
+    let s = key_share.secret.data()?;
+
+    // The sharing polynomial's constant coefficient is the secret, so we
+    // only sample the others.
+    let mut rng = thread_rng();
+    let coeffs = (1..key_share.config.threshold)
+        .map(|_| uniform_poly(&mut rng, moduli, n))
+        .collect::<Vec<_>>();
+
+    let fragments = (0..key_share.config.parties)
+        .map(|to| {
+            let x = to as u64 + 1;
+
+            let data = s
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    let q = moduli[i / n];
+
+                    let c = coeffs
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, c| add_mod(mul_mod(acc, x, q), c[i], q));
+
+                    add_mod(mul_mod(c, x, q), *s, q)
+                })
+                .collect();
+
+            PrivateKeyFragment {
+                config: key_share.config,
+                from: key_share.party,
+                to,
+                data,
+            }
+        })
+        .collect();
+
+    Ok(fragments)
+}
+
+/**
+ * Sums the fragments every party sent to the owner of `key_share` into its
+ * threshold private key.
+ */
+pub(crate) fn combine_private_key_fragments(
+    params: &Params,
+    key_share: &KeyShare,
+    fragments: &[PrivateKeyFragment],
+) -> Result<ThresholdPrivateKey> {
+    check_shares(&key_share.config, fragments, |f| (&f.config, f.from))?;
+
+    if fragments.iter().any(|f| f.to != key_share.party) {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    Ok(ThresholdPrivateKey {
+        config: key_share.config,
+        party: key_share.party,
+        data: sum(params, fragments.iter().map(|f| f.data.as_slice()))?,
+    })
+}
+
+/**
+ * Computes `l * s * c1 + e` for each of the given ciphertexts, where `s`
+ * is the party's share of the private key, `l` is its Lagrange coefficient
+ * among `parties` and `e` is smudging noise. See [`smudging_bound`] for the
+ * meaning of `noise_budget`.
+ */
+pub(crate) fn partial_decrypt(
+    params: &Params,
+    context: &SealContext,
+    ciphertexts: &[&SealCiphertext],
+    private_key: &ThresholdPrivateKey,
+    parties: &[u32],
+    noise_budget: u32,
+) -> Result<PartialDecryption> {
+    let config = &private_key.config;
+    let mut parties = parties.to_owned();
+    parties.sort_unstable();
+    parties.dedup();
+
+    if parties.iter().any(|p| *p >= config.parties) || !parties.contains(&private_key.party) {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    if parties.len() < config.threshold as usize {
+        return Err(Error::InsufficientPartialDecryptions);
+    }
+
+    let n = params.lattice_dimension as usize;
+    let evaluator = BFVEvaluator::new(context)?;
+    let mut rng = thread_rng();
+
+    let data = ciphertexts
+        .iter()
+        .map(|c| {
+            if c.num_polynomials() != 2 {
+                return Err(Error::ParameterMismatch);
+            }
+
+            let k = c.coeff_modulus_size() as usize;
+            let moduli = &params.coeff_modulus[..k];
+
+            let lambda = moduli
+                .iter()
+                .map(|q| lagrange_coefficient(private_key.party, &parties, *q))
+                .collect::<Vec<_>>();
+
+            // The private key is in NTT form, so multiply c1 by it there.
+            let mut c_ntt = evaluator.transform_to_ntt(c)?;
+            let c_data = c_ntt.data()?;
+
+            let d = c_data[k * n..]
+                .iter()
+                .zip(&private_key.data)
+                .enumerate()
+                .map(|(i, (c, s))| {
+                    let q = moduli[i / n];
+
+                    mul_mod(mul_mod(*c, *s, q), lambda[i / n], q)
+                })
+                .collect::<Vec<_>>();
+
+            c_ntt.set_data(&[d.as_slice(), &d].concat())?;
+
+            let d = evaluator.transform_from_ntt(&c_ntt)?.data()?;
+
+            let bound = smudging_bound(moduli, params.plain_modulus, parties.len(), noise_budget)?;
+            let noise = (0..n)
+                .map(|_| sample_centered(&mut rng, &bound))
+                .collect::<Vec<_>>();
+
+            Ok(d[..k * n]
+                .iter()
+                .enumerate()
+                .map(|(i, d)| {
+                    let q = moduli[i / n];
+                    let e = noise[i % n].mod_floor(&BigInt::from(q));
+
+                    // mod_floor returns a value in [0, q).
+                    add_mod(*d, e.to_u64().unwrap(), q)
+                })
+                .collect())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(PartialDecryption {
+        party: private_key.party,
+        parties,
+        threshold: config.threshold,
+        data,
+    })
+}
+
+/**
+ * Adds the partial decryptions to `c0` of each ciphertext to get its phase
+ * `c0 + s * c1 = Δm + e` and decodes the result into a plaintext.
+ */
+pub(crate) fn combine_partial_decryptions(
+    params: &Params,
+    ciphertexts: &[&SealCiphertext],
+    partial_decryptions: &[PartialDecryption],
+) -> Result<Vec<SealPlaintext>> {
+    let first = partial_decryptions
+        .first()
+        .ok_or(Error::InsufficientPartialDecryptions)?;
+
+    if partial_decryptions
+        .iter()
+        .any(|p| p.parties != first.parties || p.threshold != first.threshold)
+    {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    let mut present = partial_decryptions
+        .iter()
+        .map(|p| p.party)
+        .collect::<Vec<_>>();
+    present.sort_unstable();
+
+    if present != first.parties || present.len() < first.threshold as usize {
+        return Err(Error::InsufficientPartialDecryptions);
+    }
+
+    if partial_decryptions
+        .iter()
+        .any(|p| p.data.len() != ciphertexts.len())
+    {
+        return Err(Error::ParameterMismatch);
+    }
+
+    let n = params.lattice_dimension as usize;
+
+    ciphertexts
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| {
+            let k = c.coeff_modulus_size() as usize;
+            let level = Params {
+                coeff_modulus: params.coeff_modulus[..k].to_owned(),
+                ..params.clone()
+            };
+
+            let data = c.data()?;
+            let phase = sum(
+                &level,
+                std::iter::once(&data[..k * n])
+                    .chain(partial_decryptions.iter().map(|p| p.data[idx].as_slice())),
+            )?;
+
+            decode_phase(&level, &phase)
+        })
+        .collect()
+}
+
+/**
+ * Decodes a phase `Δm + e` in RNS form into the plaintext `m`, as BFV
+ * decryption does. Each coefficient of `m` is `round(t * x / q) mod t`,
+ * where `x` is the coefficient of the phase in `[0, q)`.
+ *
+ * # Remarks
+ * Returns [`Error::TooMuchNoise`] if the phase has no noise budget left,
+ * using the same measure as SEAL's `invariant_noise_budget`.
+ */
+fn decode_phase(params: &Params, phase: &[u64]) -> Result<SealPlaintext> {
+    let moduli = &params.coeff_modulus;
+    let n = params.lattice_dimension as usize;
+    let t = BigUint::from(params.plain_modulus);
+    let q = moduli
+        .iter()
+        .map(|q| BigUint::from(*q))
+        .product::<BigUint>();
+
+    // x = sum(x_i * (q / q_i) * ((q / q_i)^-1 mod q_i)) mod q.
+    let crt = moduli
+        .iter()
+        .map(|q_i| {
+            let q_hat = &q / q_i;
+            let q_hat_mod = (&q_hat % q_i).to_u64().unwrap_or_default();
+
+            q_hat * pow_mod(q_hat_mod, q_i - 2, *q_i)
+        })
+        .collect::<Vec<_>>();
+
+    let mut plaintext = SealPlaintext::new()?;
+    plaintext.resize(n);
+
+    let mut max_noise = BigUint::zero();
+
+    for j in 0..n {
+        let x = crt
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c * phase[i * n + j])
+            .sum::<BigUint>()
+            % &q;
+
+        let scaled = &t * x;
+        let m: BigUint = ((&scaled + (&q >> 1)) / &q) % &t;
+
+        // t * x = q * m + t * e, where the invariant noise t * e lies in
+        // (-q / 2, q / 2].
+        let noise = &scaled % &q;
+        let noise = std::cmp::min(noise.clone(), &q - noise);
+        max_noise = max_noise.max(noise);
+
+        // m < t, which fits in a u64.
+        plaintext.set_coefficient(j, m.to_u64().unwrap_or_default());
+    }
+
+    if q.bits() <= max_noise.bits() + 1 {
+        return Err(Error::TooMuchNoise);
+    }
+
+    Ok(plaintext)
+}
+
+fn validate_config(config: &ThresholdConfig) -> Result<()> {
+    if config.threshold == 0 || config.threshold > config.parties {
+        return Err(Error::InvalidThresholdParams);
+    }
+
+    Ok(())
+}
+
+/**
+ * Checks `shares` contains exactly one share from each party, all created
+ * under `config`.
+ */
+fn check_shares<T>(
+    config: &ThresholdConfig,
+    shares: &[T],
+    id: impl Fn(&T) -> (&ThresholdConfig, u32),
+) -> Result<()> {
+    let mut seen = vec![false; config.parties as usize];
+
+    for share in shares {
+        let (share_config, party) = id(share);
+
+        if share_config != config || party >= config.parties || seen[party as usize] {
+            return Err(Error::InvalidThresholdParams);
+        }
+
+        seen[party as usize] = true;
+    }
+
+    if seen.contains(&false) {
+        return Err(Error::MissingKeyShares);
+    }
+
+    Ok(())
+}
+
+/**
+ * Returns `(sum(h0_i), sum(h1_i))` for the `j`th relinearization key.
+ */
+fn sum_relin_round_1(
+    params: &Params,
+    public_shares: &[PublicKeyShare],
+    j: usize,
+) -> Result<(Vec<u64>, Vec<u64>)> {
+    let keys = public_shares
+        .iter()
+        .map(|s| {
+            s.relin_keys
+                .as_ref()
+                .and_then(|k| k.get(j))
+                .ok_or(Error::ParameterMismatch)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((
+        sum(params, keys.iter().map(|k| k.0.as_slice()))?,
+        sum(params, keys.iter().map(|k| k.1.as_slice()))?,
+    ))
+}
+
+/**
+ * Derives a uniformly random polynomial in NTT form modulo each prime from
+ * the config's seed, the label and the indices.
+ */
+fn common_random_poly(
+    params: &Params,
+    config: &ThresholdConfig,
+    label: &'static [u8],
+    indices: &[u64],
+) -> Vec<u64> {
+    let mut transcript = Transcript::new(CRS_LABEL);
+    transcript.append_message(b"seed", &config.seed);
+    transcript.append_u64(b"lattice dimension", params.lattice_dimension);
+
+    for q in &params.coeff_modulus {
+        transcript.append_u64(b"modulus", *q);
+    }
+
+    transcript.append_message(b"label", label);
+
+    for i in indices {
+        transcript.append_u64(b"index", *i);
+    }
+
+    let mut seed = [0u8; 32];
+    transcript.challenge_bytes(b"rng seed", &mut seed);
+
+    uniform_poly(
+        &mut ChaCha20Rng::from_seed(seed),
+        &params.coeff_modulus,
+        params.lattice_dimension as usize,
+    )
+}
+
+fn uniform_poly(rng: &mut impl Rng, moduli: &[u64], n: usize) -> Vec<u64> {
+    let mut poly = Vec::with_capacity(moduli.len() * n);
+
+    for q in moduli {
+        for _ in 0..n {
+            poly.push(rng.gen_range(0..*q));
+        }
+    }
+
+    poly
+}
+
+/**
+ * Samples fresh noise in NTT form. Every public key SEAL creates is
+ * `(-(a * s + e), a)` for a fresh `a` and `e`, so we recover `e` from one.
+ */
+fn noise(keygen: &KeyGenerator, s: &[u64], moduli: &[u64]) -> Result<Vec<u64>> {
+    let data = keygen.create_public_key().data()?;
+    let (p0, p1) = data.split_at(data.len() / 2);
+
+    Ok(neg(&add(p0, &mul(p1, s, moduli), moduli), moduli))
+}
+
+/**
+ * Creates Galois keys with `keygen`. When `steps` is `None`, creates keys
+ * for every power of 2 rotation, or none if the parameters don't support
+ * them. When it's an empty list, creates no keys.
+ */
+fn create_galois_keys(
+    keygen: &KeyGenerator,
+    steps: &Option<Vec<i32>>,
+) -> Result<Option<GaloisKeys>> {
+    Ok(match steps {
+        None => keygen.create_galois_keys().ok(),
+        Some(steps) if steps.is_empty() => None,
+        Some(steps) => Some(keygen.create_galois_keys_from_steps(steps)?),
+    })
+}
+
+/**
+ * Returns the `j`th component of the gadget vector SEAL uses in key
+ * switching keys, times `s`. This is `s * (P mod q_j)` modulo the `j`th
+ * prime and 0 modulo the others, where `P` is the special prime.
+ */
+fn gadget(params: &Params, s: &[u64], j: usize) -> Vec<u64> {
+    let moduli = &params.coeff_modulus;
+    let n = params.lattice_dimension as usize;
+    let q = moduli[j];
+    let factor = moduli[moduli.len() - 1] % q;
+
+    let mut out = vec![0; s.len()];
+
+    for i in j * n..(j + 1) * n {
+        out[i] = mul_mod(s[i], factor, q);
+    }
+
+    out
+}
+
+/**
+ * Returns the Lagrange coefficient for evaluating the polynomial through
+ * the parties' shares at 0. Party `i`'s share is the polynomial's value at
+ * `i + 1`.
+ */
+fn lagrange_coefficient(party: u32, parties: &[u32], q: u64) -> u64 {
+    let x = party as u64 + 1;
+
+    let (num, den) = parties
+        .iter()
+        .filter(|p| **p != party)
+        .fold((1, 1), |(num, den), p| {
+            let x_p = *p as u64 + 1;
+
+            (mul_mod(num, x_p, q), mul_mod(den, sub_mod(x_p, x, q), q))
+        });
+
+    // q is prime, so den^(q - 2) is den's inverse.
+    mul_mod(num, pow_mod(den, q - 2, q), q)
+}
+
+/**
+ * Returns the bound `B` on the smudging noise each of `parties` adds to a
+ * partial decryption of a ciphertext with at least `noise_budget` bits of
+ * noise budget left.
+ *
+ * # Remarks
+ * BFV decrypts correctly while the noise is below `q / 2t`, so the
+ * ciphertext's noise is at most `E = q / 2t / 2^noise_budget`. Smudging
+ * noise up to `B = 2^λ * E` hides it, where `λ` is
+ * [`SMUDGING_SECURITY_BITS`]. Returns [`Error::TooMuchNoise`] unless
+ * the ciphertext and every party's smudging noise together stay below
+ * `q / 2t`.
+ */
+fn smudging_bound(
+    moduli: &[u64],
+    plain_modulus: u64,
+    parties: usize,
+    noise_budget: u32,
+) -> Result<BigUint> {
+    let q = moduli
+        .iter()
+        .map(|q| BigUint::from(*q))
+        .product::<BigUint>();
+    let max_noise = q / (2 * plain_modulus);
+
+    let noise = std::cmp::max(&max_noise >> noise_budget, BigUint::one());
+    let bound = &noise << SMUDGING_SECURITY_BITS;
+
+    if noise + &bound * parties >= max_noise {
+        return Err(Error::TooMuchNoise);
+    }
+
+    Ok(bound)
+}
+
+/**
+ * Samples an integer uniformly from `[-bound, bound]`.
+ */
+fn sample_centered(rng: &mut impl Rng, bound: &BigUint) -> BigInt {
+    let range = BigInt::from(2u32 * bound + 1u32);
+
+    // Sampling 64 bits more than the range needs makes the bias from
+    // reducing modulo it negligible.
+    let limbs = (range.bits() / 64 + 2) as usize;
+    let x = BigInt::from(BigUint::new(
+        (0..2 * limbs).map(|_| rng.gen::<u32>()).collect(),
+    ));
+
+    x.mod_floor(&range) - BigInt::from(bound.clone())
+}
+
+fn sum<'a>(params: &Params, polys: impl Iterator<Item = &'a [u64]>) -> Result<Vec<u64>> {
+    let moduli = &params.coeff_modulus;
+    let mut out = vec![0; moduli.len() * params.lattice_dimension as usize];
+
+    for p in polys {
+        if p.len() != out.len() {
+            return Err(Error::ParameterMismatch);
+        }
+
+        out = add(&out, p, moduli);
+    }
+
+    Ok(out)
+}
+
+/**
+ * Applies `f` to each pair of coefficients in `a` and `b` and the prime
+ * they're modulo. Polynomials are in RNS form, so the coefficients modulo
+ * each prime are consecutive.
+ */
+fn zip_with(a: &[u64], b: &[u64], moduli: &[u64], f: impl Fn(u64, u64, u64) -> u64) -> Vec<u64> {
+    let n = a.len() / moduli.len();
+
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .map(|(i, (x, y))| f(*x, *y, moduli[i / n]))
+        .collect()
+}
+
+fn add(a: &[u64], b: &[u64], moduli: &[u64]) -> Vec<u64> {
+    zip_with(a, b, moduli, add_mod)
+}
+
+fn sub(a: &[u64], b: &[u64], moduli: &[u64]) -> Vec<u64> {
+    zip_with(a, b, moduli, sub_mod)
+}
+
+fn mul(a: &[u64], b: &[u64], moduli: &[u64]) -> Vec<u64> {
+    zip_with(a, b, moduli, mul_mod)
+}
+
+fn neg(a: &[u64], moduli: &[u64]) -> Vec<u64> {
+    sub(&vec![0; a.len()], a, moduli)
+}
+
+fn add_mod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 + b as u128) % q as u128) as u64
+}
+
+fn sub_mod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 + q as u128 - b as u128) % q as u128) as u64
+}
+
+fn mul_mod(a: u64, b: u64, q: u64) -> u64 {
+    (a as u128 * b as u128 % q as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, q: u64) -> u64 {
+    let mut out = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            out = mul_mod(out, base, q);
+        }
+
+        base = mul_mod(base, base, q);
+        exp >>= 1;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use seal_fhe::SecurityLevel;
+    use sunscreen_fhe_program::SchemeType;
+
+    use super::*;
+
+    #[test]
+    fn lagrange_coefficients_interpolate_at_0() {
+        let q = 0x7e00001;
+
+        // f(x) = 5 + 3x + 7x^2
+        let f = |x: u64| add_mod(add_mod(5, mul_mod(3, x, q), q), mul_mod(7, x * x, q), q);
+
+        for parties in [[0, 1, 2], [1, 3, 4], [0, 2, 4]] {
+            let secret = parties.iter().fold(0, |acc, p| {
+                let share = f(*p as u64 + 1);
+
+                add_mod(
+                    acc,
+                    mul_mod(share, lagrange_coefficient(*p, &parties, q), q),
+                    q,
+                )
+            });
+
+            assert_eq!(secret, 5);
+        }
+    }
+
+    #[test]
+    fn smudging_bound_scales_ciphertext_noise() {
+        // q = 2^60 - 93, t = 64, so q / 2t is just under 2^53.
+        let q = (1u64 << 60) - 93;
+        let max_noise = q / 128;
+
+        assert_eq!(
+            smudging_bound(&[q], 64, 2, 45).unwrap(),
+            BigUint::from(max_noise >> 45) << SMUDGING_SECURITY_BITS
+        );
+
+        // The smudging noise doesn't fit in the noise budget.
+        assert_eq!(
+            smudging_bound(&[q], 64, 2, SMUDGING_SECURITY_BITS),
+            Err(Error::TooMuchNoise)
+        );
+
+        // With more primes, the bound exceeds 64 bits.
+        let bound = smudging_bound(&[q, q], 64, 3, 45).unwrap();
+
+        assert_eq!(
+            bound,
+            ((BigUint::from(q) * q / 128u32) >> 45) << SMUDGING_SECURITY_BITS
+        );
+        assert!(bound.bits() > 64);
+    }
+
+    #[test]
+    fn can_decode_phase() {
+        let q = (1u64 << 60) - 93;
+        let params = Params {
+            lattice_dimension: 4,
+            coeff_modulus: vec![q, 0x7e00001],
+            plain_modulus: 64,
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let q_big = BigUint::from(q) * 0x7e00001u64;
+        let delta = &q_big / 64u32;
+
+        // Δm + e for m = [0, 1, 63, 5] and small positive and negative e.
+        let phase = [(0u32, 3i64), (1, -7), (63, 1000), (5, 0)]
+            .iter()
+            .map(|(m, e)| (BigInt::from(&delta * *m) + e).mod_floor(&BigInt::from(q_big.clone())))
+            .collect::<Vec<_>>();
+
+        let rns = params
+            .coeff_modulus
+            .iter()
+            .flat_map(|q_i| {
+                phase
+                    .iter()
+                    .map(|x| x.mod_floor(&BigInt::from(*q_i)).to_u64().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let plaintext = decode_phase(&params, &rns).unwrap();
+
+        assert_eq!(
+            (0..4)
+                .map(|i| plaintext.get_coefficient(i))
+                .collect::<Vec<_>>(),
+            vec![0, 1, 63, 5]
+        );
+
+        // Noise just under q / 2t leaves no noise budget.
+        let noise: BigInt = BigInt::from(&q_big / 128u32) - 1;
+        let rns = params
+            .coeff_modulus
+            .iter()
+            .flat_map(|q_i| vec![noise.mod_floor(&BigInt::from(*q_i)).to_u64().unwrap(); 4])
+            .collect::<Vec<_>>();
+
+        assert_eq!(decode_phase(&params, &rns).err(), Some(Error::TooMuchNoise));
+    }
+
+    #[test]
+    fn centered_samples_stay_in_bound() {
+        let mut rng = thread_rng();
+        let bound = BigUint::one() << 100;
+
+        for _ in 0..100 {
+            let x = sample_centered(&mut rng, &bound);
+
+            assert!(x.magnitude() <= &bound);
+        }
+    }
+}
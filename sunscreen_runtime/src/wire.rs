@@ -0,0 +1,388 @@
+use std::fmt::Display;
+
+use bincode::Options;
+use logproof::LogProof;
+use serde::Deserialize;
+use sunscreen_zkp_backend::{BigInt, CompiledZkpProgram, Proof};
+
+use crate::{Error, Params, Result, ZkpProof};
+
+/**
+ * The wire format version this release writes.
+ */
+pub const WIRE_FORMAT_VERSION: u16 = 1;
+
+/**
+ * The oldest wire format version this release can read.
+ */
+pub const MIN_WIRE_FORMAT_VERSION: u16 = 1;
+
+const MAGIC: [u8; 4] = *b"SNSC";
+
+const HEADER_LEN: usize = MAGIC.len() + std::mem::size_of::<u16>() + std::mem::size_of::<u8>();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/**
+ * The kinds of artifacts that can be encoded in the wire format.
+ */
+pub enum ArtifactKind {
+    /**
+     * A [`ZkpProof`].
+     */
+    ZkpProof,
+
+    /**
+     * A [`CompiledZkpProgram`].
+     */
+    ZkpProgram,
+
+    /**
+     * FHE scheme [`Params`].
+     */
+    Params,
+
+    /**
+     * A [`LogProof`].
+     */
+    LogProof,
+}
+
+impl From<ArtifactKind> for u8 {
+    fn from(kind: ArtifactKind) -> Self {
+        match kind {
+            ArtifactKind::ZkpProof => 0,
+            ArtifactKind::ZkpProgram => 1,
+            ArtifactKind::Params => 2,
+            ArtifactKind::LogProof => 3,
+        }
+    }
+}
+
+impl Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZkpProof => write!(f, "ZKP proof"),
+            Self::ZkpProgram => write!(f, "ZKP program"),
+            Self::Params => write!(f, "FHE parameter set"),
+            Self::LogProof => write!(f, "linear relation proof"),
+        }
+    }
+}
+
+/**
+ * A versioned binary encoding for artifacts exchanged between parties
+ * that may run different Sunscreen releases (e.g. a prover and a
+ * verification service).
+ *
+ * # Remarks
+ * Each encoding begins with a header consisting of the magic bytes
+ * `SNSC`, the big-endian `u16` wire format version, and a `u8`
+ * [`ArtifactKind`] tag. Decoding data with a version outside
+ * [`MIN_WIRE_FORMAT_VERSION`]`..=`[`WIRE_FORMAT_VERSION`] returns
+ * [`Error::UnsupportedWireVersion`] and decoding the wrong kind of
+ * artifact returns [`Error::WireKindMismatch`].
+ *
+ * [`Params`] use their canonical [`Params::to_bytes`] encoding.
+ * Everything else uses bincode's fixed-width little-endian encoding.
+ * A [`ZkpProof`] begins with its proof system's stable
+ * [`tag`](Proof::tag) rather than serde's variant index, so adding
+ * proof systems or enabling backend features doesn't change existing
+ * encodings. Decoding a proof from a system this build doesn't support
+ * returns [`Error::UnsupportedProofSystem`].
+ *
+ * Decoded [`CompiledZkpProgram`]s don't contain their gadgets' code, so
+ * they can verify proofs but can't create them.
+ */
+pub trait WireFormat: Sized {
+    /**
+     * The kind of artifact this type encodes.
+     */
+    const KIND: ArtifactKind;
+
+    /**
+     * Encode this value in the wire format.
+     */
+    fn to_wire(&self) -> Result<Vec<u8>>;
+
+    /**
+     * Decode a value from the wire format.
+     */
+    fn from_wire(bytes: &[u8]) -> Result<Self>;
+}
+
+/**
+ * Prepends the wire format header to the given payload.
+ */
+fn encode(kind: ArtifactKind, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&WIRE_FORMAT_VERSION.to_be_bytes());
+    bytes.push(kind.into());
+    bytes.extend_from_slice(payload);
+
+    bytes
+}
+
+/**
+ * Validates the wire format header and returns the payload.
+ */
+fn decode(kind: ArtifactKind, bytes: &[u8]) -> Result<&[u8]> {
+    if bytes.len() < HEADER_LEN || bytes[0..MAGIC.len()] != MAGIC {
+        return Err(Error::MalformedWireData);
+    }
+
+    let version = u16::from_be_bytes([bytes[MAGIC.len()], bytes[MAGIC.len() + 1]]);
+
+    if !(MIN_WIRE_FORMAT_VERSION..=WIRE_FORMAT_VERSION).contains(&version) {
+        return Err(Error::UnsupportedWireVersion(version));
+    }
+
+    let actual = bytes[HEADER_LEN - 1];
+
+    if actual != u8::from(kind) {
+        return Err(Error::WireKindMismatch {
+            expected: kind,
+            actual,
+        });
+    }
+
+    Ok(&bytes[HEADER_LEN..])
+}
+
+/**
+ * The bincode options [`bincode::serialize`] uses.
+ */
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+impl WireFormat for ZkpProof {
+    const KIND: ArtifactKind = ArtifactKind::ZkpProof;
+
+    fn to_wire(&self) -> Result<Vec<u8>> {
+        let mut payload = vec![self.proof.tag()];
+
+        self.proof
+            .serialize_contents(&mut bincode::Serializer::new(
+                &mut payload,
+                bincode_options(),
+            ))?;
+
+        bincode_options().serialize_into(&mut payload, &self.outputs)?;
+
+        Ok(encode(Self::KIND, &payload))
+    }
+
+    fn from_wire(bytes: &[u8]) -> Result<Self> {
+        let payload = decode(Self::KIND, bytes)?;
+
+        let (tag, payload) = payload.split_first().ok_or(Error::MalformedWireData)?;
+
+        if !Proof::is_tag_supported(*tag) {
+            return Err(Error::UnsupportedProofSystem(*tag));
+        }
+
+        let mut deserializer = bincode::Deserializer::from_slice(payload, bincode_options());
+
+        let proof = Proof::deserialize_contents(*tag, &mut deserializer)?;
+        let outputs = Vec::<BigInt>::deserialize(&mut deserializer)?;
+
+        Ok(Self { proof, outputs })
+    }
+}
+
+impl WireFormat for LogProof {
+    const KIND: ArtifactKind = ArtifactKind::LogProof;
+
+    fn to_wire(&self) -> Result<Vec<u8>> {
+        Ok(encode(Self::KIND, &bincode::serialize(self)?))
+    }
+
+    fn from_wire(bytes: &[u8]) -> Result<Self> {
+        Ok(bincode::deserialize(decode(Self::KIND, bytes)?)?)
+    }
+}
+
+impl WireFormat for CompiledZkpProgram {
+    const KIND: ArtifactKind = ArtifactKind::ZkpProgram;
+
+    fn to_wire(&self) -> Result<Vec<u8>> {
+        Ok(encode(Self::KIND, &bincode::serialize(self)?))
+    }
+
+    fn from_wire(bytes: &[u8]) -> Result<Self> {
+        Ok(bincode::deserialize(decode(Self::KIND, bytes)?)?)
+    }
+}
+
+impl WireFormat for Params {
+    const KIND: ArtifactKind = ArtifactKind::Params;
+
+    fn to_wire(&self) -> Result<Vec<u8>> {
+        Ok(encode(Self::KIND, &self.to_bytes()))
+    }
+
+    fn from_wire(bytes: &[u8]) -> Result<Self> {
+        Params::try_from_bytes(decode(Self::KIND, bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logproof::{
+        fields::FqSeal128_8192,
+        linear_algebra::{Matrix, ScalarRem},
+        math::make_poly,
+        InnerProductVerifierKnowledge, LogProofGenerators, LogProofProverKnowledge,
+    };
+    use merlin::Transcript;
+    use seal_fhe::SecurityLevel;
+    use sunscreen_fhe_program::SchemeType;
+
+    fn params() -> Params {
+        Params {
+            lattice_dimension: 4096,
+            plain_modulus: 64,
+            coeff_modulus: vec![1, 2, 3, 4],
+            security_level: SecurityLevel::TC128,
+            scheme_type: SchemeType::Bfv,
+        }
+    }
+
+    #[test]
+    fn can_roundtrip_params() {
+        let bytes = params().to_wire().unwrap();
+
+        assert_eq!(&bytes[0..4], b"SNSC");
+        assert_eq!(Params::from_wire(&bytes).unwrap(), params());
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let mut bytes = params().to_wire().unwrap();
+        bytes[4..6].copy_from_slice(&(WIRE_FORMAT_VERSION + 1).to_be_bytes());
+
+        assert_eq!(
+            Params::from_wire(&bytes),
+            Err(Error::UnsupportedWireVersion(WIRE_FORMAT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_artifact_kind() {
+        let bytes = params().to_wire().unwrap();
+
+        assert_eq!(
+            ZkpProof::from_wire(&bytes).err(),
+            Some(Error::WireKindMismatch {
+                expected: ArtifactKind::ZkpProof,
+                actual: u8::from(ArtifactKind::Params),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        let bytes = params().to_wire().unwrap();
+
+        assert_eq!(
+            Params::from_wire(&bytes[0..3]),
+            Err(Error::MalformedWireData)
+        );
+        assert_eq!(
+            Params::from_wire(b"not sunscreen data"),
+            Err(Error::MalformedWireData)
+        );
+        assert_eq!(
+            Params::from_wire(&bytes[0..HEADER_LEN + 3]),
+            Err(Error::ParamDeserializationError)
+        );
+    }
+
+    #[test]
+    fn can_roundtrip_custom_proof() {
+        let proof = ZkpProof {
+            proof: Proof::Custom {
+                name: "test".to_owned(),
+                data: vec![1, 2, 3],
+            },
+            outputs: vec![BigInt::from_u32(42)],
+        };
+
+        let bytes = proof.to_wire().unwrap();
+
+        assert_eq!(bytes[HEADER_LEN], Proof::CUSTOM_TAG);
+
+        let decoded = ZkpProof::from_wire(&bytes).unwrap();
+
+        match decoded.proof() {
+            Proof::Custom { name, data } => {
+                assert_eq!(name, "test");
+                assert_eq!(data, &[1, 2, 3]);
+            }
+            _ => panic!("Expected a custom proof"),
+        }
+
+        assert_eq!(decoded.outputs(), &[BigInt::from_u32(42)]);
+    }
+
+    #[test]
+    fn rejects_unsupported_proof_system() {
+        let mut bytes = ZkpProof {
+            proof: Proof::Custom {
+                name: "test".to_owned(),
+                data: vec![],
+            },
+            outputs: vec![],
+        }
+        .to_wire()
+        .unwrap();
+
+        bytes[HEADER_LEN] = 255;
+
+        assert_eq!(
+            ZkpProof::from_wire(&bytes).err(),
+            Some(Error::UnsupportedProofSystem(255))
+        );
+    }
+
+    #[test]
+    fn can_roundtrip_log_proof() {
+        type Q = FqSeal128_8192;
+
+        let a = Matrix::from([
+            [make_poly::<Q>(&[1, 2, 3, 4]), make_poly::<Q>(&[1])],
+            [make_poly::<Q>(&[0]), make_poly::<Q>(&[2])],
+        ]);
+        let s = Matrix::from([[make_poly::<Q>(&[1, 0, 1])], [make_poly::<Q>(&[0, 1, 1])]]);
+
+        // x^4 + 1
+        let f = make_poly::<Q>(&[1, 0, 0, 0, 1]);
+        let t = (&a * &s).scalar_rem(&f);
+
+        let pk = LogProofProverKnowledge::new(&a, &s, &t, 8, &f);
+        let gens = LogProofGenerators::new(pk.vk.l() as usize);
+        let u = InnerProductVerifierKnowledge::get_u();
+
+        let proof = LogProof::create(&mut Transcript::new(b"test"), &pk, &gens.g, &gens.h, &u);
+
+        let bytes = proof.to_wire().unwrap();
+        let decoded = LogProof::from_wire(&bytes).unwrap();
+
+        decoded
+            .verify(&mut Transcript::new(b"test"), &pk.vk, &gens.g, &gens.h, &u)
+            .unwrap();
+
+        assert_eq!(
+            ZkpProof::from_wire(&bytes).err(),
+            Some(Error::WireKindMismatch {
+                expected: ArtifactKind::ZkpProof,
+                actual: u8::from(ArtifactKind::LogProof),
+            })
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter},
     ops::{Add, Deref, Mul, Neg, Sub},
+    sync::{Arc, Mutex},
     time::Instant,
 };
 
@@ -127,13 +130,6 @@ impl BulletproofsCircuit {
         transcript
     }
 
-    fn make_gens(len: usize) -> (PedersenGens, BulletproofGens) {
-        let pc_gens = PedersenGens::default();
-        let bp_gens = BulletproofGens::new(len, 1);
-
-        (pc_gens, bp_gens)
-    }
-
     /**
      * # Notes
      * `graph` is declared as mutable, but the value won't actually be
@@ -312,18 +308,103 @@ impl BulletproofsCircuit {
     }
 }
 
+#[derive(Clone)]
+/**
+ * The Pedersen and Bulletproofs generators needed to prove and verify
+ * circuits with up to [`capacity`](BulletproofsGenerators::capacity)
+ * multiplication gates.
+ *
+ * # Remarks
+ * Generators are deterministically derived by hashing, so the same
+ * capacity always yields the same points. The underlying Bulletproofs
+ * crate doesn't expose the points, so generators can't be serialized;
+ * create them once with [`new`](BulletproofsGenerators::new) and share
+ * them across proofs, e.g. with
+ * [`BulletproofsBackend::from_generators`].
+ */
+pub struct BulletproofsGenerators {
+    pedersen_gens: PedersenGens,
+    bulletproof_gens: BulletproofGens,
+}
+
+impl BulletproofsGenerators {
+    /**
+     * Create generators that can prove and verify circuits needing up to
+     * `capacity` generators.
+     */
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pedersen_gens: PedersenGens::default(),
+            bulletproof_gens: BulletproofGens::new(capacity, 1),
+        }
+    }
+
+    /**
+     * The number of generators in this set.
+     */
+    pub fn capacity(&self) -> usize {
+        self.bulletproof_gens.gens_capacity
+    }
+}
+
+impl Debug for BulletproofsGenerators {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BulletproofsGenerators")
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone)]
 /**
  * A Bulletproofs backend.
+ *
+ * # Remarks
+ * Creating generators dominates the cost of proving and verifying small
+ * circuits, so the backend caches them by capacity (see
+ * [`ZkpBackend::generators`]). Clones of a backend, including those held
+ * by a runtime, share the same cache. A set of generators serves any
+ * circuit no larger than its capacity.
  */
-pub struct BulletproofsBackend;
+pub struct BulletproofsBackend {
+    generators: Arc<Mutex<BTreeMap<usize, Arc<BulletproofsGenerators>>>>,
+}
 
 impl BulletproofsBackend {
     /**
      * Create a [`BulletproofsBackend`].
      */
     pub fn new() -> Self {
-        Self
+        Self {
+            generators: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /**
+     * Create a [`BulletproofsBackend`] whose cache starts with the given
+     * generators, e.g. ones precomputed with
+     * [`BulletproofsGenerators::new`] or taken from another backend's
+     * [`generators`](ZkpBackend::generators).
+     */
+    pub fn from_generators(generators: Arc<BulletproofsGenerators>) -> Self {
+        let backend = Self::new();
+
+        backend.add_generators(generators);
+
+        backend
+    }
+
+    /**
+     * Returns the smallest cached generators with at least the given
+     * capacity, if any.
+     */
+    fn cached_generators(&self, capacity: usize) -> Option<Arc<BulletproofsGenerators>> {
+        self.generators
+            .lock()
+            .unwrap()
+            .range(capacity..)
+            .next()
+            .map(|(_, gens)| gens.clone())
     }
 }
 
@@ -371,6 +452,42 @@ fn constraint_count(graph: &ExecutableZkpProgram) -> Result<usize> {
 impl ZkpBackend for BulletproofsBackend {
     type Field = Scalar;
 
+    type Generators = Arc<BulletproofsGenerators>;
+
+    fn generators(&self, capacity: usize) -> Self::Generators {
+        if let Some(gens) = self.cached_generators(capacity) {
+            return gens;
+        }
+
+        // Create generators without holding the lock, so other threads can
+        // use the cache meanwhile. Round up so circuits of similar size
+        // share generators.
+        let now = Instant::now();
+
+        let gens = Arc::new(BulletproofsGenerators::new(capacity.next_power_of_two()));
+
+        trace!(
+            "Bulletproofs generator creation time {}s",
+            now.elapsed().as_secs_f64()
+        );
+
+        // If another thread created the same capacity meanwhile, keep
+        // theirs.
+        self.generators
+            .lock()
+            .unwrap()
+            .entry(gens.capacity())
+            .or_insert(gens)
+            .clone()
+    }
+
+    fn add_generators(&self, generators: Self::Generators) {
+        self.generators
+            .lock()
+            .unwrap()
+            .insert(generators.capacity(), generators);
+    }
+
     fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
         let expected_input_count = graph
             .node_weights()
@@ -395,12 +512,11 @@ impl ZkpBackend for BulletproofsBackend {
 
         let transcript = BulletproofsCircuit::make_transcript(constraint_count);
 
-        let (pedersen_gens, bulletproof_gens) =
-            BulletproofsCircuit::make_gens(2 * constraint_count);
+        let gens = self.generators(2 * constraint_count);
 
         let mut circuit = BulletproofsCircuit::new(graph.node_count());
 
-        let mut prover = Prover::new(&pedersen_gens, transcript);
+        let mut prover = Prover::new(&gens.pedersen_gens, transcript);
 
         let now = Instant::now();
 
@@ -411,7 +527,7 @@ impl ZkpBackend for BulletproofsBackend {
 
         let now = Instant::now();
 
-        let proof = prover.prove(&bulletproof_gens)?;
+        let proof = prover.prove(&gens.bulletproof_gens)?;
 
         trace!("Bulletproofs prover time {}s", now.elapsed().as_secs_f64());
 
@@ -431,8 +547,7 @@ impl ZkpBackend for BulletproofsBackend {
         let constraint_count = constraint_count(graph)?;
 
         let transcript = BulletproofsCircuit::make_transcript(constraint_count);
-        let (pedersen_gens, bulletproof_gens) =
-            BulletproofsCircuit::make_gens(2 * constraint_count);
+        let gens = self.generators(2 * constraint_count);
 
         let mut circuit = BulletproofsCircuit::new(graph.node_count());
 
@@ -446,7 +561,7 @@ impl ZkpBackend for BulletproofsBackend {
 
         let now = Instant::now();
 
-        verifier.verify(&proof.0, &pedersen_gens, &bulletproof_gens)?;
+        verifier.verify(&proof.0, &gens.pedersen_gens, &gens.bulletproof_gens)?;
 
         trace!("Bulletproofs verify time {}s", now.elapsed().as_secs_f64());
 
@@ -459,7 +574,7 @@ impl ZkpBackend for BulletproofsBackend {
         constant_inputs: &[BigInt],
         public_inputs: &[BigInt],
         private_inputs: &[BigInt],
-    ) -> Result<ExecutableZkpProgram> {
+    ) -> Result<(ExecutableZkpProgram, Vec<BigInt>)> {
         let constant_inputs = constant_inputs
             .iter()
             .map(Scalar::try_from)
@@ -481,6 +596,7 @@ impl ZkpBackend for BulletproofsBackend {
         prog: &crate::CompiledZkpProgram,
         constant_inputs: &[BigInt],
         public_inputs: &[BigInt],
+        outputs: &[BigInt],
     ) -> Result<ExecutableZkpProgram> {
         let constant_inputs = constant_inputs
             .iter()
@@ -492,7 +608,12 @@ impl ZkpBackend for BulletproofsBackend {
             .map(Scalar::try_from)
             .collect::<Result<Vec<Scalar>>>()?;
 
-        jit_verifier(prog, &constant_inputs, &public_inputs)
+        let outputs = outputs
+            .iter()
+            .map(Scalar::try_from)
+            .collect::<Result<Vec<Scalar>>>()?;
+
+        jit_verifier(prog, &constant_inputs, &public_inputs, &outputs)
     }
 }
 
@@ -642,8 +763,10 @@ mod tests {
         );
     }
 
-    #[test]
-    fn can_run_simple_proof() {
+    /**
+     * Builds a program constraining `a * b + c == 42`.
+     */
+    fn simple_program() -> ExecutableZkpProgram {
         let mut graph = ExecutableZkpProgram::new();
 
         let mut add_node = |op: BackendOperation, edges: &[(NodeIndex, EdgeInfo)]| {
@@ -674,6 +797,12 @@ mod tests {
             &[(add_1, EdgeInfo::Unordered)],
         );
 
+        graph
+    }
+
+    #[test]
+    fn can_run_simple_proof() {
+        let graph = simple_program();
         let backend = BulletproofsBackend::new();
 
         // 10 * 4 + 2 == 42
@@ -719,4 +848,54 @@ mod tests {
 
         assert!(backend.verify(&graph, &proof).is_err());
     }
+
+    #[test]
+    fn can_share_generators_between_backends() {
+        let graph = simple_program();
+
+        // A backend seeded with the prover's generators verifies without
+        // creating new ones.
+        let gens = Arc::new(BulletproofsGenerators::new(64));
+        let prover = BulletproofsBackend::from_generators(gens.clone());
+        let verifier = BulletproofsBackend::from_generators(gens.clone());
+
+        let proof = prover
+            .prove(
+                &graph,
+                &[
+                    BigInt::from_u32(10),
+                    BigInt::from_u32(4),
+                    BigInt::from_u32(2),
+                ],
+            )
+            .unwrap();
+
+        verifier.verify(&graph, &proof).unwrap();
+
+        assert!(Arc::ptr_eq(&prover.generators(1), &gens));
+        assert!(Arc::ptr_eq(&verifier.generators(64), &gens));
+
+        // Backends without generators create and cache them on first use.
+        let backend = BulletproofsBackend::new();
+
+        backend.verify(&graph, &proof).unwrap();
+
+        assert_eq!(backend.generators.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn caches_generators_by_capacity() {
+        let backend = BulletproofsBackend::new();
+
+        let small = backend.generators(3);
+        let large = backend.generators(100);
+
+        assert_eq!(small.capacity(), 4);
+        assert_eq!(large.capacity(), 128);
+
+        // Requests reuse the smallest set that fits.
+        assert!(Arc::ptr_eq(&backend.generators(2), &small));
+        assert!(Arc::ptr_eq(&backend.generators(5), &large));
+        assert!(Arc::ptr_eq(&backend.clone().generators(128), &large));
+    }
 }
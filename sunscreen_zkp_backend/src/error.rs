@@ -15,6 +15,14 @@ pub enum Error {
      */
     BulletproofsR1CSError(Box<bulletproofs::r1cs::R1CSError>),
 
+    #[cfg(feature = "groth16")]
+    #[error("Groth16 error: {0}")]
+    /**
+     * Encountered an error when setting up, creating or verifying a
+     * Groth16 proof.
+     */
+    Groth16Error(Box<String>),
+
     #[error("Value {0} is out of range for the chosen backend")]
     /**
      * Encountered a value out of range for the field type in the chosen backend.
@@ -56,6 +64,32 @@ pub enum Error {
      * A constraint could not be satisfied.
      */
     UnsatisfiableConstraint(NodeIndex),
+
+    #[error(
+        "Proof {} in the batch failed to verify: {}",
+        .0.index,
+        .0.source
+    )]
+    /**
+     * A proof in a batch failed to verify.
+     */
+    BatchVerificationFailed(Box<BatchVerificationFailure>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/**
+ * Describes which proof in a batch failed to verify and why.
+ */
+pub struct BatchVerificationFailure {
+    /**
+     * The index of the failing proof in the batch.
+     */
+    pub index: usize,
+
+    /**
+     * Why the proof failed to verify.
+     */
+    pub source: Error,
 }
 
 impl Error {
@@ -86,6 +120,21 @@ impl Error {
     pub fn inputs_mismatch(msg: &str) -> Self {
         Self::InputsMismatch(Box::new(msg.to_owned()))
     }
+
+    /**
+     * Create an [`Error::BatchVerificationFailed`].
+     */
+    pub fn batch_verification_failed(index: usize, err: Error) -> Self {
+        Self::BatchVerificationFailed(Box::new(BatchVerificationFailure { index, source: err }))
+    }
+
+    #[cfg(feature = "groth16")]
+    /**
+     * Create an [`Error::Groth16Error`].
+     */
+    pub fn groth16_error(msg: &str) -> Self {
+        Self::Groth16Error(Box::new(msg.to_owned()))
+    }
 }
 
 impl From<bulletproofs::r1cs::R1CSError> for Error {
@@ -94,6 +143,13 @@ impl From<bulletproofs::r1cs::R1CSError> for Error {
     }
 }
 
+#[cfg(feature = "groth16")]
+impl From<ark_relations::r1cs::SynthesisError> for Error {
+    fn from(e: ark_relations::r1cs::SynthesisError) -> Self {
+        Self::groth16_error(&e.to_string())
+    }
+}
+
 const_assert!(std::mem::size_of::<Error>() <= 16);
 
 /**
@@ -0,0 +1,796 @@
+use std::{
+    cell::RefCell,
+    ops::{Add, Mul, Neg, Sub},
+    sync::Arc,
+    time::Instant,
+};
+
+use ark_bn254::{Bn254, Fr};
+use ark_ff::{BigInteger256, PrimeField};
+use ark_groth16::{prepare_verifying_key, Groth16, PreparedVerifyingKey, ProvingKey};
+use ark_relations::{
+    lc,
+    r1cs::{
+        ConstraintSynthesizer, ConstraintSystemRef, LinearCombination, SynthesisError, Variable,
+    },
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use crypto_bigint::Encoding;
+use log::trace;
+use petgraph::stable_graph::NodeIndex;
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use sunscreen_compiler_common::forward_traverse;
+
+use crate::{
+    exec::Operation, jit::jit_verifier, jit_prover, BackendField, BigInt, CompiledZkpProgram,
+    Error, ExecutableZkpProgram, Proof, Result, ZkpBackend,
+};
+
+#[derive(Clone)]
+enum Node {
+    LinearCombination(LinearCombination<Fr>, Option<Fr>),
+    Constant(Fr),
+}
+
+impl Add<Node> for Node {
+    type Output = Self;
+
+    fn add(self, rhs: Node) -> Self::Output {
+        use Node::*;
+
+        match (self, rhs) {
+            (LinearCombination(x, a), LinearCombination(y, b)) => {
+                LinearCombination(x + y, a.zip(b).map(|(a, b)| a + b))
+            }
+            (LinearCombination(x, a), Constant(y)) | (Constant(y), LinearCombination(x, a)) => {
+                LinearCombination(x + (y, Variable::One), a.map(|a| a + y))
+            }
+            (Constant(x), Constant(y)) => Constant(x + y),
+        }
+    }
+}
+
+impl Sub<Node> for Node {
+    type Output = Self;
+
+    fn sub(self, rhs: Node) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Node {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        use Node::*;
+
+        match self {
+            LinearCombination(x, a) => LinearCombination(-x, a.map(|a| -a)),
+            Constant(x) => Constant(-x),
+        }
+    }
+}
+
+impl Mul<Fr> for Node {
+    type Output = Self;
+
+    fn mul(self, rhs: Fr) -> Self::Output {
+        use Node::*;
+
+        match self {
+            LinearCombination(x, a) => LinearCombination(x * rhs, a.map(|a| a * rhs)),
+            Constant(x) => Constant(x * rhs),
+        }
+    }
+}
+
+/**
+ * Adapts an [`ExecutableZkpProgram`] into an arkworks R1CS circuit.
+ *
+ * # Remarks
+ * Unlike Bulletproofs, Groth16 requires a trusted setup for each circuit.
+ * For one setup to serve every proof of a program, the circuit's shape
+ * must not depend on any public input's value. The JIT encodes public
+ * inputs as [`Operation::Constraint`] nodes, so each such node's value
+ * becomes a Groth16 instance variable rather than a constant baked into
+ * the constraint matrices. Instance variables are allocated in node index
+ * order, which [`public_inputs`] mirrors for the verifier.
+ *
+ * Errors other than [`SynthesisError`]s get stashed in `error`, as
+ * [`ConstraintSynthesizer`] can't return them.
+ */
+struct Groth16Circuit<'a> {
+    graph: &'a ExecutableZkpProgram,
+    inputs: Option<&'a [Fr]>,
+    error: &'a RefCell<Option<Error>>,
+}
+
+impl<'a> Groth16Circuit<'a> {
+    fn gen_circuit(&self, cs: &ConstraintSystemRef<Fr>) -> Result<()> {
+        let graph = self.graph;
+
+        let mut nodes: Vec<Option<Node>> = vec![None; graph.node_count()];
+
+        let instances = graph
+            .node_indices()
+            .filter_map(|n| match graph[n].operation {
+                Operation::Constraint(x) => Some((n, x)),
+                _ => None,
+            })
+            .map(|(n, x)| {
+                let x = Fr::try_from(x)?;
+
+                Ok((n, cs.new_input_variable(|| Ok(x))?))
+            })
+            .collect::<Result<Vec<(NodeIndex, Variable)>>>()?;
+
+        let get_node = |nodes: &[Option<Node>], idx: NodeIndex| {
+            nodes[idx.index()]
+                .clone()
+                .unwrap_or_else(|| panic!("traversal error: dependency {} not found", idx.index()))
+        };
+
+        forward_traverse(&graph.0, |query, idx| {
+            let node = query.get_node(idx).unwrap();
+
+            let output = match node.operation {
+                Operation::Input(x) => {
+                    let value = self.inputs.map(|i| i[x]);
+
+                    let var =
+                        cs.new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+
+                    Node::LinearCombination(lc!() + var, value)
+                }
+                Operation::HiddenInput(x) => {
+                    let value = match x {
+                        Some(x) => Some(Fr::try_from(x)?),
+                        None => None,
+                    };
+
+                    let var =
+                        cs.new_witness_variable(|| value.ok_or(SynthesisError::AssignmentMissing))?;
+
+                    Node::LinearCombination(lc!() + var, value)
+                }
+                Operation::Add => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    get_node(&nodes, left) + get_node(&nodes, right)
+                }
+                Operation::Sub => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    get_node(&nodes, left) - get_node(&nodes, right)
+                }
+                Operation::Neg => {
+                    let left = query.get_unary_operand(idx)?;
+
+                    -get_node(&nodes, left)
+                }
+                Operation::Mul => {
+                    let (left, right) = query.get_binary_operands(idx)?;
+
+                    match (get_node(&nodes, left), get_node(&nodes, right)) {
+                        (Node::LinearCombination(x, a), Node::LinearCombination(y, b)) => {
+                            let value = a.zip(b).map(|(a, b)| a * b);
+
+                            let o = cs.new_witness_variable(|| {
+                                value.ok_or(SynthesisError::AssignmentMissing)
+                            })?;
+
+                            cs.enforce_constraint(x, y, lc!() + o)?;
+
+                            Node::LinearCombination(lc!() + o, value)
+                        }
+                        (x, Node::Constant(y)) | (Node::Constant(y), x) => x * y,
+                    }
+                }
+                Operation::Constraint(x) => {
+                    let operands = query.get_unordered_operands(idx)?;
+
+                    // Allocated above for every constraint node.
+                    let (_, instance) = instances.iter().find(|(n, _)| *n == idx).unwrap();
+
+                    for o_idx in operands {
+                        match get_node(&nodes, o_idx) {
+                            Node::LinearCombination(o, _) => {
+                                cs.enforce_constraint(
+                                    o - (Fr::from(1u64), *instance),
+                                    lc!() + Variable::One,
+                                    lc!(),
+                                )?;
+                            }
+                            Node::Constant(o) => {
+                                if Fr::try_from(x)? != o {
+                                    return Err(Error::groth16_error(&format!(
+                                        "Constant {o:?} does not equal {x:?}"
+                                    )));
+                                }
+                            }
+                        }
+                    }
+
+                    return Ok(());
+                }
+                Operation::Constant(x) => Node::Constant(Fr::try_from(x)?),
+            };
+
+            nodes[idx.index()] = Some(output);
+
+            Ok::<(), Error>(())
+        })?;
+
+        Ok(())
+    }
+}
+
+impl<'a> ConstraintSynthesizer<Fr> for Groth16Circuit<'a> {
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<Fr>,
+    ) -> std::result::Result<(), SynthesisError> {
+        self.gen_circuit(&cs).map_err(|e| {
+            *self.error.borrow_mut() = Some(e);
+            SynthesisError::Unsatisfiable
+        })
+    }
+}
+
+/**
+ * Returns the Groth16 public inputs for the given [`ExecutableZkpProgram`].
+ * These are the values of its [`Operation::Constraint`] nodes in node
+ * index order.
+ */
+fn public_inputs(graph: &ExecutableZkpProgram) -> Result<Vec<Fr>> {
+    graph
+        .node_weights()
+        .filter_map(|n| match n.operation {
+            Operation::Constraint(x) => Some(Fr::try_from(x)),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+ * Runs `f` with a [`Groth16Circuit`] over `graph`, preferring any error
+ * the circuit stashed over the arkworks error it caused.
+ */
+fn with_circuit<T, F>(graph: &ExecutableZkpProgram, inputs: Option<&[Fr]>, f: F) -> Result<T>
+where
+    F: FnOnce(Groth16Circuit<'_>) -> std::result::Result<T, SynthesisError>,
+{
+    let error = RefCell::new(None);
+
+    let circuit = Groth16Circuit {
+        graph,
+        inputs,
+        error: &error,
+    };
+
+    let result = f(circuit);
+
+    if let Some(e) = error.into_inner() {
+        return Err(e);
+    }
+
+    Ok(result?)
+}
+
+#[derive(Clone)]
+/**
+ * A verifiable Groth16 proof over the BN254 curve. Its size is constant,
+ * regardless of the ZKP program's size.
+ */
+pub struct Groth16Proof(ark_groth16::Proof<Bn254>);
+
+impl Serialize for Groth16Proof {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut data = vec![];
+
+        self.0
+            .serialize_compressed(&mut data)
+            .map_err(serde::ser::Error::custom)?;
+
+        serializer.serialize_bytes(&data)
+    }
+}
+
+impl<'de> Deserialize<'de> for Groth16Proof {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = Vec::<u8>::deserialize(deserializer)?;
+
+        let proof = ark_groth16::Proof::deserialize_compressed(data.as_slice())
+            .map_err(D::Error::custom)?;
+
+        Ok(Self(proof))
+    }
+}
+
+#[derive(Clone)]
+/**
+ * A Groth16 backend over the BN254 curve.
+ *
+ * # Remarks
+ * Groth16 proofs are 3 group elements and verify with a constant number
+ * of pairings, which suits on-chain verification. The price is a
+ * circuit-specific trusted setup: [`Groth16Backend::setup`] samples toxic
+ * waste that, if retained, allows forging proofs. Production deployments
+ * should instead run a multi-party ceremony and load the result with
+ * [`Groth16Backend::from_proving_key`].
+ *
+ * A setup binds the ZKP program and its constant inputs, but not its
+ * public or private inputs. A backend built from only a verifying key can
+ * verify, but not prove. Backends serialize their keys, so a setup can be
+ * stored and reloaded.
+ */
+pub struct Groth16Backend {
+    proving_key: Option<Arc<ProvingKey<Bn254>>>,
+    verifying_key: Arc<PreparedVerifyingKey<Bn254>>,
+}
+
+impl Groth16Backend {
+    /**
+     * Run a trusted setup for the given ZKP program with the given
+     * constant inputs, sampling randomness from `rng`.
+     */
+    pub fn setup<R>(
+        prog: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        rng: &mut R,
+    ) -> Result<Self>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let public_input_count = prog
+            .node_weights()
+            .filter(|x| matches!(x.operation, crate::Operation::PublicInput(_)))
+            .count();
+
+        let output_count = prog
+            .node_weights()
+            .filter(|x| matches!(x.operation, crate::Operation::Output(_)))
+            .count();
+
+        // The circuit's shape doesn't depend on public input or output
+        // values, so any will do.
+        let public_inputs = vec![BigInt::ZERO; public_input_count];
+        let outputs = vec![BigInt::ZERO; output_count];
+
+        let graph = Self::jit_verifier_internal(prog, constant_inputs, &public_inputs, &outputs)?;
+
+        let now = Instant::now();
+
+        let proving_key = with_circuit(&graph, None, |c| {
+            Groth16::<Bn254>::generate_random_parameters_with_reduction(c, rng)
+        })?;
+
+        trace!("Groth16 setup time {}s", now.elapsed().as_secs_f64());
+
+        Ok(Self::from_proving_key(proving_key))
+    }
+
+    /**
+     * Create a [`Groth16Backend`] that can prove and verify from the
+     * given proving key.
+     */
+    pub fn from_proving_key(proving_key: ProvingKey<Bn254>) -> Self {
+        let verifying_key = prepare_verifying_key(&proving_key.vk);
+
+        Self {
+            proving_key: Some(Arc::new(proving_key)),
+            verifying_key: Arc::new(verifying_key),
+        }
+    }
+
+    /**
+     * Create a [`Groth16Backend`] that can only verify from the given
+     * verifying key.
+     */
+    pub fn from_verifying_key(verifying_key: &ark_groth16::VerifyingKey<Bn254>) -> Self {
+        Self {
+            proving_key: None,
+            verifying_key: Arc::new(prepare_verifying_key(verifying_key)),
+        }
+    }
+
+    /**
+     * The proving key, if this backend has one.
+     */
+    pub fn proving_key(&self) -> Option<&ProvingKey<Bn254>> {
+        self.proving_key.as_deref()
+    }
+
+    /**
+     * The verifying key.
+     */
+    pub fn verifying_key(&self) -> &ark_groth16::VerifyingKey<Bn254> {
+        &self.verifying_key.vk
+    }
+
+    fn jit_verifier_internal(
+        prog: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+        outputs: &[BigInt],
+    ) -> Result<ExecutableZkpProgram> {
+        let constant_inputs = constant_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        let public_inputs = public_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        let outputs = outputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        jit_verifier(prog, &constant_inputs, &public_inputs, &outputs)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedKeys {
+    proving_key: Option<Vec<u8>>,
+    verifying_key: Vec<u8>,
+}
+
+/**
+ * Serializes the backend's keys, so a setup can be persisted and reloaded
+ * rather than repeated. Verifier-only backends serialize only the
+ * verifying key.
+ */
+impl Serialize for Groth16Backend {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let proving_key = match &self.proving_key {
+            Some(pk) => {
+                let mut data = vec![];
+
+                pk.serialize_compressed(&mut data)
+                    .map_err(serde::ser::Error::custom)?;
+
+                Some(data)
+            }
+            None => None,
+        };
+
+        let mut verifying_key = vec![];
+
+        self.verifying_key
+            .vk
+            .serialize_compressed(&mut verifying_key)
+            .map_err(serde::ser::Error::custom)?;
+
+        SerializedKeys {
+            proving_key,
+            verifying_key,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Groth16Backend {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let keys = SerializedKeys::deserialize(deserializer)?;
+
+        match keys.proving_key {
+            Some(pk) => {
+                let pk =
+                    ProvingKey::deserialize_compressed(pk.as_slice()).map_err(D::Error::custom)?;
+
+                Ok(Self::from_proving_key(pk))
+            }
+            None => {
+                let vk = ark_groth16::VerifyingKey::deserialize_compressed(
+                    keys.verifying_key.as_slice(),
+                )
+                .map_err(D::Error::custom)?;
+
+                Ok(Self::from_verifying_key(&vk))
+            }
+        }
+    }
+}
+
+impl ZkpBackend for Groth16Backend {
+    type Field = Fr;
+
+    // Groth16's keys are specific to a circuit rather than its size, so
+    // there are no generators to share.
+    type Generators = ();
+
+    fn generators(&self, _capacity: usize) -> Self::Generators {}
+
+    fn add_generators(&self, _generators: Self::Generators) {}
+
+    fn prove(&self, graph: &ExecutableZkpProgram, inputs: &[BigInt]) -> Result<Proof> {
+        let proving_key = self.proving_key.as_ref().ok_or_else(|| {
+            Error::groth16_error("Cannot prove with a backend lacking a proving key.")
+        })?;
+
+        let expected_input_count = graph
+            .node_weights()
+            .filter(|x| matches!(x.operation, Operation::Input(_)))
+            .count();
+
+        if expected_input_count != inputs.len() {
+            return Err(Error::inputs_mismatch(&format!(
+                "Internal error: Groth16 runtime arguments mismatch. Expected {}, got {}.",
+                expected_input_count,
+                inputs.len()
+            )));
+        }
+
+        let inputs = inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        let now = Instant::now();
+
+        let proof = with_circuit(graph, Some(&inputs), |c| {
+            Groth16::<Bn254>::create_random_proof_with_reduction(c, proving_key, &mut OsRng)
+        })?;
+
+        trace!("Groth16 prover time {}s", now.elapsed().as_secs_f64());
+
+        Ok(Proof::Groth16(Box::new(Groth16Proof(proof))))
+    }
+
+    fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()> {
+        let proof = match proof {
+            Proof::Groth16(x) => x,
+            _ => {
+                return Err(Error::IncorrectProofType);
+            }
+        };
+
+        let public_inputs = public_inputs(graph)?;
+
+        let now = Instant::now();
+
+        let valid = Groth16::<Bn254>::verify_proof(&self.verifying_key, &proof.0, &public_inputs)?;
+
+        trace!("Groth16 verify time {}s", now.elapsed().as_secs_f64());
+
+        if !valid {
+            return Err(Error::groth16_error("Proof is invalid."));
+        }
+
+        Ok(())
+    }
+
+    fn jit_prover(
+        &self,
+        prog: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+        private_inputs: &[BigInt],
+    ) -> Result<(ExecutableZkpProgram, Vec<BigInt>)> {
+        let constant_inputs = constant_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+        let public_inputs = public_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+        let private_inputs = private_inputs
+            .iter()
+            .map(Fr::try_from)
+            .collect::<Result<Vec<Fr>>>()?;
+
+        jit_prover::<Fr>(prog, &constant_inputs, &public_inputs, &private_inputs)
+    }
+
+    fn jit_verifier(
+        &self,
+        prog: &CompiledZkpProgram,
+        constant_inputs: &[BigInt],
+        public_inputs: &[BigInt],
+        outputs: &[BigInt],
+    ) -> Result<ExecutableZkpProgram> {
+        Self::jit_verifier_internal(prog, constant_inputs, public_inputs, outputs)
+    }
+}
+
+fn try_bigint_to_fr(x: &BigInt) -> Result<Fr> {
+    let bytes = x.0.to_le_bytes();
+
+    // BigInt values are little endian. Thus, we attempt to convert the
+    // lower 256 bits to a field element and assert the upper bytes are zero.
+    let (lower, upper) = bytes.split_at(32);
+
+    if upper.iter().any(|x| *x != 0) {
+        return Err(Error::out_of_range(&x.to_string()));
+    }
+
+    let mut words = [0u64; 4];
+
+    for (w, chunk) in words.iter_mut().zip(lower.chunks_exact(8)) {
+        *w = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    Fr::from_bigint(BigInteger256::new(words)).ok_or_else(|| Error::out_of_range(&x.to_string()))
+}
+
+fn fr_to_bigint(x: &Fr) -> BigInt {
+    let words = x.into_bigint().0;
+
+    BigInt::from_words([words[0], words[1], words[2], words[3], 0, 0, 0, 0])
+}
+
+impl BackendField for Fr {
+    // 21888242871839275222246405745257275088548364400416034343698204186575808495617
+    const FIELD_MODULUS: BigInt = BigInt::from_words([
+        0x43e1f593f0000001,
+        0x2833e84879b97091,
+        0xb85045b68181585d,
+        0x30644e72e131a029,
+        0x0,
+        0x0,
+        0x0,
+        0x0,
+    ]);
+}
+
+impl TryFrom<BigInt> for Fr {
+    type Error = Error;
+
+    fn try_from(value: BigInt) -> Result<Self> {
+        try_bigint_to_fr(&value)
+    }
+}
+
+impl TryFrom<&BigInt> for Fr {
+    type Error = Error;
+
+    fn try_from(value: &BigInt) -> Result<Self> {
+        try_bigint_to_fr(value)
+    }
+}
+
+impl crate::ZkpFrom<Fr> for BigInt {
+    fn zkp_from(val: Fr) -> BigInt {
+        fr_to_bigint(&val)
+    }
+}
+
+impl crate::ZkpFrom<&Fr> for BigInt {
+    fn zkp_from(val: &Fr) -> BigInt {
+        fr_to_bigint(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto_bigint::U512;
+    use sunscreen_compiler_common::{EdgeInfo, NodeInfo};
+
+    use super::*;
+    use crate::Operation as JitOperation;
+
+    #[test]
+    fn can_roundtrip_fr() {
+        let a = BigInt::from_words([0x1234567890abcdef, 0x1, 0x0, 0x7, 0x0, 0x0, 0x0, 0x0]);
+
+        let fr = Fr::try_from(a).unwrap();
+
+        assert_eq!(a, <BigInt as crate::ZkpFrom<Fr>>::zkp_from(fr));
+    }
+
+    #[test]
+    fn field_modulus_out_of_range() {
+        let p = Fr::FIELD_MODULUS;
+
+        assert!(Fr::try_from(p).is_err());
+
+        let p_min_1 = BigInt(p.0.wrapping_sub(&U512::ONE));
+
+        assert_eq!(
+            p_min_1,
+            <BigInt as crate::ZkpFrom<Fr>>::zkp_from(Fr::try_from(p_min_1).unwrap())
+        );
+        assert_eq!(
+            <BigInt as crate::ZkpFrom<Fr>>::zkp_from(-Fr::from(1u64)),
+            p_min_1
+        );
+    }
+
+    #[test]
+    fn can_run_simple_proof() {
+        let mut prog = CompiledZkpProgram::new();
+
+        let mut add_node = |op: JitOperation, edges: &[(NodeIndex, EdgeInfo)]| {
+            let n = prog.add_node(NodeInfo { operation: op });
+
+            for (source, edge) in edges {
+                prog.add_edge(*source, n, *edge);
+            }
+
+            n
+        };
+
+        let a = add_node(JitOperation::PrivateInput(0), &[]);
+        let b = add_node(JitOperation::PrivateInput(1), &[]);
+        let c = add_node(JitOperation::PublicInput(0), &[]);
+
+        let mul = add_node(
+            JitOperation::Mul,
+            &[(a, EdgeInfo::Left), (b, EdgeInfo::Right)],
+        );
+        let add = add_node(
+            JitOperation::Add,
+            &[(mul, EdgeInfo::Left), (c, EdgeInfo::Right)],
+        );
+
+        let _ = add_node(
+            JitOperation::Constraint(BigInt::from_u32(42)),
+            &[(add, EdgeInfo::Unordered)],
+        );
+
+        let backend = Groth16Backend::setup(&prog, &[], &mut OsRng).unwrap();
+
+        let prove = |a: u32, b: u32, c: u32| {
+            let (exec, _) = backend
+                .jit_prover(
+                    &prog,
+                    &[],
+                    &[BigInt::from_u32(c)],
+                    &[BigInt::from_u32(a), BigInt::from_u32(b)],
+                )
+                .unwrap();
+
+            backend
+                .prove(
+                    &exec,
+                    &[
+                        BigInt::from_u32(c),
+                        BigInt::from_u32(a),
+                        BigInt::from_u32(b),
+                    ],
+                )
+                .unwrap()
+        };
+
+        let verify = |proof: &Proof, c: u32| {
+            let exec = backend
+                .jit_verifier(&prog, &[], &[BigInt::from_u32(c)], &[])
+                .unwrap();
+
+            backend.verify(&exec, proof)
+        };
+
+        // 10 * 4 + 2 == 42
+        let proof = prove(10, 4, 2);
+        verify(&proof, 2).unwrap();
+
+        // The proof doesn't hold for a different public input.
+        assert!(verify(&proof, 3).is_err());
+
+        // A verifier-only backend accepts the same proof.
+        let verifier = Groth16Backend::from_verifying_key(backend.verifying_key());
+        let exec = verifier
+            .jit_verifier(&prog, &[], &[BigInt::from_u32(2)], &[])
+            .unwrap();
+        verifier.verify(&exec, &proof).unwrap();
+        assert!(verifier.prove(&exec, &[]).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     BackendField, BigInt, Error, Gadget, Result,
 };
 use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction, Graph};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sunscreen_compiler_common::{
     forward_traverse, forward_traverse_mut,
     transforms::{GraphTransforms, Transform},
@@ -73,6 +74,13 @@ pub enum Operation {
      * A constant field element.
      */
     Constant(BigInt),
+
+    /**
+     * Reveal the node's parent as the given public output. Mechanically,
+     * the prover computes the output's value and both the prover and
+     * verifier constrain the parent to equal it.
+     */
+    Output(usize),
 }
 
 impl Hash for Operation {
@@ -110,6 +118,10 @@ impl Hash for Operation {
                 state.write_u8(10);
                 x.hash(state);
             }
+            Self::Output(x) => {
+                state.write_u8(11);
+                x.hash(state);
+            }
         }
     }
 }
@@ -127,6 +139,7 @@ impl PartialEq for Operation {
             (Self::Sub, Self::Sub) => true,
             (Self::Mul, Self::Mul) => true,
             (Self::Neg, Self::Neg) => true,
+            (Self::Output(x), Self::Output(y)) => x == y,
             _ => false,
         }
     }
@@ -148,10 +161,138 @@ impl Debug for Operation {
             Self::Sub => write!(f, "Sub"),
             Self::Mul => write!(f, "Mul"),
             Self::Neg => write!(f, "Neg"),
+            Self::Output(x) => write!(f, "Output({x})"),
         }
     }
 }
 
+#[derive(Serialize, Deserialize)]
+/**
+ * The serialized form of an [`Operation`]. Gadgets can't be serialized, so
+ * we record their name and arity instead.
+ */
+enum SerializedOperation {
+    PrivateInput(usize),
+    PublicInput(usize),
+    ConstantInput(usize),
+    HiddenInput(usize),
+    InvokeGadget {
+        name: String,
+        gadget_input_count: usize,
+        hidden_input_count: usize,
+    },
+    Add,
+    Mul,
+    Sub,
+    Neg,
+    Constraint(BigInt),
+    Constant(BigInt),
+    Output(usize),
+}
+
+impl Serialize for Operation {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let op = match self {
+            Self::PrivateInput(x) => SerializedOperation::PrivateInput(*x),
+            Self::PublicInput(x) => SerializedOperation::PublicInput(*x),
+            Self::ConstantInput(x) => SerializedOperation::ConstantInput(*x),
+            Self::HiddenInput(x) => SerializedOperation::HiddenInput(*x),
+            Self::InvokeGadget(g) => SerializedOperation::InvokeGadget {
+                name: g.debug_name().to_owned(),
+                gadget_input_count: g.gadget_input_count(),
+                hidden_input_count: g.hidden_input_count(),
+            },
+            Self::Add => SerializedOperation::Add,
+            Self::Mul => SerializedOperation::Mul,
+            Self::Sub => SerializedOperation::Sub,
+            Self::Neg => SerializedOperation::Neg,
+            Self::Constraint(x) => SerializedOperation::Constraint(*x),
+            Self::Constant(x) => SerializedOperation::Constant(*x),
+            Self::Output(x) => SerializedOperation::Output(*x),
+        };
+
+        op.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Operation {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let op = match SerializedOperation::deserialize(deserializer)? {
+            SerializedOperation::PrivateInput(x) => Self::PrivateInput(x),
+            SerializedOperation::PublicInput(x) => Self::PublicInput(x),
+            SerializedOperation::ConstantInput(x) => Self::ConstantInput(x),
+            SerializedOperation::HiddenInput(x) => Self::HiddenInput(x),
+            SerializedOperation::InvokeGadget {
+                name,
+                gadget_input_count,
+                hidden_input_count,
+            } => Self::InvokeGadget(Arc::new(OpaqueGadget {
+                name,
+                gadget_input_count,
+                hidden_input_count,
+            })),
+            SerializedOperation::Add => Self::Add,
+            SerializedOperation::Mul => Self::Mul,
+            SerializedOperation::Sub => Self::Sub,
+            SerializedOperation::Neg => Self::Neg,
+            SerializedOperation::Constraint(x) => Self::Constraint(x),
+            SerializedOperation::Constant(x) => Self::Constant(x),
+            SerializedOperation::Output(x) => Self::Output(x),
+        };
+
+        Ok(op)
+    }
+}
+
+/**
+ * Stands in for a gadget in a deserialized [`CompiledZkpProgram`].
+ *
+ * # Remarks
+ * Verifying a proof never computes a gadget's hidden inputs, so a
+ * deserialized program can verify proofs. However, proving requires the
+ * original gadget, so [`jit_prover`] fails on these programs.
+ */
+struct OpaqueGadget {
+    name: String,
+    gadget_input_count: usize,
+    hidden_input_count: usize,
+}
+
+impl Gadget for OpaqueGadget {
+    fn gen_circuit(
+        &self,
+        _gadget_inputs: &[NodeIndex],
+        _hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        unreachable!("Gadget {} was deserialized after compilation.", self.name)
+    }
+
+    fn compute_inputs(&self, _gadget_inputs: &[BigInt]) -> Result<Vec<BigInt>> {
+        Err(Error::gadget_error(&format!(
+            "Gadget {} was deserialized and can't compute its hidden inputs. Deserialized ZKP programs can only verify proofs.",
+            self.name
+        )))
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        self.gadget_input_count
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        self.hidden_input_count
+    }
+
+    fn debug_name(&self) -> &'static str {
+        "OpaqueGadget"
+    }
+}
+
 impl OperationTrait for Operation {
     fn is_binary(&self) -> bool {
         matches!(self, Operation::Add | Operation::Sub | Operation::Mul)
@@ -162,7 +303,7 @@ impl OperationTrait for Operation {
     }
 
     fn is_unary(&self) -> bool {
-        matches!(self, Operation::Neg)
+        matches!(self, Operation::Neg | Operation::Output(_))
     }
 
     fn is_unordered(&self) -> bool {
@@ -227,6 +368,17 @@ fn validate_zkp_program(prog: &CompiledZkpProgram) -> Result<()> {
     public_inputs.sort();
     assert_range(&private_inputs, "private input")?;
 
+    let mut outputs = prog
+        .node_weights()
+        .filter_map(|x| match x.operation {
+            Operation::Output(x) => Some(x),
+            _ => None,
+        })
+        .collect::<Vec<usize>>();
+
+    outputs.sort();
+    assert_range(&outputs, "output")?;
+
     // TODO: check for cycles, assert each node has correct inputs.
 
     Ok(())
@@ -234,6 +386,7 @@ fn validate_zkp_program(prog: &CompiledZkpProgram) -> Result<()> {
 
 /**
  * Just in time compile a [`CompiledZkpProgram`] into an [`ExecutableZkpProgram`] for creating proofs.
+ * Additionally returns the values of the program's [`Operation::Output`]s.
  *
  * # Remarks
  * This method computes [`Gadget`]'s hidden inputs from their gadget inputs. To do this,
@@ -244,7 +397,7 @@ pub fn jit_prover<U>(
     constant_inputs: &[U],
     public_inputs: &[U],
     private_inputs: &[U],
-) -> Result<ExecutableZkpProgram>
+) -> Result<(ExecutableZkpProgram, Vec<BigInt>)>
 where
     U: BackendField,
 {
@@ -269,6 +422,7 @@ where
     validate_zkp_program(&prog)?;
 
     let mut node_outputs: HashMap<NodeIndex, U> = HashMap::new();
+    let mut outputs: HashMap<usize, BigInt> = HashMap::new();
 
     // Run the graph as a computation (not a ZKP) to compute all the
     // gadget hidden input values.
@@ -338,6 +492,11 @@ where
             Operation::Constant(x) => {
                 node_outputs.insert(id, U::try_from(x)?);
             }
+            Operation::Output(x) => {
+                let parent = query.get_unary_operand(id)?;
+
+                outputs.insert(x, node_outputs[&parent].clone().zkp_into());
+            }
             Operation::InvokeGadget(ref g) => {
                 // Have the gadget tell us what the values are for the
                 // hidden inputs and assign their value.
@@ -424,12 +583,22 @@ where
         Ok::<_, Error>(())
     })?;
 
-    jit_common(prog, constant_inputs, public_inputs, Some(node_outputs))
+    // validate_zkp_program ensures the output indices form a range.
+    let outputs = (0..outputs.len())
+        .map(|i| outputs[&i])
+        .collect::<Vec<BigInt>>();
+
+    constrain_outputs(&mut prog, &outputs)?;
+
+    let prog = jit_common(prog, constant_inputs, public_inputs, Some(node_outputs))?;
+
+    Ok((prog, outputs))
 }
 
 /**
  * Just in time compile a [`CompiledZkpProgram`] into an [`ExecutableZkpProgram`] for
- * verifying proofs.
+ * verifying proofs. The program's [`Operation::Output`]s are constrained to
+ * equal the claimed `outputs`.
  *
  * # Remarks
  * This version doesn't compute hidden inputs, as the verifier doesn't know them.
@@ -438,6 +607,7 @@ pub fn jit_verifier<U>(
     prog: &CompiledZkpProgram,
     constant_inputs: &[U],
     public_inputs: &[U],
+    outputs: &[U],
 ) -> Result<ExecutableZkpProgram>
 where
     U: BackendField,
@@ -448,6 +618,13 @@ where
     verify_constant_inputs(&prog, constant_inputs)?;
     constrain_public_inputs(&mut prog, public_inputs)?;
 
+    let outputs = outputs
+        .iter()
+        .map(|x| x.clone().zkp_into())
+        .collect::<Vec<BigInt>>();
+
+    constrain_outputs(&mut prog, &outputs)?;
+
     jit_common(prog, constant_inputs, public_inputs, None)
 }
 
@@ -496,6 +673,7 @@ where
                 None => NodeInfo::new(ExecOperation::HiddenInput(None)),
             },
             Operation::InvokeGadget(_) => unreachable!("Not all gadgets processed and removed"),
+            Operation::Output(_) => unreachable!("Not all outputs processed and removed"),
         },
         |_, e| *e,
     );
@@ -577,3 +755,45 @@ where
 
     Ok(())
 }
+
+/**
+ * Replaces each [`Operation::Output`] node with a constraint fixing its
+ * parent to the corresponding value in `outputs`.
+ */
+fn constrain_outputs(prog: &mut CompiledZkpProgram, outputs: &[BigInt]) -> Result<()> {
+    let output_nodes = prog
+        .node_indices()
+        .filter_map(|id| match prog[id].operation {
+            Operation::Output(x) => Some((id, x)),
+            _ => None,
+        })
+        .collect::<Vec<(NodeIndex, usize)>>();
+
+    if outputs.len() != output_nodes.len() {
+        return Err(Error::inputs_mismatch(&format!(
+            "Expected {} outputs, found {}",
+            output_nodes.len(),
+            outputs.len()
+        )));
+    }
+
+    // Add all the constraints before removing any outputs so the prover
+    // and verifier assign the new nodes identical indices.
+    for (id, x) in &output_nodes {
+        let parent = prog
+            .neighbors_directed(*id, Direction::Incoming)
+            .next()
+            .ok_or(GraphQueryError::NotUnaryOperation)?;
+
+        let constraint = prog.add_node(NodeInfo {
+            operation: Operation::Constraint(outputs[*x]),
+        });
+        prog.add_edge(parent, constraint, EdgeInfo::Unordered);
+    }
+
+    for (id, _) in output_nodes {
+        prog.remove_node(id);
+    }
+
+    Ok(())
+}
@@ -10,6 +10,12 @@
  */
 pub mod bulletproofs;
 
+#[cfg(feature = "groth16")]
+/**
+ * Types for working with Groth16 over BN254 as the ZKP backend.
+ */
+pub mod groth16;
+
 mod error;
 mod exec;
 mod jit;
@@ -150,6 +156,117 @@ pub enum Proof {
          */
         data: Vec<u8>,
     },
+
+    // Feature-gated variants go last so enabling them doesn't change the
+    // serialized index of the variants above.
+    #[cfg(feature = "groth16")]
+    /**
+     * A Groth16 proof.
+     */
+    Groth16(Box<groth16::Groth16Proof>),
+}
+
+impl Proof {
+    /**
+     * The [`tag`](Proof::tag) of a [`Proof::Bulletproofs`].
+     */
+    pub const BULLETPROOFS_TAG: u8 = 0;
+
+    /**
+     * The [`tag`](Proof::tag) of a [`Proof::Groth16`].
+     */
+    pub const GROTH16_TAG: u8 = 1;
+
+    /**
+     * The [`tag`](Proof::tag) of a [`Proof::Custom`].
+     */
+    pub const CUSTOM_TAG: u8 = 2;
+
+    /**
+     * Returns a tag identifying this proof's system.
+     *
+     * # Remarks
+     * Unlike serde's variant index, the tag depends on neither the order
+     * of [`Proof`]'s variants nor which backend features are enabled, so
+     * it's stable across releases. Use it with
+     * [`serialize_contents`](Proof::serialize_contents) and
+     * [`deserialize_contents`](Proof::deserialize_contents) to build
+     * encodings other releases can read.
+     */
+    pub fn tag(&self) -> u8 {
+        match self {
+            #[cfg(feature = "bulletproofs")]
+            Self::Bulletproofs(_) => Self::BULLETPROOFS_TAG,
+            #[cfg(feature = "groth16")]
+            Self::Groth16(_) => Self::GROTH16_TAG,
+            Self::Custom { .. } => Self::CUSTOM_TAG,
+        }
+    }
+
+    /**
+     * Whether this build can decode proofs with the given
+     * [`tag`](Proof::tag). This is false for unknown tags and for the
+     * tags of backends whose features are disabled.
+     */
+    pub fn is_tag_supported(tag: u8) -> bool {
+        match tag {
+            Self::BULLETPROOFS_TAG => cfg!(feature = "bulletproofs"),
+            Self::GROTH16_TAG => cfg!(feature = "groth16"),
+            Self::CUSTOM_TAG => true,
+            _ => false,
+        }
+    }
+
+    /**
+     * Serializes this proof without its [`tag`](Proof::tag).
+     */
+    pub fn serialize_contents<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            #[cfg(feature = "bulletproofs")]
+            Self::Bulletproofs(proof) => proof.serialize(serializer),
+            #[cfg(feature = "groth16")]
+            Self::Groth16(proof) => proof.serialize(serializer),
+            Self::Custom { name, data } => (name, data).serialize(serializer),
+        }
+    }
+
+    /**
+     * Deserializes the contents of a proof with the given
+     * [`tag`](Proof::tag), as written by
+     * [`serialize_contents`](Proof::serialize_contents).
+     *
+     * # Remarks
+     * Fails if [`is_tag_supported`](Proof::is_tag_supported) returns false
+     * for `tag`.
+     */
+    pub fn deserialize_contents<'de, D>(
+        tag: u8,
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        match tag {
+            #[cfg(feature = "bulletproofs")]
+            Self::BULLETPROOFS_TAG => Ok(Self::Bulletproofs(Box::deserialize(deserializer)?)),
+            #[cfg(feature = "groth16")]
+            Self::GROTH16_TAG => Ok(Self::Groth16(Box::deserialize(deserializer)?)),
+            Self::CUSTOM_TAG => {
+                let (name, data) = <(String, Vec<u8>)>::deserialize(deserializer)?;
+
+                Ok(Self::Custom { name, data })
+            }
+            _ => Err(D::Error::custom(format!(
+                "Unsupported proof system tag {}",
+                tag
+            ))),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
@@ -193,6 +310,24 @@ impl Ord for BigInt {
     }
 }
 
+impl Serialize for BigInt {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_words().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInt {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Self::from_words(<[u64; 8]>::deserialize(deserializer)?))
+    }
+}
+
 impl ConditionallySelectable for BigInt {
     fn conditional_select(a: &Self, b: &Self, choice: crypto_bigint::subtle::Choice) -> Self {
         Self(U512::conditional_select(&a.0, &b.0, choice))
@@ -343,6 +478,27 @@ pub trait ZkpBackend {
      */
     type Field: BackendField;
 
+    /**
+     * Precomputed public data this backend reuses when proving and
+     * verifying circuits up to a given size, e.g. Bulletproofs generators.
+     * Backends without such data use `()`.
+     */
+    type Generators: Clone;
+
+    /**
+     * Returns generators that can prove and verify circuits with up to
+     * `capacity` multiplication gates, creating and caching them if the
+     * backend hasn't cached a large enough set.
+     */
+    fn generators(&self, capacity: usize) -> Self::Generators;
+
+    /**
+     * Adds the given generators to this backend's cache, keyed by their
+     * capacity. Proofs and verifications of circuits they fit then reuse
+     * them.
+     */
+    fn add_generators(&self, generators: Self::Generators);
+
     /**
      * Create a proof for the given executable Sunscreen
      * program with the given inputs.
@@ -355,10 +511,32 @@ pub trait ZkpBackend {
      */
     fn verify(&self, graph: &ExecutableZkpProgram, proof: &Proof) -> Result<()>;
 
+    /**
+     * Verify each of the given proofs against its executable Sunscreen
+     * program.
+     *
+     * # Remarks
+     * Returns [`Error::BatchVerificationFailed`] with the index of the
+     * first proof that fails.
+     *
+     * This verifies each proof in turn, so it costs the same as calling
+     * [`verify`](ZkpBackend::verify) on every proof; it does not
+     * amortize any work across the batch.
+     */
+    fn verify_batch(&self, batch: &[(ExecutableZkpProgram, &Proof)]) -> Result<()> {
+        for (i, (graph, proof)) in batch.iter().enumerate() {
+            self.verify(graph, proof)
+                .map_err(|e| Error::batch_verification_failed(i, e))?;
+        }
+
+        Ok(())
+    }
+
     /**
      * JIT the given frontend-compiled ZKP program
      * to an executable Sunscreen program for use by
-     * a prover.
+     * a prover. Additionally returns the values of the
+     * program's public outputs.
      *
      * # Remarks
      * Implementors should generally just call
@@ -371,11 +549,13 @@ pub trait ZkpBackend {
         constant_inputs: &[BigInt],
         public_inputs: &[BigInt],
         private_inputs: &[BigInt],
-    ) -> Result<ExecutableZkpProgram>;
+    ) -> Result<(ExecutableZkpProgram, Vec<BigInt>)>;
 
     /**
      * JIT the given backend-compiled ZKP program to an
      * executable Sunscreen program for use by a verifier.
+     * The program's public outputs are constrained to equal
+     * `outputs`.
      *
      * # Remarks
      * Implementors should generally just call
@@ -387,6 +567,7 @@ pub trait ZkpBackend {
         prog: &CompiledZkpProgram,
         constant_inputs: &[BigInt],
         public_inputs: &[BigInt],
+        outputs: &[BigInt],
     ) -> Result<ExecutableZkpProgram>;
 }
 